@@ -1,35 +1,202 @@
 //! Build script for the runtime.
+//!
+//! Memory layout is selected with cargo features:
+//!
+//! - `layout-ocm`: everything is placed to on-chip memory (default).
+//! - `layout-ddr`: everything is placed to DDR memory.
+//! - `layout-split`: stacks are placed to on-chip memory and everything else to DDR memory.
+//!
+//! Section `.ocm_fast` is always placed to on-chip memory.
+//! Section `.uncached` is always placed to on-chip memory mapped high,
+//! which is the only memory startup code maps as uncached.
+//! Initial values of `.data` are loaded after code and copied to their run address by startup code.
+//!
+//! Stack and heap sizes are read from environment variables,
+//! which can be set in `.cargo/config.toml`'s `[env]` table:
+//!
+//! - `PYNQ_Z1_STACK_SIZE_SYSTEM`
+//! - `PYNQ_Z1_STACK_SIZE_IRQ`
+//! - `PYNQ_Z1_STACK_SIZE_FIQ`
+//! - `PYNQ_Z1_STACK_SIZE_SUPERVISOR`
+//! - `PYNQ_Z1_STACK_SIZE_ABORT`
+//! - `PYNQ_Z1_STACK_SIZE_UNDEFINED`
+//! - `PYNQ_Z1_HEAP_SIZE`, which defaults to zero without `alloc` feature
+//!
+//! Sizes are in bytes, either decimal or hexadecimal with `0x` prefix.
+
+use std::env::var;
+
+/// Memory region.
+#[derive(Clone, Copy)]
+enum Region {
+    /// On-chip memory.
+    Ocm,
+
+    /// DDR memory.
+    Ddr,
+
+    /// On-chip memory mapped high.
+    OcmHigh,
+}
+
+impl Region {
+    /// Region's name in the linker script.
+    const fn name(self) -> &'static str {
+        match self {
+            Self::Ocm => "OCM",
+            Self::Ddr => "DDR",
+            Self::OcmHigh => "OCM_HIGH",
+        }
+    }
+}
+
+/// Memory regions of output sections.
+struct Layout {
+    /// Code, read-only data and registered handlers.
+    text: Region,
+
+    /// Initialized and zero-initialized data.
+    data: Region,
+
+    /// Initial values of initialized data, copied to data region by startup code.
+    data_load: Region,
+
+    /// Buffers shared with bus masters.
+    uncached: Region,
+
+    /// Heap.
+    heap: Region,
+
+    /// Stacks of each processor mode.
+    stack: Region,
+}
+
+impl Layout {
+    /// Select layout with cargo features.
+    fn from_features() -> Self {
+        let ocm = var("CARGO_FEATURE_LAYOUT_OCM").is_ok();
+        let ddr = var("CARGO_FEATURE_LAYOUT_DDR").is_ok();
+        let split = var("CARGO_FEATURE_LAYOUT_SPLIT").is_ok();
+        match (ocm, ddr, split) {
+            (_, false, false) => Self::uniform(Region::Ocm),
+            (false, true, false) => Self::uniform(Region::Ddr),
+            (false, false, true) => Self {
+                stack: Region::Ocm,
+                ..Self::uniform(Region::Ddr)
+            },
+            _ => panic!("select at most one of features layout-ocm, layout-ddr and layout-split"),
+        }
+    }
+
+    /// Place every section except uncached buffers to given region.
+    const fn uniform(region: Region) -> Self {
+        Self {
+            text: region,
+            data: region,
+            data_load: region,
+            uncached: Region::OcmHigh,
+            heap: region,
+            stack: region,
+        }
+    }
+}
+
+/// Read size from environment variable.
+fn size(name: &str, default: u32) -> u32 {
+    println!("cargo:rerun-if-env-changed={name}");
+    let Ok(value) = var(name) else {
+        return default;
+    };
+    let value = value.trim().replace('_', "");
+    let parsed = match value.strip_prefix("0x") {
+        Some(hexadecimal) => u32::from_str_radix(hexadecimal, 16),
+        None => value.parse(),
+    };
+    parsed.unwrap_or_else(|_| panic!("{name} is not a valid size: {value}"))
+}
+
+/// Generate memory layout included by the linker script.
+fn generate_layout() -> String {
+    let layout = Layout::from_features();
+    // Heap is reserved only for the global allocator.
+    let heap_size = if var("CARGO_FEATURE_ALLOC").is_ok() {
+        0x4000
+    } else {
+        0
+    };
+    let sizes = [
+        (
+            "STACK_SIZE_SYSTEM",
+            size("PYNQ_Z1_STACK_SIZE_SYSTEM", 0x800),
+        ),
+        ("STACK_SIZE_IRQ", size("PYNQ_Z1_STACK_SIZE_IRQ", 0x800)),
+        ("STACK_SIZE_FIQ", size("PYNQ_Z1_STACK_SIZE_FIQ", 0x400)),
+        (
+            "STACK_SIZE_SUPERVISOR",
+            size("PYNQ_Z1_STACK_SIZE_SUPERVISOR", 0x400),
+        ),
+        ("STACK_SIZE_ABORT", size("PYNQ_Z1_STACK_SIZE_ABORT", 0x400)),
+        (
+            "STACK_SIZE_UNDEFINED",
+            size("PYNQ_Z1_STACK_SIZE_UNDEFINED", 0x400),
+        ),
+        ("HEAP_SIZE", size("PYNQ_Z1_HEAP_SIZE", heap_size)),
+    ];
+    let regions = [
+        ("REGION_TEXT", layout.text),
+        ("REGION_DATA", layout.data),
+        ("REGION_DATA_LOAD", layout.data_load),
+        ("REGION_UNCACHED", layout.uncached),
+        ("REGION_HEAP", layout.heap),
+        ("REGION_STACK", layout.stack),
+    ];
+
+    let mut script = String::from("/* generated by build script */\n");
+    for (name, size) in sizes {
+        script += &format!("{name} = {size:#X};\n");
+    }
+    for (alias, region) in regions {
+        script += &format!("REGION_ALIAS(\"{alias}\", {});\n", region.name());
+    }
+    script
+}
 
 fn main() {
-    use std::env::var;
     use std::fs;
     use std::path::PathBuf;
 
+    println!("cargo:rerun-if-changed=src/");
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=examples/");
+    println!("cargo:rerun-if-changed=linker_script.ld");
+
     // Check build target.
+    // Drivers can be built for the host with a simulated register bus,
+    // but the runtime is built only for the board.
     let target_expected = "armv7a-none-eabi";
     let target_actual = var("TARGET").unwrap();
     if target_actual != target_expected {
-        panic!(
-            "attempted to build for target {target_actual} but correct target is {target_expected}"
-        );
+        return;
     }
 
-    // Move linker script so that linker can find it.
+    // Move linker script and generated memory layout so that linker can find them.
     let out_dir = PathBuf::from(var("OUT_DIR").unwrap());
     fs::write(
         out_dir.join("linker_script.ld"),
         include_bytes!("linker_script.ld"),
     )
     .unwrap();
+    fs::write(out_dir.join("layout.ld"), generate_layout()).unwrap();
     println!("cargo:rustc-link-search={}", out_dir.display());
 
-    println!("cargo:rerun-if-changed=src/");
-    println!("cargo:rerun-if-changed=build.rs");
-    println!("cargo:rerun-if-changed=examples/");
-    println!("cargo:rerun-if-changed=linker_script.ld");
-    cc::Build::new()
+    let mut build = cc::Build::new();
+    build
         .file("src/runtime.S")
         .compiler("arm-none-eabi-gcc")
-        .archiver("arm-none-eabi-ar")
-        .compile("runtime");
+        .archiver("arm-none-eabi-ar");
+    // Select interrupt request entry stub.
+    if var("CARGO_FEATURE_NESTED_INTERRUPTS").is_ok() {
+        build.define("NESTED_INTERRUPTS", None);
+    }
+    build.compile("runtime");
 }
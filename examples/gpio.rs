@@ -5,39 +5,37 @@
 
 use core::panic::PanicInfo;
 use pynq_z1_bsp::common::instruction::nop;
+use pynq_z1_bsp::entry;
 use pynq_z1_bsp::peripheral::gpio::PinDirection;
-use pynq_z1_bsp::peripheral::gpio::GPIO;
-use pynq_z1_bsp::peripheral::uart::UART0;
+use pynq_z1_bsp::peripheral::Peripherals;
 
-#[no_mangle]
-#[inline(never)]
-#[link_section = ".text"]
-fn main() {
-    unsafe {
-        GPIO.set_mio_direction(0, PinDirection::Input);
-        GPIO.set_mio_direction(1, PinDirection::Output);
-        GPIO.toggle_mio_interrupt(0, true);
-        GPIO.toggle_mio_interrupt(1, true);
+#[entry]
+fn main() -> ! {
+    let peripherals = Peripherals::take().unwrap();
+    let banks = peripherals.gpio.split();
+    let mio = banks.mio0;
+    mio.set_direction(0, PinDirection::Input).unwrap();
+    mio.set_direction(1, PinDirection::Output).unwrap();
+    mio.toggle_interrupt(0, true).unwrap();
+    mio.toggle_interrupt(1, true).unwrap();
 
-        UART0.configure().unwrap();
-        UART0.toggle(true);
-    }
+    let uart = peripherals.uart0;
+    uart.configure(&peripherals.slcr).unwrap();
+    uart.toggle(true);
 
     loop {
         for _ in 0..1_000 {
             nop();
         }
-        unsafe {
-            if GPIO.read_mio_input(0) {
-                UART0.transmit_line("MIO pin 0 is high.");
-            } else {
-                UART0.transmit_line("MIO pin 0 is low.");
-            }
-            if GPIO.read_mio_output(1) {
-                GPIO.write_mio_output(1, false);
-            } else {
-                GPIO.write_mio_output(1, true);
-            }
+        if mio.read_input(0).unwrap() {
+            uart.transmit_line("MIO pin 0 is high.");
+        } else {
+            uart.transmit_line("MIO pin 0 is low.");
+        }
+        if mio.read_output(1).unwrap() {
+            mio.write_output(1, false).unwrap();
+        } else {
+            mio.write_output(1, true).unwrap();
         }
     }
 }
@@ -5,26 +5,26 @@
 
 use core::panic::PanicInfo;
 use pynq_z1_bsp::common::instruction::nop;
-use pynq_z1_bsp::peripheral::uart::UART0;
+use pynq_z1_bsp::entry;
+use pynq_z1_bsp::peripheral::Peripherals;
 
-#[no_mangle]
-#[inline(never)]
-#[link_section = ".text"]
-fn main() {
-    unsafe {
-        UART0.configure().unwrap();
-        UART0.toggle(true);
-    }
+#[entry]
+fn main() -> ! {
+    let peripherals = Peripherals::take().unwrap();
+    let uart = peripherals.uart0;
+    uart.configure(&peripherals.slcr).unwrap();
+    uart.toggle(true);
     loop {
         for _ in 0..1_000 {
             nop();
         }
-        unsafe { UART0.transmit_line("Hello, World!") };
+        uart.transmit_line("Hello, World!");
     }
 }
 
 #[panic_handler]
 fn panic(_info: &PanicInfo) -> ! {
-    unsafe { UART0.transmit_line("Hello, World!") };
+    let peripherals = unsafe { Peripherals::steal() };
+    peripherals.uart0.transmit_line("Hello, World!");
     loop {}
 }
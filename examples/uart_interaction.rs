@@ -5,95 +5,63 @@
 
 #[no_mangle]
 #[inline(never)]
-fn read_uart() {
-    use pynq_z1_bsp::peripheral::uart::UART0;
-    use pynq_z1_bsp::sprintln;
-
-    while let Some(byte) = unsafe { UART0.try_receive_byte() } {
-        let character = char::from(byte);
-        sprintln!("received: {character}");
-    }
-}
-
-#[no_mangle]
-#[inline(never)]
-fn handle_uart0() {
-    use pynq_z1_bsp::peripheral::uart::Interrupt;
-    use pynq_z1_bsp::peripheral::uart::ReceiverInterrupt;
-    use pynq_z1_bsp::peripheral::uart::UART0;
-
-    let causes = unsafe { UART0.read_interrupt_causes() };
-    if causes.receiver_fifo_trigger || causes.receiver_fifo_full {
-        read_uart();
-        unsafe {
-            UART0.clear_interrupt(Interrupt::Receiver(ReceiverInterrupt::FifoTrigger));
-            UART0.clear_interrupt(Interrupt::Receiver(ReceiverInterrupt::FifoFull));
-        }
-    }
-}
-
-#[no_mangle]
-#[inline(never)]
-fn setup() {
+fn setup() -> pynq_z1_bsp::peripheral::uart::buffered::BufferedUart {
+    use pynq_z1_bsp::interrupt::cpu;
     use pynq_z1_bsp::interrupt::gic::InterruptTargets;
-    use pynq_z1_bsp::interrupt::gic::GIC;
-    use pynq_z1_bsp::interrupt::handler::irq::IRQ_HANDLER;
     use pynq_z1_bsp::interrupt::icc::InterruptPriorityFilter;
-    use pynq_z1_bsp::interrupt::icc::ICC;
     use pynq_z1_bsp::interrupt::irq_numbers::Irq;
     use pynq_z1_bsp::interrupt::irq_numbers::SpiIrq;
-    use pynq_z1_bsp::peripheral::uart::Interrupt;
-    use pynq_z1_bsp::peripheral::uart::ReceiverInterrupt;
-    use pynq_z1_bsp::peripheral::uart::UART0;
+    use pynq_z1_bsp::peripheral::uart::buffered::BufferedUart;
+    use pynq_z1_bsp::peripheral::Peripherals;
 
-    unsafe {
-        IRQ_HANDLER.handle_uart0 = handle_uart0;
+    let peripherals = Peripherals::take().unwrap();
 
-        // TODO: GIC.reset();
-        GIC.toggle_interrupt(Irq::Spi(SpiIrq::Uart0), true);
-        GIC.set_shared_peripheral_interrupt_targets(SpiIrq::Uart0, InterruptTargets::Cpu0);
-        GIC.toggle(true);
+    let gic = peripherals.gic;
+    // TODO: gic.reset();
+    gic.toggle_interrupt(Irq::Spi(SpiIrq::Uart0), true);
+    gic.set_shared_peripheral_interrupt_targets(SpiIrq::Uart0, InterruptTargets::Cpu0);
+    gic.toggle(true);
 
-        ICC.set_interrupt_priority_filter(InterruptPriorityFilter::AllowAll);
-        ICC.toggle(true);
+    let icc = peripherals.icc;
+    icc.set_interrupt_priority_filter(InterruptPriorityFilter::AllowAll);
+    icc.toggle(true);
 
-        // TODO: handle
-        let _ = UART0.configure();
-        UART0.toggle_interrupt(Interrupt::Receiver(ReceiverInterrupt::FifoTrigger), true);
-        UART0.toggle_interrupt(Interrupt::Receiver(ReceiverInterrupt::FifoFull), true);
-        let _ = UART0.set_receiver_fifo_trigger_value(1);
-        UART0.toggle(true);
+    let uart = peripherals.uart0;
+    // TODO: handle
+    let _ = uart.configure(&peripherals.slcr);
+    // Buffered UART installs its own interrupt handler.
+    let uart = BufferedUart::new(uart);
 
-        // TODO: remove this
-        core::arch::asm!(
-            // Read CPSR.
-            "mrs {temp}, cpsr",
-            // Enable interrupts.
-            "bic {temp}, {temp}, 0b10000000",
-            // Write CPSR.
-            "msr cpsr, {temp}",
-            temp = in(reg) 0,
-        );
-    }
+    // Safety:
+    // Interrupt handlers are installed and no critical section is active.
+    unsafe { cpu::enable_irq() };
+
+    uart
 }
 
-#[no_mangle]
-#[inline(never)]
-fn main() {
-    setup();
-    loop {}
+#[pynq_z1_bsp::entry]
+fn main() -> ! {
+    let mut uart = setup();
+    let mut buffer = [0; 16];
+    loop {
+        // Echo received bytes back.
+        let count = uart.read(&mut buffer);
+        let mut sent = 0;
+        while sent < count {
+            sent += uart.write(&buffer[sent..count]);
+        }
+    }
 }
 
 #[panic_handler]
 fn panic(info: &core::panic::PanicInfo) -> ! {
-    use pynq_z1_bsp::peripheral::uart::UART0;
-    use pynq_z1_bsp::sprintln;
+    use pynq_z1_bsp::esprintln;
+    use pynq_z1_bsp::peripheral::Peripherals;
 
-    unsafe {
-        // TODO: handle
-        let _ = UART0.configure();
-        UART0.toggle(true);
-    }
-    sprintln!("Panic: {info}");
+    let peripherals = unsafe { Peripherals::steal() };
+    // TODO: handle
+    let _ = peripherals.uart0.configure(&peripherals.slcr);
+    peripherals.uart0.toggle(true);
+    esprintln!("Panic: {info}");
     loop {}
 }
@@ -0,0 +1,381 @@
+//! Attribute macros for `pynq-z1-bsp`.
+//!
+//! Macros are re-exported by `pynq-z1-bsp`, use them from there.
+//!
+//! # How to use?
+//!
+//! ```ignore
+//! use pynq_z1_bsp::entry;
+//! use pynq_z1_bsp::exception;
+//! use pynq_z1_bsp::interrupt;
+//!
+//! #[entry]
+//! fn main() -> ! {
+//!     loop {}
+//! }
+//!
+//! #[interrupt(Uart0)]
+//! fn handle_uart0() {}
+//!
+//! #[exception(DataAbort)]
+//! fn handle_data_abort(context: &mut ExceptionContext) {}
+//! ```
+
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::spanned::Spanned;
+use syn::Error;
+use syn::Ident;
+use syn::ItemFn;
+use syn::ReturnType;
+use syn::Type;
+
+/// Software generated interrupts, see `SgiIrq`.
+const SGI_NAMES: [&str; 16] = [
+    "Sgi0", "Sgi1", "Sgi2", "Sgi3", "Sgi4", "Sgi5", "Sgi6", "Sgi7", "Sgi8", "Sgi9", "Sgi10",
+    "Sgi11", "Sgi12", "Sgi13", "Sgi14", "Sgi15",
+];
+
+/// Private peripheral interrupts, see `PpiIrq`.
+const PPI_NAMES: [&str; 5] = ["GlobalTimer", "NFiq", "CpuPrivateTimer", "Awdt", "NIrq"];
+
+/// Mark function as the program's entry point.
+///
+/// Function must not take arguments and must never return.
+/// Runtime calls it after memory and stacks are initialized.
+#[proc_macro_attribute]
+pub fn entry(arguments: TokenStream, item: TokenStream) -> TokenStream {
+    expand(expand_entry(arguments.into(), item.into()))
+}
+
+/// Register function as an exception handler.
+///
+/// Argument is a variant of `Exception`.
+/// Function must have signature `fn(&mut ExceptionContext)`.
+/// Registered handler takes precedence over `EXCEPTION_HANDLER`.
+#[proc_macro_attribute]
+pub fn exception(arguments: TokenStream, item: TokenStream) -> TokenStream {
+    expand(expand_exception(arguments.into(), item.into()))
+}
+
+/// Register function as an interrupt request handler.
+///
+/// Argument is a variant of `SgiIrq`, `PpiIrq` or `SpiIrq`.
+/// Function must have signature `fn()`.
+/// Registered handler takes precedence over `IRQ_HANDLER`.
+#[proc_macro_attribute]
+pub fn interrupt(arguments: TokenStream, item: TokenStream) -> TokenStream {
+    expand(expand_interrupt(arguments.into(), item.into()))
+}
+
+/// Turn expansion or its error into compiler input.
+fn expand(result: Result<TokenStream2, Error>) -> TokenStream {
+    result.unwrap_or_else(Error::into_compile_error).into()
+}
+
+/// Expand `#[entry]`.
+fn expand_entry(arguments: TokenStream2, item: TokenStream2) -> Result<TokenStream2, Error> {
+    let function: ItemFn = syn::parse2(item)?;
+    if !arguments.is_empty() {
+        return Err(Error::new(
+            Span::call_site(),
+            "`#[entry]` does not take arguments",
+        ));
+    }
+    check_signature(&function, 0)?;
+    let returns_never = matches!(
+        &function.sig.output,
+        ReturnType::Type(_, output) if matches!(**output, Type::Never(_))
+    );
+    if !returns_never {
+        return Err(Error::new(
+            function.sig.span(),
+            "`#[entry]` function must have signature `fn() -> !`",
+        ));
+    }
+
+    let ident = &function.sig.ident;
+    Ok(quote! {
+        #function
+
+        const _: () = {
+            #[export_name = "main"]
+            extern "C" fn __pynq_z1_bsp_entry() -> ! {
+                #ident()
+            }
+        };
+    })
+}
+
+/// Expand `#[exception]`.
+fn expand_exception(arguments: TokenStream2, item: TokenStream2) -> Result<TokenStream2, Error> {
+    let function: ItemFn = syn::parse2(item)?;
+    let exception: Ident = syn::parse2(arguments)?;
+    check_signature(&function, 1)?;
+    if !returns_unit(&function) {
+        return Err(Error::new(
+            function.sig.span(),
+            "`#[exception]` function must have signature `fn(&mut ExceptionContext)`",
+        ));
+    }
+
+    let ident = &function.sig.ident;
+    Ok(quote! {
+        #function
+
+        const _: () = {
+            use ::pynq_z1_bsp::interrupt::handler::exception::Exception;
+            use ::pynq_z1_bsp::interrupt::handler::exception::ExceptionRegistration;
+
+            #[used]
+            #[link_section = ".exception_handlers"]
+            static REGISTRATION: ExceptionRegistration = ExceptionRegistration {
+                exception: Exception::#exception,
+                handler: #ident,
+            };
+        };
+    })
+}
+
+/// Expand `#[interrupt]`.
+fn expand_interrupt(arguments: TokenStream2, item: TokenStream2) -> Result<TokenStream2, Error> {
+    let function: ItemFn = syn::parse2(item)?;
+    let interrupt: Ident = syn::parse2(arguments)?;
+    check_signature(&function, 0)?;
+    if !returns_unit(&function) {
+        return Err(Error::new(
+            function.sig.span(),
+            "`#[interrupt]` function must have signature `fn()`",
+        ));
+    }
+
+    let name = interrupt.to_string();
+    let irq = if SGI_NAMES.contains(&name.as_str()) {
+        quote!(Irq::Sgi(SgiIrq::#interrupt))
+    } else if PPI_NAMES.contains(&name.as_str()) {
+        quote!(Irq::Ppi(PpiIrq::#interrupt))
+    } else {
+        quote!(Irq::Spi(SpiIrq::#interrupt))
+    };
+
+    let ident = &function.sig.ident;
+    Ok(quote! {
+        #function
+
+        const _: () = {
+            use ::pynq_z1_bsp::interrupt::handler::irq::InterruptRegistration;
+            use ::pynq_z1_bsp::interrupt::irq_numbers::Irq;
+            use ::pynq_z1_bsp::interrupt::irq_numbers::PpiIrq;
+            use ::pynq_z1_bsp::interrupt::irq_numbers::SgiIrq;
+            use ::pynq_z1_bsp::interrupt::irq_numbers::SpiIrq;
+
+            #[used]
+            #[link_section = ".interrupt_handlers"]
+            static REGISTRATION: InterruptRegistration = InterruptRegistration {
+                irq: #irq,
+                handler: #ident,
+            };
+        };
+    })
+}
+
+/// Check that function can be called by the runtime.
+fn check_signature(function: &ItemFn, inputs: usize) -> Result<(), Error> {
+    let signature = &function.sig;
+    if signature.constness.is_some()
+        || signature.asyncness.is_some()
+        || signature.abi.is_some()
+        || !signature.generics.params.is_empty()
+        || signature.variadic.is_some()
+    {
+        return Err(Error::new(
+            signature.span(),
+            "function must be a plain function without generics",
+        ));
+    }
+    if signature.inputs.len() != inputs {
+        return Err(Error::new(
+            signature.inputs.span(),
+            format!("function must take {inputs} argument(s)"),
+        ));
+    }
+    Ok(())
+}
+
+/// True if function returns unit.
+fn returns_unit(function: &ItemFn) -> bool {
+    match &function.sig.output {
+        ReturnType::Default => true,
+        ReturnType::Type(_, output) => {
+            matches!(&**output, Type::Tuple(tuple) if tuple.elems.is_empty())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Expansion as a string without whitespace differences.
+    fn expanded(result: Result<TokenStream2, Error>) -> String {
+        result.expect("expansion failed").to_string()
+    }
+
+    /// Error message of rejected expansion.
+    fn rejected(result: Result<TokenStream2, Error>) -> String {
+        result.expect_err("expansion succeeded").to_string()
+    }
+
+    #[test]
+    fn entry_is_exported_as_main() {
+        let output = expanded(expand_entry(
+            quote!(),
+            quote!(
+                fn main() -> ! {
+                    loop {}
+                }
+            ),
+        ));
+        assert!(output.contains(&quote!(#[export_name = "main"]).to_string()));
+        assert!(output.contains(&quote!(main()).to_string()));
+    }
+
+    #[test]
+    fn entry_rejects_invalid_signatures() {
+        assert_eq!(
+            rejected(expand_entry(
+                quote!(),
+                quote!(
+                    fn main() {}
+                )
+            )),
+            "`#[entry]` function must have signature `fn() -> !`"
+        );
+        assert_eq!(
+            rejected(expand_entry(
+                quote!(),
+                quote!(
+                    fn main(argument: u32) -> ! {
+                        loop {}
+                    }
+                )
+            )),
+            "function must take 0 argument(s)"
+        );
+        assert_eq!(
+            rejected(expand_entry(
+                quote!(),
+                quote!(
+                    async fn main() -> ! {
+                        loop {}
+                    }
+                )
+            )),
+            "function must be a plain function without generics"
+        );
+        assert_eq!(
+            rejected(expand_entry(
+                quote!(Uart0),
+                quote!(
+                    fn main() -> ! {
+                        loop {}
+                    }
+                )
+            )),
+            "`#[entry]` does not take arguments"
+        );
+    }
+
+    #[test]
+    fn exception_is_registered_to_its_section() {
+        let output = expanded(expand_exception(
+            quote!(DataAbort),
+            quote!(
+                fn handle_data_abort(context: &mut ExceptionContext) {}
+            ),
+        ));
+        assert!(output.contains(&quote!(#[link_section = ".exception_handlers"]).to_string()));
+        assert!(output.contains(&quote!(exception: Exception::DataAbort).to_string()));
+        assert!(output.contains(&quote!(handler: handle_data_abort).to_string()));
+    }
+
+    #[test]
+    fn exception_rejects_invalid_signatures() {
+        assert_eq!(
+            rejected(expand_exception(
+                quote!(DataAbort),
+                quote!(
+                    fn handle_data_abort() {}
+                )
+            )),
+            "function must take 1 argument(s)"
+        );
+        assert_eq!(
+            rejected(expand_exception(
+                quote!(DataAbort),
+                quote!(
+                    fn handle_data_abort(context: &mut ExceptionContext) -> u32 {
+                        0
+                    }
+                )
+            )),
+            "`#[exception]` function must have signature `fn(&mut ExceptionContext)`"
+        );
+        assert!(expand_exception(
+            quote!(),
+            quote!(
+                fn handle_data_abort(context: &mut ExceptionContext) {}
+            )
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn interrupt_selects_interrupt_kind() {
+        let handler = quote!(
+            fn handle() {}
+        );
+        let output = expanded(expand_interrupt(quote!(Sgi3), handler.clone()));
+        assert!(output.contains(&quote!(irq: Irq::Sgi(SgiIrq::Sgi3)).to_string()));
+        let output = expanded(expand_interrupt(quote!(GlobalTimer), handler.clone()));
+        assert!(output.contains(&quote!(irq: Irq::Ppi(PpiIrq::GlobalTimer)).to_string()));
+        let output = expanded(expand_interrupt(quote!(Uart0), handler));
+        assert!(output.contains(&quote!(irq: Irq::Spi(SpiIrq::Uart0)).to_string()));
+        assert!(output.contains(&quote!(#[link_section = ".interrupt_handlers"]).to_string()));
+    }
+
+    #[test]
+    fn interrupt_rejects_invalid_signatures() {
+        assert_eq!(
+            rejected(expand_interrupt(
+                quote!(Uart0),
+                quote!(
+                    fn handle(value: u32) {}
+                )
+            )),
+            "function must take 0 argument(s)"
+        );
+        assert_eq!(
+            rejected(expand_interrupt(
+                quote!(Uart0),
+                quote!(
+                    fn handle() -> bool {
+                        true
+                    }
+                )
+            )),
+            "`#[interrupt]` function must have signature `fn()`"
+        );
+        assert_eq!(
+            rejected(expand_interrupt(
+                quote!(Uart0),
+                quote!(
+                    fn handle<T>() {}
+                )
+            )),
+            "function must be a plain function without generics"
+        );
+    }
+}
@@ -1,6 +1,9 @@
 //! Commonly used functionality.
 
 pub mod bitman;
+pub mod bus;
 pub mod instruction;
 pub mod memman;
+pub mod register;
+pub mod snapshot;
 pub mod timing;
@@ -0,0 +1,295 @@
+//! Register access.
+//!
+//! Drivers do not access memory mapped registers directly.
+//! Instead, they are generic over a [`Bus`] which performs the accesses.
+//!
+//! - [`Mmio`] accesses real hardware registers with volatile reads and writes,
+//!   Read-modify-write is not locked, so drivers which share a register between contexts
+//!   serialize the modifications themselves.
+//! - [`SimulatedBus`] stores register values in memory and records every write,
+//!   so drivers can be exercised on the host.
+//!
+//! # How to use?
+//!
+//! ```ignore
+//! let bus = SimulatedBus::<32, 64>::new();
+//! let uart = Uart::with_bus(DeviceIndex::Uart0, &bus);
+//! uart.toggle(true);
+//! assert!(bus.writes().any(|(address, _)| address == 0xE000_0000 as *mut u32));
+//! ```
+
+use super::bitman::ReadBitwise;
+use super::bitman::ReadBitwiseRange;
+use super::memman::modify_address;
+use super::memman::read_from_address;
+use super::memman::write_to_address;
+use core::cell::Cell;
+use core::ops::RangeInclusive;
+
+/// Can read and write 32-bit registers.
+///
+/// # Safety
+///
+/// Every method takes a raw register address.
+/// Callers must pass only addresses of registers which the bus is allowed to access,
+/// that is, registers of the peripheral whose driver holds the bus.
+/// [`Mmio`] accesses the address with volatile reads and writes.
+pub trait Bus {
+    /// Read register value.
+    ///
+    /// # Safety
+    ///
+    /// See [`Bus`].
+    unsafe fn read(&self, address: *mut u32) -> u32;
+
+    /// Write register value.
+    ///
+    /// # Safety
+    ///
+    /// See [`Bus`].
+    unsafe fn write(&self, address: *mut u32, value: u32);
+
+    /// Replace register value with its modified value.
+    ///
+    /// # Safety
+    ///
+    /// See [`Bus`].
+    #[inline]
+    unsafe fn modify<F>(&self, address: *mut u32, f: F)
+    where
+        F: FnOnce(u32) -> u32,
+    {
+        // Safety:
+        // Caller guarantees that address is accessible.
+        unsafe {
+            let old = self.read(address);
+            self.write(address, f(old));
+        }
+    }
+
+    /// Read register value's bit's value.
+    ///
+    /// # Safety
+    ///
+    /// See [`Bus`].
+    #[inline]
+    #[must_use]
+    unsafe fn read_bit(&self, address: *mut u32, index: u32) -> bool {
+        // Safety:
+        // Caller guarantees that address is accessible.
+        let value = unsafe { self.read(address) };
+        value.read_bit(index)
+    }
+
+    /// Read multiple bits from register.
+    ///
+    /// # Safety
+    ///
+    /// See [`Bus`].
+    #[inline]
+    #[must_use]
+    unsafe fn read_bits(&self, address: *mut u32, indices: RangeInclusive<u32>) -> u32 {
+        // Safety:
+        // Caller guarantees that address is accessible.
+        let value = unsafe { self.read(address) };
+        value.read_bits(indices)
+    }
+}
+
+impl<B: Bus + ?Sized> Bus for &B {
+    #[inline]
+    unsafe fn read(&self, address: *mut u32) -> u32 {
+        // Safety:
+        // Caller guarantees that address is accessible.
+        unsafe { (**self).read(address) }
+    }
+
+    #[inline]
+    unsafe fn write(&self, address: *mut u32, value: u32) {
+        // Safety:
+        // Caller guarantees that address is accessible.
+        unsafe { (**self).write(address, value) };
+    }
+
+    #[inline]
+    unsafe fn modify<F>(&self, address: *mut u32, f: F)
+    where
+        F: FnOnce(u32) -> u32,
+    {
+        // Safety:
+        // Caller guarantees that address is accessible.
+        unsafe { (**self).modify(address, f) };
+    }
+}
+
+/// Memory mapped hardware registers.
+///
+/// Owning `Mmio` allows accessing hardware registers,
+/// so it is only handed out by [`Peripherals::take`](crate::peripheral::Peripherals::take)
+/// as part of peripheral handles.
+#[derive(Clone, Copy)]
+pub struct Mmio(());
+
+impl Mmio {
+    /// Create register access without taking peripherals.
+    ///
+    /// # Safety
+    ///
+    /// Drivers created with returned value may alias drivers owned elsewhere.
+    /// Caller must ensure that aliasing drivers do not access the same registers concurrently.
+    #[inline]
+    #[must_use]
+    pub const unsafe fn steal() -> Self {
+        Self(())
+    }
+}
+
+impl Bus for Mmio {
+    #[inline]
+    unsafe fn read(&self, address: *mut u32) -> u32 {
+        // Safety:
+        // Caller passes a register address of a peripheral it owns.
+        unsafe { read_from_address(address) }
+    }
+
+    #[inline]
+    unsafe fn write(&self, address: *mut u32, value: u32) {
+        // Safety:
+        // Caller passes a register address of a peripheral it owns.
+        unsafe { write_to_address(address, value) };
+    }
+
+    #[inline]
+    unsafe fn modify<F>(&self, address: *mut u32, f: F)
+    where
+        F: FnOnce(u32) -> u32,
+    {
+        // Safety:
+        // Caller passes a register address of a peripheral it owns.
+        unsafe { modify_address(address, f) };
+    }
+}
+
+/// Simulated register file.
+///
+/// - Holds values for at most `REGISTERS` distinct addresses.
+/// - Records at most `WRITES` writes.
+/// - Registers which have never been written read as zero.
+///
+/// Hardware side-effects, such as self-clearing bits, are not simulated.
+pub struct SimulatedBus<const REGISTERS: usize, const WRITES: usize> {
+    /// Address and value of each used register.
+    registers: [Cell<Option<(usize, u32)>>; REGISTERS],
+
+    /// Address and value of each write in order.
+    writes: [Cell<(usize, u32)>; WRITES],
+
+    /// How many writes have been recorded.
+    write_count: Cell<usize>,
+}
+
+impl<const REGISTERS: usize, const WRITES: usize> SimulatedBus<REGISTERS, WRITES> {
+    /// Create empty register file.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            registers: core::array::from_fn(|_| Cell::new(None)),
+            writes: core::array::from_fn(|_| Cell::new((0, 0))),
+            write_count: Cell::new(0),
+        }
+    }
+
+    /// Set register value without recording a write.
+    ///
+    /// Use this to simulate values written by hardware, such as status bits.
+    ///
+    /// # Panics
+    ///
+    /// Register file is full.
+    pub fn preload(&self, address: *mut u32, value: u32) {
+        let address = address as usize;
+        for register in &self.registers {
+            match register.get() {
+                Some((used, _)) if used == address => {
+                    register.set(Some((address, value)));
+                    return;
+                }
+                Some(_) => {}
+                None => {
+                    register.set(Some((address, value)));
+                    return;
+                }
+            }
+        }
+        panic!("Simulated register file is full, capacity is {REGISTERS}.");
+    }
+
+    /// Current value of register.
+    #[must_use]
+    pub fn value(&self, address: *mut u32) -> u32 {
+        let address = address as usize;
+        self.registers
+            .iter()
+            .filter_map(Cell::get)
+            .find(|(used, _)| *used == address)
+            .map_or(0, |(_, value)| value)
+    }
+
+    /// Recorded writes in order.
+    pub fn writes(&self) -> impl Iterator<Item = (*mut u32, u32)> + '_ {
+        self.writes[..self.write_count.get()].iter().map(|write| {
+            let (address, value) = write.get();
+            (address as *mut u32, value)
+        })
+    }
+
+    /// Forget recorded writes.
+    ///
+    /// Register values are kept.
+    pub fn clear_writes(&self) {
+        self.write_count.set(0);
+    }
+}
+
+impl<const REGISTERS: usize, const WRITES: usize> Default for SimulatedBus<REGISTERS, WRITES> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const REGISTERS: usize, const WRITES: usize> Bus for SimulatedBus<REGISTERS, WRITES> {
+    unsafe fn read(&self, address: *mut u32) -> u32 {
+        self.value(address)
+    }
+
+    /// # Panics
+    ///
+    /// Register file or write log is full.
+    unsafe fn write(&self, address: *mut u32, value: u32) {
+        let count = self.write_count.get();
+        assert!(
+            count < WRITES,
+            "Simulated write log is full, capacity is {WRITES}."
+        );
+        self.writes[count].set((address as usize, value));
+        self.write_count.set(count + 1);
+        self.preload(address, value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn modify_writes_modified_value_once() {
+        let bus = SimulatedBus::<4, 4>::new();
+        let address = 0x1000 as *mut u32;
+        bus.preload(address, 0xFFFF_FFFF);
+        // Safety:
+        // Simulated bus does not access memory at the address.
+        unsafe { bus.modify(address, |old| old & !0x30) };
+        assert_eq!(bus.value(address), 0xFFFF_FFCF);
+        assert!(bus.writes().eq([(address, 0xFFFF_FFCF)]));
+    }
+}
@@ -15,5 +15,8 @@ pub fn nop() {
 pub fn breakpoint() {
     // Safety:
     // Does not cause any side-effects.
-    unsafe { asm!("bkpt") };
+    #[cfg(target_arch = "arm")]
+    unsafe {
+        asm!("bkpt")
+    };
 }
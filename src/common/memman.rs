@@ -1,69 +1,79 @@
 //! Memory address manipulation.
 
-use super::bitman::ClearBitwise;
 use super::bitman::ReadBitwise;
 use super::bitman::ReadBitwiseRange;
-use super::bitman::SetBitwise;
-use super::bitman::WriteBitwise;
 use core::ops::RangeInclusive;
 use core::ptr::read_volatile;
 use core::ptr::write_volatile;
 
+/// Replace memory address value with its modified value.
+///
+/// Value is read, modified and written without a lock.
+/// Callers which modify the same address from several contexts must serialize the modifications,
+/// for example with [`cpu::free`](crate::interrupt::cpu::free) or a [`Mutex`](crate::sync::mutex::Mutex).
+///
+/// # Safety
+///
+/// Address must be valid for volatile reads and writes.
+#[inline]
+pub unsafe fn modify_address(address: *mut u32, modify: impl FnOnce(u32) -> u32) {
+    // Safety:
+    // Caller guarantees that address is valid.
+    unsafe {
+        let old = read_from_address(address);
+        write_to_address(address, modify(old));
+    }
+}
+
 /// Read value from memory address.
+///
+/// # Safety
+///
+/// Address must be valid for volatile reads.
 #[inline]
 #[must_use]
-pub fn read_from_address(address: *mut u32) -> u32 {
-    // SAFETY:
-    // Programmer is responsible for reading from valid address.
+pub unsafe fn read_from_address(address: *mut u32) -> u32 {
+    // Safety:
+    // Caller guarantees that address is valid.
     unsafe { read_volatile(address) }
 }
 
 /// Write value to memory address.
+///
+/// # Safety
+///
+/// Address must be valid for volatile writes.
 #[inline]
-pub fn write_to_address(address: *mut u32, value: u32) {
-    // SAFETY:
-    // Programmer is responsible for reading from valid address.
+pub unsafe fn write_to_address(address: *mut u32, value: u32) {
+    // Safety:
+    // Caller guarantees that address is valid.
     unsafe { write_volatile(address, value) }
 }
 
-/// Set memory address value's bit high.
-#[inline]
-pub fn set_address_bit(address: *mut u32, index: u32) {
-    let old = read_from_address(address);
-    let new = old.set_bit(index);
-    write_to_address(address, new);
-}
-
-/// Set memory address value's bit low.
-#[inline]
-pub fn clear_address_bit(address: *mut u32, index: u32) {
-    let old = read_from_address(address);
-    let new = old.clear_bit(index);
-    write_to_address(address, new);
-}
-
 /// Read memory address value's bit's value.
+///
+/// # Safety
+///
+/// Address must be valid for volatile reads.
 #[inline]
 #[must_use]
-pub fn read_address_bit(address: *mut u32, index: u32) -> bool {
-    let value = read_from_address(address);
+pub unsafe fn read_address_bit(address: *mut u32, index: u32) -> bool {
+    // Safety:
+    // Caller guarantees that address is valid.
+    let value = unsafe { read_from_address(address) };
     value.read_bit(index)
 }
 
 /// Read multiple bits from memory address.
+///
+/// # Safety
+///
+/// Address must be valid for volatile reads.
 #[inline]
 #[must_use]
-pub fn read_address_bits(address: *mut u32, indices: RangeInclusive<u32>) -> u32 {
-    let value = read_from_address(address);
+pub unsafe fn read_address_bits(address: *mut u32, indices: RangeInclusive<u32>) -> u32 {
+    // Safety:
+    // Caller guarantees that address is valid.
+    let value = unsafe { read_from_address(address) };
     value.read_bits(indices)
 }
-
-/// Write multiple bits to memory address.
-#[inline]
-pub fn write_address_bits(address: *mut u32, indices: RangeInclusive<u32>, value: u32) {
-    let start = *indices.start();
-    let length = indices.end() - indices.start();
-    let old = read_from_address(address);
-    let new = old.write_bits(start, value, length);
-    write_to_address(address, new);
-}
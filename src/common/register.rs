@@ -0,0 +1,484 @@
+//! Typed registers.
+//!
+//! Registers of a peripheral are described once with [`register_block!`](crate::register_block).
+//! For each register the description generates a module, which holds
+//! - register's specification: offset, reset value and access permission,
+//! - register's fields: bit offsets and widths.
+//!
+//! Fields are typed by their register, so field of one register can not be used with another register.
+//! Access permissions are checked at compile time, so read-only registers can not be written.
+//!
+//! # How to use?
+//!
+//! ```ignore
+//! register_block! {
+//!     /// Example registers.
+//!     pub ExampleRegisters {
+//!         /// Control register.
+//!         0x00 => control: ReadWrite, reset = 0x0000_0000 {
+//!             /// Enable peripheral.
+//!             ENABLE: 0, 1;
+//!             /// Clock prescaler.
+//!             PRESCALER: 8, 8;
+//!         }
+//!         /// Input data registers.
+//!         0x10 [4; 0x04] => data: ReadOnly, reset = 0x0000_0000 {
+//!             /// Pin values.
+//!             pin[32]: 0, 1;
+//!         }
+//!     }
+//! }
+//!
+//! let registers = ExampleRegisters::new(0xE000_0000, unsafe { Mmio::steal() });
+//! registers
+//!     .control()
+//!     .modify(|r| r.write(control::PRESCALER, 4).set(control::ENABLE, true));
+//! let high = registers.data(1).read().is_set(data::pin(3));
+//! ```
+
+use super::bus::Bus;
+use core::marker::PhantomData;
+
+/// Register can be read and written.
+pub struct ReadWrite;
+
+/// Register can only be read.
+pub struct ReadOnly;
+
+/// Register can only be written.
+pub struct WriteOnly;
+
+/// Access permission allows reading.
+pub trait Readable {}
+
+/// Access permission allows writing.
+pub trait Writable {}
+
+impl Readable for ReadWrite {}
+impl Readable for ReadOnly {}
+impl Writable for ReadWrite {}
+impl Writable for WriteOnly {}
+
+/// Register specification.
+pub trait RegisterSpec {
+    /// Access permission.
+    type Access;
+
+    /// Offset from register block's base address.
+    const OFFSET: u32;
+
+    /// Value after reset.
+    const RESET: u32;
+}
+
+/// Bits of a register.
+pub struct Field<S> {
+    /// Index of lowest bit.
+    offset: u32,
+
+    /// How many bits.
+    width: u32,
+
+    /// Register which has this field.
+    spec: PhantomData<S>,
+}
+
+impl<S> Clone for Field<S> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<S> Copy for Field<S> {}
+
+impl<S> Field<S> {
+    /// Create field.
+    ///
+    /// # Panics
+    ///
+    /// Field does not fit to 32-bit register.
+    #[inline]
+    #[must_use]
+    pub const fn new(offset: u32, width: u32) -> Self {
+        assert!(0 < width, "Field must have at least one bit.");
+        assert!(offset + width <= 32, "Field must fit to 32-bit register.");
+        Self {
+            offset,
+            width,
+            spec: PhantomData,
+        }
+    }
+
+    /// Index of lowest bit.
+    #[inline]
+    #[must_use]
+    pub const fn offset(self) -> u32 {
+        self.offset
+    }
+
+    /// How many bits.
+    #[inline]
+    #[must_use]
+    pub const fn width(self) -> u32 {
+        self.width
+    }
+
+    /// Field's bits set high, other bits low.
+    #[inline]
+    #[must_use]
+    pub const fn mask(self) -> u32 {
+        (u32::MAX >> (32 - self.width)) << self.offset
+    }
+}
+
+/// Value of a register.
+pub struct Value<S> {
+    /// Raw bits.
+    bits: u32,
+
+    /// Register which has this value.
+    spec: PhantomData<S>,
+}
+
+impl<S> Clone for Value<S> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<S> Copy for Value<S> {}
+
+impl<S> Value<S> {
+    /// Create value from raw bits.
+    #[inline]
+    #[must_use]
+    pub const fn from_bits(bits: u32) -> Self {
+        Self {
+            bits,
+            spec: PhantomData,
+        }
+    }
+
+    /// Raw bits.
+    #[inline]
+    #[must_use]
+    pub const fn bits(self) -> u32 {
+        self.bits
+    }
+
+    /// Read field's value.
+    #[inline]
+    #[must_use]
+    pub const fn read(self, field: Field<S>) -> u32 {
+        (self.bits & field.mask()) >> field.offset
+    }
+
+    /// True if any of field's bits is high.
+    #[inline]
+    #[must_use]
+    pub const fn is_set(self, field: Field<S>) -> bool {
+        self.read(field) != 0
+    }
+
+    /// Write field's value.
+    ///
+    /// # Panics
+    ///
+    /// Value does not fit to field.
+    #[inline]
+    #[must_use]
+    pub const fn write(self, field: Field<S>, value: u32) -> Self {
+        let mask = field.mask();
+        assert!(
+            value <= mask >> field.offset,
+            "Value does not fit to field."
+        );
+        Self::from_bits((self.bits & !mask) | (value << field.offset))
+    }
+
+    /// Set field's bits high or low.
+    #[inline]
+    #[must_use]
+    pub const fn set(self, field: Field<S>, enable: bool) -> Self {
+        let value = if enable {
+            field.mask() >> field.offset
+        } else {
+            0
+        };
+        self.write(field, value)
+    }
+}
+
+impl<S: RegisterSpec> Value<S> {
+    /// Value after reset.
+    #[inline]
+    #[must_use]
+    pub const fn reset() -> Self {
+        Self::from_bits(S::RESET)
+    }
+}
+
+/// Handle to a register.
+pub struct Register<'a, S, B> {
+    /// Register's address.
+    address: *mut u32,
+
+    /// Register access.
+    bus: &'a B,
+
+    /// Register's specification.
+    spec: PhantomData<S>,
+}
+
+impl<'a, S: RegisterSpec, B: Bus> Register<'a, S, B> {
+    /// Create handle to register at given address.
+    #[inline]
+    #[must_use]
+    pub fn new(address: *mut u32, bus: &'a B) -> Self {
+        Self {
+            address,
+            bus,
+            spec: PhantomData,
+        }
+    }
+
+    /// Register's address.
+    #[inline]
+    #[must_use]
+    pub fn address(&self) -> *mut u32 {
+        self.address
+    }
+
+    /// Read register.
+    #[inline]
+    #[must_use]
+    pub fn read(&self) -> Value<S>
+    where
+        S::Access: Readable,
+    {
+        // Safety:
+        // Register blocks are created by drivers at their peripheral's base address.
+        Value::from_bits(unsafe { self.bus.read(self.address) })
+    }
+
+    /// Write register.
+    ///
+    /// Closure receives register's reset value.
+    #[inline]
+    pub fn write<F>(&self, f: F)
+    where
+        S::Access: Writable,
+        F: FnOnce(Value<S>) -> Value<S>,
+    {
+        let value = f(Value::reset());
+        // Safety:
+        // Register blocks are created by drivers at their peripheral's base address.
+        unsafe { self.bus.write(self.address, value.bits()) };
+    }
+
+    /// Write register.
+    ///
+    /// Closure receives zero.
+    /// Use this with registers where writing zero to a bit has no effect.
+    #[inline]
+    pub fn write_with_zero<F>(&self, f: F)
+    where
+        S::Access: Writable,
+        F: FnOnce(Value<S>) -> Value<S>,
+    {
+        let value = f(Value::from_bits(0));
+        // Safety:
+        // Register blocks are created by drivers at their peripheral's base address.
+        unsafe { self.bus.write(self.address, value.bits()) };
+    }
+
+    /// Write raw bits to register.
+    #[inline]
+    pub fn write_bits(&self, bits: u32)
+    where
+        S::Access: Writable,
+    {
+        // Safety:
+        // Register blocks are created by drivers at their peripheral's base address.
+        unsafe { self.bus.write(self.address, bits) };
+    }
+
+    /// Read, modify and write register.
+    ///
+    /// Closure receives register's current value.
+    /// Modification is not atomic, see [`modify_address`](crate::common::memman::modify_address).
+    #[inline]
+    pub fn modify<F>(&self, f: F)
+    where
+        S::Access: Readable + Writable,
+        F: FnOnce(Value<S>) -> Value<S>,
+    {
+        // Safety:
+        // Register blocks are created by drivers at their peripheral's base address.
+        unsafe {
+            self.bus
+                .modify(self.address, |bits| f(Value::from_bits(bits)).bits());
+        }
+    }
+
+    /// Write register's reset value.
+    #[inline]
+    pub fn reset(&self)
+    where
+        S::Access: Writable,
+    {
+        self.write(|r| r);
+    }
+}
+
+/// Describe block of registers.
+///
+/// See [module documentation](crate::common::register) for the syntax.
+///
+/// - Register arrays are described with `offset [count; stride]`.
+/// - Field arrays are described with `name[count]` or `name[count; stride]`.
+///   Stride defaults to field's width.
+#[macro_export]
+macro_rules! register_block {
+    (@field $(#[$meta:meta])* $field:ident : $offset:literal, $width:literal) => {
+        $(#[$meta])*
+        pub const $field: $crate::common::register::Field<Spec> =
+            $crate::common::register::Field::new($offset, $width);
+    };
+    (@field $(#[$meta:meta])* $field:ident [$count:literal] : $offset:literal, $width:literal) => {
+        $crate::register_block!(@field $(#[$meta])* $field [$count; $width] : $offset, $width);
+    };
+    (@field $(#[$meta:meta])* $field:ident [$count:literal; $stride:literal] : $offset:literal, $width:literal) => {
+        $(#[$meta])*
+        ///
+        /// # Panics
+        ///
+        /// Invalid index.
+        #[inline]
+        #[must_use]
+        pub const fn $field(index: u32) -> $crate::common::register::Field<Spec> {
+            assert!(index < $count, "Invalid field index.");
+            $crate::common::register::Field::new($offset + index * $stride, $width)
+        }
+    };
+    (@array) => {};
+    (@array [$count:literal; $stride:literal]) => {
+        /// How many registers.
+        pub const COUNT: u32 = $count;
+
+        /// Distance between consecutive registers.
+        pub const STRIDE: u32 = $stride;
+    };
+    (@accessor $(#[$meta:meta])* $name:ident) => {
+        $(#[$meta])*
+        #[inline]
+        #[must_use]
+        pub fn $name(&self) -> $crate::common::register::Register<'_, $name::Spec, B> {
+            let offset = <$name::Spec as $crate::common::register::RegisterSpec>::OFFSET;
+            $crate::common::register::Register::new((self.base + offset) as *mut u32, &self.bus)
+        }
+    };
+    (@accessor $(#[$meta:meta])* $name:ident [$count:literal; $stride:literal]) => {
+        $(#[$meta])*
+        ///
+        /// # Panics
+        ///
+        /// Invalid index.
+        #[inline]
+        #[must_use]
+        pub fn $name(&self, index: u32) -> $crate::common::register::Register<'_, $name::Spec, B> {
+            assert!(index < $count, "Invalid register index.");
+            let offset = <$name::Spec as $crate::common::register::RegisterSpec>::OFFSET;
+            let offset = offset + index * $stride;
+            $crate::common::register::Register::new((self.base + offset) as *mut u32, &self.bus)
+        }
+    };
+    (
+        $(#[$block_meta:meta])*
+        $block_vis:vis $block:ident {
+            $(
+                $(#[$meta:meta])*
+                $offset:literal $([$count:literal; $stride:literal])? => $name:ident : $access:ident, reset = $reset:literal {
+                    $(
+                        $(#[$field_meta:meta])*
+                        $field:ident $([$($field_array:tt)*])? : $field_offset:literal, $field_width:literal;
+                    )*
+                }
+            )*
+        }
+    ) => {
+        $(
+            $(#[$meta])*
+            pub mod $name {
+                /// Register specification.
+                pub struct Spec;
+
+                impl $crate::common::register::RegisterSpec for Spec {
+                    type Access = $crate::common::register::$access;
+                    const OFFSET: u32 = $offset;
+                    const RESET: u32 = $reset;
+                }
+
+                $crate::register_block!(@array $([$count; $stride])?);
+
+                $(
+                    $crate::register_block!(
+                        @field $(#[$field_meta])* $field $([$($field_array)*])? : $field_offset, $field_width
+                    );
+                )*
+            }
+        )*
+
+        $(#[$block_meta])*
+        $block_vis struct $block<B = $crate::common::bus::Mmio> {
+            /// Base address.
+            base: u32,
+
+            /// Register access.
+            bus: B,
+        }
+
+        impl<B> $block<B> {
+            /// Create register block at given base address using given register access.
+            #[inline]
+            #[must_use]
+            pub const fn new(base: u32, bus: B) -> Self {
+                Self { base, bus }
+            }
+
+            /// Base address.
+            #[inline]
+            #[must_use]
+            pub const fn base(&self) -> u32 {
+                self.base
+            }
+
+            /// Register access.
+            ///
+            /// Not public, so that owned register access can not be copied out of a driver.
+            #[inline]
+            #[must_use]
+            #[allow(dead_code)]
+            pub(crate) const fn bus(&self) -> &B {
+                &self.bus
+            }
+        }
+
+        impl<B: Clone> Clone for $block<B> {
+            fn clone(&self) -> Self {
+                Self {
+                    base: self.base,
+                    bus: self.bus.clone(),
+                }
+            }
+        }
+
+        impl<B: Copy> Copy for $block<B> {}
+
+        impl<B: $crate::common::bus::Bus> $block<B> {
+            $(
+                $crate::register_block!(@accessor $(#[$meta])* $name $([$count; $stride])?);
+            )*
+        }
+    };
+}
@@ -0,0 +1,30 @@
+//! Peripheral configuration snapshots.
+//!
+//! Peripherals implementing [`Snapshot`] can capture their register configuration into a value
+//! and write it back later.
+//! Use this to reconfigure a peripheral temporarily,
+//! or to resume after the peripheral has been powered down or reset.
+//!
+//! Status registers and free-running counters which can not be written are not captured.
+//!
+//! # How to use?
+//!
+//! ```ignore
+//! let saved = uart.save();
+//! uart.configure(&peripherals.slcr)?;
+//! uart.transmit_line("Diagnostics");
+//! uart.restore(&saved);
+//! ```
+
+/// Peripheral whose register configuration can be saved and restored.
+pub trait Snapshot {
+    /// Saved register configuration.
+    type State: Clone + Copy;
+
+    /// Capture register configuration.
+    #[must_use]
+    fn save(&self) -> Self::State;
+
+    /// Write captured register configuration back.
+    fn restore(&self, state: &Self::State);
+}
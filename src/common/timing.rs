@@ -1,5 +1,10 @@
 //! System timing values.
 
+/// Frequency of `PS_CLK`, the input clock of all PLLs.
+///
+/// `PYNQ-Z1` provides 50 MHz clock to `Zynq-7000`'s `PS_CLK` pin.
+pub const FREQUENCY_PS_CLK: u32 = 50_000_000;
+
 /// Processor frequency.
 ///
 /// `PYNQ-Z1` provides 50 MHz clock to `Zynq-7000`'s `PS_CLK` pin.
@@ -0,0 +1,336 @@
+//! Console output.
+//!
+//! [`sprint!`](crate::sprint) and [`sprintln!`](crate::sprintln) write to the console selected at runtime.
+//! Console is locked with IRQ and FIQ masked while one invocation is written,
+//! so output from interrupt handlers or the other core does not interleave mid-line.
+//! Keep output of latency sensitive code short, since transmitting waits for the UART.
+//!
+//! Exception and panic handlers may interrupt code which holds the lock,
+//! so they print with [`esprint!`](crate::esprint) and [`esprintln!`](crate::esprintln) instead.
+//! These do not wait for any lock.
+//!
+//! # How to use?
+//!
+//! ```ignore
+//! console::set_console(Console::Uart1);
+//! sprintln!("Hello, World!");
+//!
+//! console::set_console(Console::RingBuffer);
+//! sprintln!("Stored for later.");
+//! let mut buffer = [0; 64];
+//! let count = console::read_ring_buffer(&mut buffer);
+//! ```
+
+use crate::common::bus::Mmio;
+use crate::interrupt::cpu;
+use crate::peripheral::uart::DeviceIndex;
+use crate::peripheral::uart::Uart;
+use crate::sync::mutex::Mutex;
+use crate::sync::spinlock::Spinlock;
+use core::fmt::Write;
+use core::sync::atomic::AtomicU8;
+use core::sync::atomic::Ordering;
+
+/// Where console output is written.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Console {
+    /// Output is discarded.
+    Disabled,
+
+    /// Output is transmitted using UART 0.
+    ///
+    /// UART 0 must be configured and enabled.
+    /// Output may interleave with output written through the owned UART 0 handle.
+    Uart0,
+
+    /// Output is transmitted using UART 1.
+    ///
+    /// UART 1 must be configured and enabled.
+    /// Output may interleave with output written through the owned UART 1 handle.
+    Uart1,
+
+    /// Output is written to debugger using semihosting.
+    ///
+    /// Debugger must have semihosting enabled, otherwise writing causes a supervisor call exception,
+    /// which is ignored by default [handler](crate::interrupt::handler::exception).
+    Semihosting,
+
+    /// Output is stored to a ring buffer.
+    ///
+    /// When the buffer is full, oldest bytes are overwritten.
+    /// Read stored bytes with [`read_ring_buffer`].
+    RingBuffer,
+}
+
+impl Console {
+    /// Transform to unsigned 8-bit integer.
+    #[inline]
+    #[must_use]
+    pub const fn as_u8(self) -> u8 {
+        match self {
+            Self::Disabled => 0,
+            Self::Uart0 => 1,
+            Self::Uart1 => 2,
+            Self::Semihosting => 3,
+            Self::RingBuffer => 4,
+        }
+    }
+
+    /// Transform from unsigned 8-bit integer.
+    ///
+    /// Unknown values are transformed to [`Console::Disabled`].
+    #[inline]
+    #[must_use]
+    pub const fn from_u8(value: u8) -> Self {
+        match value {
+            1 => Self::Uart0,
+            2 => Self::Uart1,
+            3 => Self::Semihosting,
+            4 => Self::RingBuffer,
+            _ => Self::Disabled,
+        }
+    }
+}
+
+impl core::fmt::Display for Console {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let name = match self {
+            Self::Disabled => "disabled",
+            Self::Uart0 => "UART 0",
+            Self::Uart1 => "UART 1",
+            Self::Semihosting => "semihosting",
+            Self::RingBuffer => "ring buffer",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// Selected console.
+static CONSOLE: AtomicU8 = AtomicU8::new(Console::Uart0.as_u8());
+
+/// Select where console output is written.
+#[inline]
+pub fn set_console(console: Console) {
+    CONSOLE.store(console.as_u8(), Ordering::Relaxed);
+}
+
+/// Get where console output is written.
+#[inline]
+#[must_use]
+pub fn console() -> Console {
+    Console::from_u8(CONSOLE.load(Ordering::Relaxed))
+}
+
+/// How many bytes the ring buffer holds.
+pub const RING_BUFFER_SIZE: usize = 1024;
+
+/// Bytes written to ring buffer console.
+struct RingBuffer {
+    /// Stored bytes.
+    bytes: [u8; RING_BUFFER_SIZE],
+
+    /// Index of oldest stored byte.
+    head: usize,
+
+    /// How many bytes are stored.
+    length: usize,
+}
+
+impl RingBuffer {
+    /// Store byte, overwrite oldest byte if buffer is full.
+    fn push(&mut self, byte: u8) {
+        let tail = (self.head + self.length) % RING_BUFFER_SIZE;
+        self.bytes[tail] = byte;
+        if self.length == RING_BUFFER_SIZE {
+            self.head = (self.head + 1) % RING_BUFFER_SIZE;
+        } else {
+            self.length += 1;
+        }
+    }
+
+    /// Take oldest byte.
+    fn pop(&mut self) -> Option<u8> {
+        if self.length == 0 {
+            return None;
+        }
+        let byte = self.bytes[self.head];
+        self.head = (self.head + 1) % RING_BUFFER_SIZE;
+        self.length -= 1;
+        Some(byte)
+    }
+}
+
+/// Ring buffer console.
+///
+/// Lock is held only while bytes are pushed or popped.
+static RING_BUFFER: Mutex<RingBuffer> = Mutex::new(RingBuffer {
+    bytes: [0; RING_BUFFER_SIZE],
+    head: 0,
+    length: 0,
+});
+
+/// Move bytes from ring buffer console to given buffer.
+///
+/// Returns how many bytes were moved.
+pub fn read_ring_buffer(buffer: &mut [u8]) -> usize {
+    RING_BUFFER.lock(|ring_buffer| {
+        let mut count = 0;
+        for byte in buffer.iter_mut() {
+            match ring_buffer.pop() {
+                Some(value) => *byte = value,
+                None => break,
+            }
+            count += 1;
+        }
+        count
+    })
+}
+
+/// Write byte to debugger using semihosting.
+fn write_semihosting(byte: u8) {
+    /// Semihosting operation which writes one character.
+    #[cfg(target_arch = "arm")]
+    const SYS_WRITEC: u32 = 0x03;
+
+    #[cfg(target_arch = "arm")]
+    // Safety:
+    // Debugger handles the supervisor call and reads one byte from given address.
+    unsafe {
+        core::arch::asm!(
+            "svc 0x123456",
+            inout("r0") SYS_WRITEC => _,
+            in("r1") &byte as *const u8,
+            out("lr") _,
+            options(nostack),
+        );
+    }
+}
+
+/// Held while one invocation is written to console.
+///
+/// Taken with IRQ and FIQ masked, so an interrupt handler never waits for the code it interrupted.
+static OUTPUT_LOCK: Spinlock = Spinlock::new();
+
+/// Writes to given console.
+struct ConsoleWriter {
+    /// Selected console.
+    console: Console,
+
+    /// Wait for ring buffer lock.
+    ///
+    /// Otherwise ring buffer output is discarded if the lock could not be taken.
+    wait: bool,
+}
+
+impl Write for ConsoleWriter {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        match self.console {
+            Console::Disabled => {}
+            Console::Uart0 | Console::Uart1 => {
+                let index = if self.console == Console::Uart0 {
+                    DeviceIndex::Uart0
+                } else {
+                    DeviceIndex::Uart1
+                };
+                // Console does not own UART, see `Console` variants.
+                let uart = Uart::with_bus(index, unsafe { Mmio::steal() });
+                uart.transmit_string(s);
+            }
+            Console::Semihosting => {
+                for byte in s.bytes() {
+                    write_semihosting(byte);
+                }
+            }
+            Console::RingBuffer => {
+                let push = |ring_buffer: &mut RingBuffer| {
+                    for byte in s.bytes() {
+                        ring_buffer.push(byte);
+                    }
+                };
+                if self.wait {
+                    RING_BUFFER.lock(push);
+                } else {
+                    let _ = RING_BUFFER.try_lock(push);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Print formatted string to selected console.
+#[doc(hidden)]
+pub fn _print(arguments: core::fmt::Arguments) {
+    cpu::free(|_| {
+        OUTPUT_LOCK.lock();
+        let mut writer = ConsoleWriter {
+            console: console(),
+            wait: true,
+        };
+        // Console writer does not fail.
+        let _ = writer.write_fmt(arguments);
+        OUTPUT_LOCK.unlock();
+    });
+}
+
+/// Print formatted string to selected console without waiting for locks.
+///
+/// If a lock is held, for example by the code which caused an exception,
+/// UART and semihosting output is written without the lock and ring buffer output is discarded.
+#[doc(hidden)]
+pub fn _print_fallible(arguments: core::fmt::Arguments) {
+    cpu::free(|_| {
+        let locked = OUTPUT_LOCK.try_lock();
+        let mut writer = ConsoleWriter {
+            console: console(),
+            wait: false,
+        };
+        // Console writer does not fail.
+        let _ = writer.write_fmt(arguments);
+        if locked {
+            OUTPUT_LOCK.unlock();
+        }
+    });
+}
+
+/// Print formatted string to selected [console](crate::console).
+#[macro_export]
+macro_rules! sprint {
+    ($($tt:tt)*) => {
+        $crate::console::_print(format_args!($($tt)*));
+    };
+}
+
+/// Print formatted line to selected [console](crate::console).
+#[macro_export]
+macro_rules! sprintln {
+    () => {
+        $crate::sprint!("\r\n");
+    };
+    ($($tt:tt)*) => {
+        $crate::console::_print(format_args!("{}\r\n", format_args!($($tt)*)));
+    };
+}
+
+/// Print formatted string to selected [console](crate::console) from exception or panic handler.
+///
+/// Does not wait for the console lock, so output may interleave with interrupted output.
+#[macro_export]
+macro_rules! esprint {
+    ($($tt:tt)*) => {
+        $crate::console::_print_fallible(format_args!($($tt)*));
+    };
+}
+
+/// Print formatted line to selected [console](crate::console) from exception or panic handler.
+///
+/// Does not wait for the console lock, so output may interleave with interrupted output.
+#[macro_export]
+macro_rules! esprintln {
+    () => {
+        $crate::esprint!("\r\n");
+    };
+    ($($tt:tt)*) => {
+        $crate::console::_print_fallible(format_args!("{}\r\n", format_args!($($tt)*)));
+    };
+}
@@ -0,0 +1,174 @@
+//! Global heap allocator.
+//!
+//! Heap occupies `.heap` section reserved by the linker script,
+//! its size is configured with `PYNQ_Z1_HEAP_SIZE`, see build script.
+//! Heap is registered as the global allocator on the board,
+//! so collections from `alloc` crate can be used.
+//! Heap is initialized on first allocation.
+//!
+//! Heap is modified with interrupts masked and a spinlock taken,
+//! so interrupt handlers and both cores may allocate.
+//!
+//! # How to use?
+//!
+//! ```ignore
+//! extern crate alloc;
+//!
+//! use alloc::vec::Vec;
+//!
+//! let mut values = Vec::new();
+//! values.push(1);
+//! let statistics = heap::statistics();
+//! sprintln!("{} of {} bytes used", statistics.used, statistics.size);
+//! ```
+
+use crate::sync::mutex::Mutex;
+use core::alloc::GlobalAlloc;
+use core::alloc::Layout;
+use core::ptr::NonNull;
+
+/// Heap usage.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Statistics {
+    /// Size of heap in bytes.
+    pub size: usize,
+
+    /// Bytes in use, including padding required by alignment.
+    pub used: usize,
+
+    /// Bytes not in use.
+    pub free: usize,
+
+    /// Largest amount of bytes in use at once.
+    pub peak: usize,
+
+    /// Successful allocations.
+    pub allocations: usize,
+
+    /// Allocations which failed because no large enough free block was found.
+    pub failures: usize,
+}
+
+/// Allocator and its usage.
+struct State {
+    /// Allocator.
+    heap: linked_list_allocator::Heap,
+
+    /// True if heap has been given its memory.
+    initialized: bool,
+
+    /// Largest amount of bytes in use at once.
+    peak: usize,
+
+    /// Successful allocations.
+    allocations: usize,
+
+    /// Failed allocations.
+    failures: usize,
+}
+
+impl State {
+    /// Give heap its memory on first use.
+    fn initialize(&mut self) {
+        if self.initialized {
+            return;
+        }
+        self.initialized = true;
+        let (start, size) = region();
+        if size != 0 {
+            // Safety:
+            // Region is reserved for the heap by the linker script and initialized only once.
+            unsafe { self.heap.init(start, size) };
+        }
+    }
+}
+
+/// Heap allocator.
+struct Heap {
+    /// Allocator and its usage.
+    state: Mutex<State>,
+}
+
+impl Heap {
+    /// Create heap without memory.
+    const fn new() -> Self {
+        Self {
+            state: Mutex::new(State {
+                heap: linked_list_allocator::Heap::empty(),
+                initialized: false,
+                peak: 0,
+                allocations: 0,
+                failures: 0,
+            }),
+        }
+    }
+
+    /// Run closure with exclusive access to heap.
+    fn with_state<R>(&self, f: impl FnOnce(&mut State) -> R) -> R {
+        self.state.lock(|state| {
+            state.initialize();
+            f(state)
+        })
+    }
+}
+
+unsafe impl GlobalAlloc for Heap {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        self.with_state(|state| match state.heap.allocate_first_fit(layout) {
+            Ok(pointer) => {
+                state.allocations += 1;
+                state.peak = state.peak.max(state.heap.used());
+                pointer.as_ptr()
+            }
+            Err(()) => {
+                state.failures += 1;
+                core::ptr::null_mut()
+            }
+        })
+    }
+
+    unsafe fn dealloc(&self, pointer: *mut u8, layout: Layout) {
+        self.with_state(|state| {
+            // Safety:
+            // Caller guarantees that pointer was allocated from this heap with given layout.
+            unsafe {
+                state
+                    .heap
+                    .deallocate(NonNull::new_unchecked(pointer), layout)
+            };
+        });
+    }
+}
+
+/// Global heap allocator.
+#[cfg_attr(target_arch = "arm", global_allocator)]
+static HEAP: Heap = Heap::new();
+
+/// Read heap usage.
+#[must_use]
+pub fn statistics() -> Statistics {
+    HEAP.with_state(|state| Statistics {
+        size: state.heap.size(),
+        used: state.heap.used(),
+        free: state.heap.free(),
+        peak: state.peak,
+        allocations: state.allocations,
+        failures: state.failures,
+    })
+}
+
+/// Start address and size of memory reserved for the heap.
+fn region() -> (*mut u8, usize) {
+    #[cfg(target_arch = "arm")]
+    {
+        extern "C" {
+            static mut _heap_start: u8;
+            static mut _heap_final: u8;
+        }
+        let start = unsafe { core::ptr::addr_of_mut!(_heap_start) };
+        let end = unsafe { core::ptr::addr_of_mut!(_heap_final) };
+        (start, end as usize - start as usize)
+    }
+    #[cfg(not(target_arch = "arm"))]
+    (core::ptr::null_mut(), 0)
+}
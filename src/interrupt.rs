@@ -7,28 +7,72 @@
 //! Following example configures `PL0` shared peripheral interrupt.
 //!
 //! ```ignore
-//! GIC.toggle(false);
-//! ICC.toggle(false);
+//! let gic = peripherals.gic;
+//! let icc = peripherals.icc;
+//! gic.toggle(false);
+//! icc.toggle(false);
 //!
 //! let interrupt = Irq::Spi(SpiIrq::Pl0);
-//! GIC.toggle_interrupt(interrupt, true);
-//! GIC.set_shared_peripheral_interrupt_sensitivity(interrupt, InterruptSensitivity::Edge);
-//! GIC.set_shared_peripheral_interrupt_targets(interrupt, InterruptTargets::Cpu0);
-//! GIC.set_interrupt_priority(interrupt, InterruptPriority::Priority0);
+//! gic.toggle_interrupt(interrupt, true);
+//! gic.set_shared_peripheral_interrupt_sensitivity(interrupt, InterruptSensitivity::Edge);
+//! gic.set_shared_peripheral_interrupt_targets(interrupt, InterruptTargets::Cpu0);
+//! gic.set_interrupt_priority(interrupt, InterruptPriority::Priority0);
 //!
-//! ICC.set_interrupt_priority_filter(InterruptPriorityFilter::AllowAll);
+//! icc.set_interrupt_priority_filter(InterruptPriorityFilter::AllowAll);
 //!
-//! ICC.toggle(true);
-//! GIC.toggle(true);
+//! icc.toggle(true);
+//! gic.toggle(true);
+//!
+//! unsafe { cpu::enable_irq() };
 //! ```
+//!
+//! # Nested interrupts
+//!
+//! By default, interrupt request handlers run with interrupt requests masked.
+//! With `nested-interrupts` feature, handlers run with interrupt requests unmasked,
+//! so an interrupt with higher [priority](InterruptPriority) than the
+//! [running priority](icc::Icc::running_priority) preempts the handler.
+//! Handlers must then tolerate being preempted.
 
 // TODO: clear interrupts when reset
 
+pub mod cpu;
 pub mod gic;
 pub mod handler;
 pub mod icc;
 pub mod irq_numbers;
 
+/// Interrupt error.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// Value is not a valid interrupt priority.
+    InvalidPriority(u8),
+
+    /// Every fast interrupt handler slot is in use.
+    FiqHandlersFull,
+
+    /// Value is not a known interrupt identifier.
+    UnknownIrq(u32),
+
+    /// Value is not a valid set of interrupt targets.
+    InvalidTargets(u32),
+
+    /// No interrupt was acknowledged.
+    SpuriousInterrupt,
+}
+
+impl core::fmt::Display for Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::InvalidPriority(value) => write!(f, "invalid interrupt priority: {value}"),
+            Self::FiqHandlersFull => write!(f, "every fast interrupt handler slot is in use"),
+            Self::UnknownIrq(value) => write!(f, "unknown interrupt identifier: {value}"),
+            Self::InvalidTargets(value) => write!(f, "invalid interrupt targets: {value:#b}"),
+            Self::SpuriousInterrupt => write!(f, "spurious interrupt"),
+        }
+    }
+}
+
 /// Used to determine in which order parallel interrupts are handled.
 ///
 /// The higher the number, the lower the priority.
@@ -72,8 +116,13 @@ pub enum InterruptPriority {
 }
 
 impl InterruptPriority {
-    pub fn from_u8(value: u8) -> Self {
-        match value {
+    /// Transform from priority field's value.
+    ///
+    /// # Errors
+    ///
+    /// - Value is not a multiple of 8, board uses only the highest 5 bits.
+    pub fn from_u8(value: u8) -> Result<Self, Error> {
+        let result = match value {
             0b00000000 => Self::Priority0,
             0b00001000 => Self::Priority1,
             0b00010000 => Self::Priority2,
@@ -106,8 +155,11 @@ impl InterruptPriority {
             0b11101000 => Self::Priority29,
             0b11110000 => Self::Priority30,
             0b11111000 => Self::Priority31,
-            other => panic!("Invalid interrupt priority: {other}"),
-        }
+            other => {
+                return Err(Error::InvalidPriority(other));
+            }
+        };
+        Ok(result)
     }
 
     pub fn as_u8(self) -> u8 {
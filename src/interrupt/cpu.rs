@@ -0,0 +1,262 @@
+//! Processor's interrupt masks and program status.
+//!
+//! IRQ and FIQ are masked with the current program status register of the executing core.
+//! Masking does not affect the other core or the [GIC](crate::interrupt::gic).
+//!
+//! # How to use?
+//!
+//! ```ignore
+//! // Configure GIC and ICC, then let the processor take interrupt requests.
+//! unsafe { cpu::enable_irq() };
+//!
+//! let count = cpu::free(|_| COUNTER.load(Ordering::Relaxed));
+//!
+//! let status = ProgramStatus::read();
+//! sprintln!("{}, IRQ masked: {}", status.mode, status.irq_masked);
+//! ```
+
+use core::fmt::Display;
+
+/// Processor mode.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProcessorMode {
+    User,
+    Fiq,
+    Irq,
+    Supervisor,
+    Monitor,
+    Abort,
+    Hypervisor,
+    Undefined,
+    System,
+
+    /// Mode value is reserved.
+    Unknown(u32),
+}
+
+impl ProcessorMode {
+    /// Decode processor mode from program status register.
+    #[must_use]
+    pub const fn from_status(status: u32) -> Self {
+        match status & 0b1_1111 {
+            0b1_0000 => Self::User,
+            0b1_0001 => Self::Fiq,
+            0b1_0010 => Self::Irq,
+            0b1_0011 => Self::Supervisor,
+            0b1_0110 => Self::Monitor,
+            0b1_0111 => Self::Abort,
+            0b1_1010 => Self::Hypervisor,
+            0b1_1011 => Self::Undefined,
+            0b1_1111 => Self::System,
+            other => Self::Unknown(other),
+        }
+    }
+
+    /// True if mode has access to system registers.
+    #[inline]
+    #[must_use]
+    pub const fn is_privileged(self) -> bool {
+        !matches!(self, Self::User | Self::Unknown(_))
+    }
+}
+
+impl Display for ProcessorMode {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::User => write!(f, "user mode"),
+            Self::Fiq => write!(f, "FIQ mode"),
+            Self::Irq => write!(f, "IRQ mode"),
+            Self::Supervisor => write!(f, "supervisor mode"),
+            Self::Monitor => write!(f, "monitor mode"),
+            Self::Abort => write!(f, "abort mode"),
+            Self::Hypervisor => write!(f, "hypervisor mode"),
+            Self::Undefined => write!(f, "undefined mode"),
+            Self::System => write!(f, "system mode"),
+            Self::Unknown(value) => write!(f, "unknown mode {value:#07b}"),
+        }
+    }
+}
+
+/// Program status, decoded from program status register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ProgramStatus {
+    /// Processor mode.
+    pub mode: ProcessorMode,
+
+    /// True if processor executes Thumb instructions.
+    pub thumb: bool,
+
+    /// True if FIQ is masked.
+    pub fiq_masked: bool,
+
+    /// True if IRQ is masked.
+    pub irq_masked: bool,
+
+    /// True if asynchronous aborts are masked.
+    pub abort_masked: bool,
+}
+
+impl ProgramStatus {
+    /// Decode program status register.
+    #[must_use]
+    pub const fn from_status(status: u32) -> Self {
+        Self {
+            mode: ProcessorMode::from_status(status),
+            thumb: status & (1 << 5) != 0,
+            fiq_masked: status & (1 << 6) != 0,
+            irq_masked: status & (1 << 7) != 0,
+            abort_masked: status & (1 << 8) != 0,
+        }
+    }
+
+    /// Read current program status register of the executing core.
+    #[inline]
+    #[must_use]
+    pub fn read() -> Self {
+        Self::from_status(read_cpsr())
+    }
+}
+
+/// Read current program status register.
+#[inline]
+#[must_use]
+pub fn read_cpsr() -> u32 {
+    #[cfg(target_arch = "arm")]
+    {
+        let cpsr: u32;
+        // Safety:
+        // Reading program status register does not cause side-effects.
+        unsafe { core::arch::asm!("mrs {}, cpsr", out(reg) cpsr, options(nomem, nostack)) };
+        cpsr
+    }
+    // Simulated value on the host: system mode with IRQ and FIQ masked.
+    #[cfg(not(target_arch = "arm"))]
+    0b1101_1111
+}
+
+/// Unmask IRQ.
+///
+/// # Safety
+///
+/// Must not be called inside [`free`] or a [`Mutex`](crate::sync::mutex::Mutex) lock,
+/// otherwise interrupt handlers may access state protected by them.
+#[inline]
+pub unsafe fn enable_irq() {
+    #[cfg(target_arch = "arm")]
+    core::arch::asm!("cpsie i");
+}
+
+/// Mask IRQ.
+#[inline]
+pub fn disable_irq() {
+    #[cfg(target_arch = "arm")]
+    // Safety:
+    // Only IRQ mask is changed.
+    unsafe {
+        core::arch::asm!("cpsid i")
+    };
+}
+
+/// Unmask FIQ.
+///
+/// # Safety
+///
+/// Must not be called inside [`free`] or a [`Mutex`](crate::sync::mutex::Mutex) lock,
+/// otherwise fast interrupt handlers may access state protected by them.
+#[inline]
+pub unsafe fn enable_fiq() {
+    #[cfg(target_arch = "arm")]
+    core::arch::asm!("cpsie f");
+}
+
+/// Mask FIQ.
+#[inline]
+pub fn disable_fiq() {
+    #[cfg(target_arch = "arm")]
+    // Safety:
+    // Only FIQ mask is changed.
+    unsafe {
+        core::arch::asm!("cpsid f")
+    };
+}
+
+/// Proof that IRQ and FIQ are masked on the executing core.
+pub struct CriticalSection {
+    /// Created only by [`free`].
+    _private: (),
+}
+
+/// Run closure with IRQ and FIQ masked.
+///
+/// Previous masks are restored afterwards, so calls can be nested.
+/// Masking does not stop the other core, see [`Mutex`](crate::sync::mutex::Mutex).
+#[inline]
+pub fn free<R>(f: impl FnOnce(&CriticalSection) -> R) -> R {
+    let cpsr = mask_interrupts();
+    let result = f(&CriticalSection { _private: () });
+    restore_interrupts(cpsr);
+    result
+}
+
+/// Mask IRQ and FIQ.
+///
+/// Returns program status register before masking, pass it to [`restore_interrupts`].
+#[inline]
+pub(crate) fn mask_interrupts() -> u32 {
+    #[cfg(target_arch = "arm")]
+    {
+        let cpsr: u32;
+        // Safety:
+        // Only interrupt masks are changed.
+        unsafe { core::arch::asm!("mrs {}, cpsr", "cpsid if", out(reg) cpsr) };
+        cpsr
+    }
+    #[cfg(not(target_arch = "arm"))]
+    read_cpsr()
+}
+
+/// Restore IRQ and FIQ masks returned by [`mask_interrupts`].
+#[inline]
+pub(crate) fn restore_interrupts(cpsr: u32) {
+    #[cfg(target_arch = "arm")]
+    // Safety:
+    // Restores mode and interrupt masks to their values before masking.
+    unsafe {
+        core::arch::asm!("msr cpsr_c, {}", in(reg) cpsr)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn program_status_is_decoded_from_status_register() {
+        // Supervisor mode, Thumb state, IRQ and asynchronous aborts masked.
+        let status = ProgramStatus::from_status(0x6000_01B3);
+        assert_eq!(
+            status,
+            ProgramStatus {
+                mode: ProcessorMode::Supervisor,
+                thumb: true,
+                fiq_masked: false,
+                irq_masked: true,
+                abort_masked: true,
+            }
+        );
+
+        let status = ProgramStatus::from_status(0b0101_0001);
+        assert_eq!(status.mode, ProcessorMode::Fiq);
+        assert!(status.fiq_masked);
+        assert!(!status.irq_masked && !status.thumb && !status.abort_masked);
+    }
+
+    #[test]
+    fn reserved_processor_mode_is_unknown() {
+        let status = ProgramStatus::from_status(0b1_0100);
+        assert_eq!(status.mode, ProcessorMode::Unknown(0b1_0100));
+        assert!(!status.mode.is_privileged());
+        assert!(!ProcessorMode::User.is_privileged());
+        assert!(ProcessorMode::System.is_privileged());
+    }
+}
@@ -0,0 +1,118 @@
+//! General interrupt controller distributor registers.
+
+crate::register_block! {
+    /// General interrupt controller distributor registers.
+    pub GicRegisters {
+        /// Distributor control register.
+        0x000 => distributor_control: ReadWrite, reset = 0x0000_0000 {
+            /// Enable forwarding of secure interrupts.
+            ENABLE_SECURE: 0, 1;
+            /// Enable forwarding of non-secure interrupts.
+            ENABLE_NON_SECURE: 1, 1;
+        }
+
+        /// Interrupt controller type register.
+        0x004 => controller_type: ReadOnly, reset = 0x0000_0C22 {
+            /// Supported interrupts, `32 * (N + 1)`.
+            IT_LINES_NUMBER: 0, 5;
+            /// Number of CPU interfaces minus one.
+            CPU_NUMBER: 5, 3;
+            /// Security extensions are implemented.
+            SECURITY_EXTN: 10, 1;
+            /// Number of lockable shared peripheral interrupts.
+            LSPI: 11, 5;
+        }
+
+        /// Distributor implementer identification register.
+        0x008 => implementer_identification: ReadOnly, reset = 0x0102_043B {
+            /// Implementer.
+            IMPLEMENTER: 0, 12;
+            /// Revision number.
+            REVISION: 12, 12;
+            /// Implementation version.
+            VARIANT: 24, 8;
+        }
+
+        /// Interrupt security registers.
+        0x080 [3; 0x004] => interrupt_security: ReadWrite, reset = 0x0000_0000 {
+            /// Interrupt is non-secure.
+            interrupt[32]: 0, 1;
+        }
+
+        /// Interrupt set-enable registers.
+        0x100 [3; 0x004] => set_enable: ReadWrite, reset = 0x0000_0000 {
+            /// Write high to enable interrupt, read high if interrupt is enabled.
+            interrupt[32]: 0, 1;
+        }
+
+        /// Interrupt clear-enable registers.
+        0x180 [3; 0x004] => clear_enable: ReadWrite, reset = 0x0000_0000 {
+            /// Write high to disable interrupt, read high if interrupt is enabled.
+            interrupt[32]: 0, 1;
+        }
+
+        /// Interrupt set-pending registers.
+        0x200 [3; 0x004] => set_pending: ReadWrite, reset = 0x0000_0000 {
+            /// Write high to set interrupt pending, read high if interrupt is pending.
+            interrupt[32]: 0, 1;
+        }
+
+        /// Interrupt clear-pending registers.
+        0x280 [3; 0x004] => clear_pending: ReadWrite, reset = 0x0000_0000 {
+            /// Write high to clear interrupt's pending status, read high if interrupt is pending.
+            interrupt[32]: 0, 1;
+        }
+
+        /// Active bit registers.
+        0x300 [3; 0x004] => active: ReadOnly, reset = 0x0000_0000 {
+            /// Interrupt is active.
+            interrupt[32]: 0, 1;
+        }
+
+        /// Interrupt priority registers.
+        0x400 [24; 0x004] => priority: ReadWrite, reset = 0x0000_0000 {
+            /// Interrupt's priority.
+            priority[4]: 0, 8;
+        }
+
+        /// Interrupt processor targets registers.
+        0x800 [24; 0x004] => processor_targets: ReadWrite, reset = 0x0000_0000 {
+            /// CPUs which handle the interrupt.
+            targets[4]: 0, 8;
+        }
+
+        /// Interrupt configuration registers.
+        ///
+        /// Corresponding interrupt must be disabled before altering.
+        0xC00 [6; 0x004] => configuration: ReadWrite, reset = 0x5555_5555 {
+            /// Interrupt handling model.
+            model[16; 2]: 0, 1;
+            /// Interrupt is edge-triggered.
+            sensitivity[16; 2]: 1, 1;
+        }
+
+        /// Software generated interrupt register.
+        0xF00 => software_generated_interrupt: WriteOnly, reset = 0x0000_0000 {
+            /// Software generated interrupt identifier.
+            SGI_ID: 0, 4;
+            /// Send interrupt only if it is non-secure.
+            SATT: 15, 1;
+            /// CPUs which receive the interrupt.
+            CPU_TARGET_LIST: 16, 8;
+            /// How CPUs which receive the interrupt are selected.
+            TARGET_LIST_FILTER: 24, 2;
+        }
+
+        /// Peripheral ID2 register.
+        0xFE8 => peripheral_id2: ReadOnly, reset = 0x0000_001B {
+            /// Revision field for the GIC architecture.
+            ARCHITECTURE_REVISION: 4, 4;
+        }
+
+        /// ARM-defined fixed values for the preamble for component discovery.
+        0xFF0 [4; 0x004] => component_id: ReadOnly, reset = 0x0000_0000 {
+            /// Preamble byte.
+            PREAMBLE: 0, 8;
+        }
+    }
+}
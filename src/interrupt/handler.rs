@@ -1,3 +1,5 @@
 //! Interrupt handlers.
 
+pub mod exception;
+pub mod fiq;
 pub mod irq;
@@ -0,0 +1,541 @@
+//! Exception handler.
+//!
+//! Assembly entry stubs in `runtime.S` save exception context to exception mode's stack
+//! and call handlers from [`EXCEPTION_HANDLER`].
+//! When handler returns, possibly modified context is restored
+//! and execution continues from context's [`pc`](ExceptionContext::pc).
+//!
+//! Default handlers print fault report to [console](crate::console) and halt,
+//! except supervisor call handler which returns immediately.
+//! Exception may interrupt code which holds the console lock,
+//! so handlers print with [`esprintln!`](crate::esprintln), which does not wait for the lock.
+//!
+//! # How to use?
+//!
+//! ```ignore
+//! fn handle_data_abort(context: &mut ExceptionContext) {
+//!     let fault = DataFault::read();
+//!     esprintln!("{fault}");
+//!     // Skip faulting instruction.
+//!     context.pc += 4;
+//! }
+//!
+//! unsafe { EXCEPTION_HANDLER.handle_data_abort = handle_data_abort };
+//! ```
+//!
+//! Handler can also be registered at link time,
+//! in which case it takes precedence over [`EXCEPTION_HANDLER`].
+//!
+//! ```ignore
+//! #[exception(DataAbort)]
+//! fn handle_data_abort(context: &mut ExceptionContext) {
+//!     esprintln!("{}", DataFault::read());
+//!     context.pc += 4;
+//! }
+//! ```
+
+use crate::esprintln;
+use crate::interrupt::cpu::ProgramStatus;
+use core::fmt::Display;
+
+/// Processor state saved when exception was taken.
+///
+/// Layout must match `EXCEPTION_ENTRY` in `runtime.S`.
+#[derive(Clone, Copy, Debug)]
+#[repr(C)]
+pub struct ExceptionContext {
+    /// General purpose registers `r0-r12`.
+    pub registers: [u32; 13],
+
+    /// Stack pointer of context's mode.
+    ///
+    /// If exception was taken from the exception's own mode,
+    /// this is the stack pointer above the saved context, and changing it has no effect.
+    pub sp: u32,
+
+    /// Link register of context's mode.
+    ///
+    /// If exception was taken from the exception's own mode,
+    /// exception has overwritten it with the return address, and changing it has no effect.
+    pub lr: u32,
+
+    /// Address of instruction which caused the exception.
+    ///
+    /// For supervisor and hypervisor calls, address of instruction following the call.
+    pub pc: u32,
+
+    /// Current program status register.
+    pub cpsr: u32,
+
+    /// Keeps stack 8 byte aligned.
+    padding: u32,
+}
+
+impl ExceptionContext {
+    /// Decode saved program status register.
+    #[inline]
+    #[must_use]
+    pub const fn status(&self) -> ProgramStatus {
+        ProgramStatus::from_status(self.cpsr)
+    }
+}
+
+impl Display for ExceptionContext {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        for (index, register) in self.registers.iter().enumerate() {
+            let separator = if index % 4 == 3 { "\r\n" } else { " " };
+            write!(f, "r{index:<2} = {register:#010X}{separator}")?;
+        }
+        write!(f, "sp  = {:#010X} ", self.sp)?;
+        write!(f, "lr  = {:#010X} ", self.lr)?;
+        write!(f, "pc  = {:#010X}\r\n", self.pc)?;
+        write!(f, "cpsr = {:#010X} ({})", self.cpsr, self.status().mode)
+    }
+}
+
+/// Source of a fault, decoded from fault status register.
+///
+/// Decoding follows short-descriptor translation table format.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FaultSource {
+    AlignmentFault,
+    DebugEvent,
+    AccessFlagSection,
+    AccessFlagPage,
+    InstructionCacheMaintenance,
+    TranslationSection,
+    TranslationPage,
+    DomainSection,
+    DomainPage,
+    PermissionSection,
+    PermissionPage,
+    SynchronousExternalAbort,
+    SynchronousExternalAbortOnTranslationFirstLevel,
+    SynchronousExternalAbortOnTranslationSecondLevel,
+    SynchronousParityError,
+    SynchronousParityErrorOnTranslationFirstLevel,
+    SynchronousParityErrorOnTranslationSecondLevel,
+    AsynchronousExternalAbort,
+    AsynchronousParityError,
+    TlbConflictAbort,
+    Lockdown,
+    CoprocessorAbort,
+
+    /// Status value is reserved.
+    Unknown(u32),
+}
+
+impl FaultSource {
+    /// Decode fault source from fault status register.
+    #[must_use]
+    pub const fn from_status(status: u32) -> Self {
+        // Status is split into bits 10 and 3:0.
+        let value = ((status >> 6) & 0b1_0000) | (status & 0b1111);
+        match value {
+            0b00001 => Self::AlignmentFault,
+            0b00010 => Self::DebugEvent,
+            0b00011 => Self::AccessFlagSection,
+            0b00110 => Self::AccessFlagPage,
+            0b00100 => Self::InstructionCacheMaintenance,
+            0b00101 => Self::TranslationSection,
+            0b00111 => Self::TranslationPage,
+            0b01001 => Self::DomainSection,
+            0b01011 => Self::DomainPage,
+            0b01101 => Self::PermissionSection,
+            0b01111 => Self::PermissionPage,
+            0b01000 => Self::SynchronousExternalAbort,
+            0b01100 => Self::SynchronousExternalAbortOnTranslationFirstLevel,
+            0b01110 => Self::SynchronousExternalAbortOnTranslationSecondLevel,
+            0b11001 => Self::SynchronousParityError,
+            0b11100 => Self::SynchronousParityErrorOnTranslationFirstLevel,
+            0b11110 => Self::SynchronousParityErrorOnTranslationSecondLevel,
+            0b10110 => Self::AsynchronousExternalAbort,
+            0b11000 => Self::AsynchronousParityError,
+            0b10000 => Self::TlbConflictAbort,
+            0b10100 => Self::Lockdown,
+            0b11010 => Self::CoprocessorAbort,
+            other => Self::Unknown(other),
+        }
+    }
+}
+
+impl Display for FaultSource {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::AlignmentFault => write!(f, "alignment fault"),
+            Self::DebugEvent => write!(f, "debug event"),
+            Self::AccessFlagSection => write!(f, "access flag fault on section"),
+            Self::AccessFlagPage => write!(f, "access flag fault on page"),
+            Self::InstructionCacheMaintenance => write!(f, "instruction cache maintenance fault"),
+            Self::TranslationSection => write!(f, "translation fault on section"),
+            Self::TranslationPage => write!(f, "translation fault on page"),
+            Self::DomainSection => write!(f, "domain fault on section"),
+            Self::DomainPage => write!(f, "domain fault on page"),
+            Self::PermissionSection => write!(f, "permission fault on section"),
+            Self::PermissionPage => write!(f, "permission fault on page"),
+            Self::SynchronousExternalAbort => write!(f, "synchronous external abort"),
+            Self::SynchronousExternalAbortOnTranslationFirstLevel => {
+                write!(
+                    f,
+                    "synchronous external abort on first level translation table walk"
+                )
+            }
+            Self::SynchronousExternalAbortOnTranslationSecondLevel => {
+                write!(
+                    f,
+                    "synchronous external abort on second level translation table walk"
+                )
+            }
+            Self::SynchronousParityError => write!(f, "synchronous parity error"),
+            Self::SynchronousParityErrorOnTranslationFirstLevel => {
+                write!(
+                    f,
+                    "synchronous parity error on first level translation table walk"
+                )
+            }
+            Self::SynchronousParityErrorOnTranslationSecondLevel => {
+                write!(
+                    f,
+                    "synchronous parity error on second level translation table walk"
+                )
+            }
+            Self::AsynchronousExternalAbort => write!(f, "asynchronous external abort"),
+            Self::AsynchronousParityError => write!(f, "asynchronous parity error"),
+            Self::TlbConflictAbort => write!(f, "TLB conflict abort"),
+            Self::Lockdown => write!(f, "lockdown"),
+            Self::CoprocessorAbort => write!(f, "coprocessor abort"),
+            Self::Unknown(value) => write!(f, "unknown fault status: {value:#07b}"),
+        }
+    }
+}
+
+/// Data abort information.
+#[derive(Clone, Copy, Debug)]
+pub struct DataFault {
+    /// Data fault status register (DFSR).
+    pub status: u32,
+
+    /// Data fault address register (DFAR).
+    ///
+    /// Not valid for asynchronous faults.
+    pub address: u32,
+}
+
+impl DataFault {
+    /// Read data fault status and address registers.
+    #[must_use]
+    pub fn read() -> Self {
+        let mut status = 0;
+        let mut address = 0;
+        #[cfg(target_arch = "arm")]
+        // Safety:
+        // Reading fault registers does not cause side-effects.
+        unsafe {
+            core::arch::asm!("mrc p15, 0, {}, c5, c0, 0", out(reg) status);
+            core::arch::asm!("mrc p15, 0, {}, c6, c0, 0", out(reg) address);
+        }
+        Self { status, address }
+    }
+
+    /// Decoded fault source.
+    #[inline]
+    #[must_use]
+    pub const fn source(&self) -> FaultSource {
+        FaultSource::from_status(self.status)
+    }
+
+    /// True if fault was caused by a write access.
+    #[inline]
+    #[must_use]
+    pub const fn is_write(&self) -> bool {
+        self.status & (1 << 11) != 0
+    }
+
+    /// Domain which was accessed.
+    ///
+    /// Only valid for domain faults.
+    #[inline]
+    #[must_use]
+    pub const fn domain(&self) -> u32 {
+        (self.status >> 4) & 0b1111
+    }
+}
+
+impl Display for DataFault {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let access = if self.is_write() { "write" } else { "read" };
+        write!(
+            f,
+            "{} on {access} at {:#010X} (DFSR = {:#010X}, domain = {})",
+            self.source(),
+            self.address,
+            self.status,
+            self.domain()
+        )
+    }
+}
+
+/// Prefetch abort information.
+#[derive(Clone, Copy, Debug)]
+pub struct InstructionFault {
+    /// Instruction fault status register (IFSR).
+    pub status: u32,
+
+    /// Instruction fault address register (IFAR).
+    pub address: u32,
+}
+
+impl InstructionFault {
+    /// Read instruction fault status and address registers.
+    #[must_use]
+    pub fn read() -> Self {
+        let mut status = 0;
+        let mut address = 0;
+        #[cfg(target_arch = "arm")]
+        // Safety:
+        // Reading fault registers does not cause side-effects.
+        unsafe {
+            core::arch::asm!("mrc p15, 0, {}, c5, c0, 1", out(reg) status);
+            core::arch::asm!("mrc p15, 0, {}, c6, c0, 2", out(reg) address);
+        }
+        Self { status, address }
+    }
+
+    /// Decoded fault source.
+    #[inline]
+    #[must_use]
+    pub const fn source(&self) -> FaultSource {
+        FaultSource::from_status(self.status)
+    }
+}
+
+impl Display for InstructionFault {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "{} at {:#010X} (IFSR = {:#010X})",
+            self.source(),
+            self.address,
+            self.status
+        )
+    }
+}
+
+/// Exception which has a handler.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u32)]
+pub enum Exception {
+    UndefinedInstruction,
+    SupervisorCall,
+    PrefetchAbort,
+    DataAbort,
+    HypervisorCall,
+}
+
+/// Exception handler registered at link time.
+///
+/// Created by `#[exception]` attribute macro
+/// and placed to `.exception_handlers` section by the linker.
+/// Runtime walks the section as an array, so layout is fixed.
+#[repr(C)]
+pub struct ExceptionRegistration {
+    /// Handled exception.
+    pub exception: Exception,
+
+    /// Handler function.
+    pub handler: fn(&mut ExceptionContext),
+}
+
+/// Exception handlers registered at link time.
+fn registrations() -> &'static [ExceptionRegistration] {
+    #[cfg(target_arch = "arm")]
+    {
+        extern "C" {
+            static _exception_handlers_start: [u8; 0];
+            static _exception_handlers_final: [u8; 0];
+        }
+        // Safety:
+        // Linker script places registrations between the symbols.
+        unsafe {
+            let start =
+                core::ptr::addr_of!(_exception_handlers_start).cast::<ExceptionRegistration>();
+            let end =
+                core::ptr::addr_of!(_exception_handlers_final).cast::<ExceptionRegistration>();
+            let length = end.offset_from(start);
+            core::slice::from_raw_parts(start, length as usize)
+        }
+    }
+    #[cfg(not(target_arch = "arm"))]
+    &[]
+}
+
+/// Find handler for given exception.
+///
+/// Handlers registered at link time take precedence over [`EXCEPTION_HANDLER`].
+fn get_handler(exception: Exception) -> fn(&mut ExceptionContext) {
+    if let Some(registration) = registrations()
+        .iter()
+        .find(|registration| registration.exception == exception)
+    {
+        return registration.handler;
+    }
+    // Safety:
+    // Handlers are function pointers, which are replaced with single stores.
+    let exception_handler = unsafe { &*core::ptr::addr_of!(EXCEPTION_HANDLER) };
+    match exception {
+        Exception::UndefinedInstruction => exception_handler.handle_undefined_instruction,
+        Exception::SupervisorCall => exception_handler.handle_supervisor_call,
+        Exception::PrefetchAbort => exception_handler.handle_prefetch_abort,
+        Exception::DataAbort => exception_handler.handle_data_abort,
+        Exception::HypervisorCall => exception_handler.handle_hypervisor_call,
+    }
+}
+
+/// Exception handler.
+///
+/// Handlers may modify the context, which is restored when handler returns.
+pub struct ExceptionHandler {
+    pub handle_undefined_instruction: fn(&mut ExceptionContext),
+    pub handle_supervisor_call: fn(&mut ExceptionContext),
+    pub handle_prefetch_abort: fn(&mut ExceptionContext),
+    pub handle_data_abort: fn(&mut ExceptionContext),
+    pub handle_hypervisor_call: fn(&mut ExceptionContext),
+}
+
+/// Exception handler.
+pub static mut EXCEPTION_HANDLER: ExceptionHandler = ExceptionHandler {
+    handle_undefined_instruction: report_undefined_instruction,
+    handle_supervisor_call: |_| {},
+    handle_prefetch_abort: report_prefetch_abort,
+    handle_data_abort: report_data_abort,
+    handle_hypervisor_call: report_hypervisor_call,
+};
+
+/// Print undefined instruction report and halt.
+pub fn report_undefined_instruction(context: &mut ExceptionContext) {
+    esprintln!("undefined instruction at {:#010X}", context.pc);
+    halt(context);
+}
+
+/// Print prefetch abort report and halt.
+pub fn report_prefetch_abort(context: &mut ExceptionContext) {
+    esprintln!("prefetch abort: {}", InstructionFault::read());
+    halt(context);
+}
+
+/// Print data abort report and halt.
+pub fn report_data_abort(context: &mut ExceptionContext) {
+    esprintln!("data abort: {}", DataFault::read());
+    halt(context);
+}
+
+/// Print hypervisor call report and halt.
+pub fn report_hypervisor_call(context: &mut ExceptionContext) {
+    esprintln!("unexpected hypervisor call from {:#010X}", context.pc);
+    halt(context);
+}
+
+/// Print register dump and halt.
+fn halt(context: &ExceptionContext) -> ! {
+    esprintln!("{context}");
+    // Breakpoint would cause another exception without a debugger.
+    loop {
+        crate::common::instruction::nop();
+    }
+}
+
+/// Handle undefined instruction.
+///
+/// This function is called from assembly exception handler.
+#[no_mangle]
+#[inline(never)]
+extern "C" fn handle_undefined_instruction(context: &mut ExceptionContext) {
+    let handler = get_handler(Exception::UndefinedInstruction);
+    (handler)(context);
+}
+
+/// Handle supervisor call.
+///
+/// This function is called from assembly exception handler.
+#[no_mangle]
+#[inline(never)]
+extern "C" fn handle_supervisor_call(context: &mut ExceptionContext) {
+    let handler = get_handler(Exception::SupervisorCall);
+    (handler)(context);
+}
+
+/// Handle prefetch abort.
+///
+/// This function is called from assembly exception handler.
+#[no_mangle]
+#[inline(never)]
+extern "C" fn handle_prefetch_abort(context: &mut ExceptionContext) {
+    let handler = get_handler(Exception::PrefetchAbort);
+    (handler)(context);
+}
+
+/// Handle data abort.
+///
+/// This function is called from assembly exception handler.
+#[no_mangle]
+#[inline(never)]
+extern "C" fn handle_data_abort(context: &mut ExceptionContext) {
+    let handler = get_handler(Exception::DataAbort);
+    (handler)(context);
+}
+
+/// Handle hypervisor call.
+///
+/// This function is called from assembly exception handler.
+#[no_mangle]
+#[inline(never)]
+extern "C" fn handle_hypervisor_call(context: &mut ExceptionContext) {
+    let handler = get_handler(Exception::HypervisorCall);
+    (handler)(context);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fault_source_is_decoded_from_split_status() {
+        assert_eq!(FaultSource::from_status(0x001), FaultSource::AlignmentFault);
+        assert_eq!(FaultSource::from_status(0x00F), FaultSource::PermissionPage);
+        // Bit 10 is the fifth bit of the status.
+        assert_eq!(
+            FaultSource::from_status(0x406),
+            FaultSource::AsynchronousExternalAbort
+        );
+        assert_eq!(
+            FaultSource::from_status(0x400),
+            FaultSource::TlbConflictAbort
+        );
+        assert_eq!(
+            FaultSource::from_status(0x41E),
+            FaultSource::SynchronousParityErrorOnTranslationSecondLevel
+        );
+    }
+
+    #[test]
+    fn fault_source_ignores_domain_and_write_bits() {
+        // Write bit 11 and domain bits 7:4 are set.
+        assert_eq!(
+            FaultSource::from_status(0x8F5),
+            FaultSource::TranslationSection
+        );
+        assert_eq!(
+            FaultSource::from_status(0x8F5 | 0x400),
+            FaultSource::Unknown(0b10101)
+        );
+    }
+
+    #[test]
+    fn reserved_fault_status_is_unknown() {
+        assert_eq!(FaultSource::from_status(0x000), FaultSource::Unknown(0));
+        assert_eq!(
+            FaultSource::from_status(0x40F),
+            FaultSource::Unknown(0b11111)
+        );
+    }
+}
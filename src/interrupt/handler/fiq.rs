@@ -0,0 +1,159 @@
+//! Fast interrupt request handler.
+//!
+//! Secure interrupts are signaled with FIQ when [`ICC`](crate::interrupt::icc) has FIQ signaling enabled.
+//! Other interrupts must be made non-secure, so that they are still signaled with IRQ.
+//!
+//! FIQ mode has banked `r8-r12`, so its entry stub saves fewer registers than IRQ's,
+//! and FIQ preempts IRQ handlers.
+//!
+//! # How to use?
+//!
+//! ```ignore
+//! fn handle_pl0() {
+//!     // Clear interrupt source.
+//! }
+//!
+//! let interrupt = Irq::Spi(SpiIrq::Pl0);
+//! unsafe { (*core::ptr::addr_of_mut!(FIQ_HANDLER)).register(interrupt, handle_pl0)? };
+//!
+//! gic.toggle(false);
+//! icc.toggle(false);
+//! gic.route_all_to_irq();
+//! gic.route_to_fiq(interrupt, true);
+//! gic.toggle_interrupt(interrupt, true);
+//! icc.toggle_fiq(true);
+//! icc.toggle(true);
+//! gic.toggle(true);
+//! ```
+
+use super::irq;
+use crate::interrupt::icc::InterruptAcknowledge;
+use crate::interrupt::irq_numbers::Irq;
+use crate::interrupt::Error;
+
+/// How many interrupts can have a fast interrupt handler.
+pub const FIQ_HANDLER_CAPACITY: usize = 4;
+
+/// Interrupt and its fast interrupt handler.
+#[derive(Clone, Copy)]
+struct FiqRegistration {
+    /// Handled interrupt.
+    irq: Irq,
+
+    /// Handler function.
+    handler: fn(),
+}
+
+/// Fast interrupt request handler.
+pub struct FiqHandler {
+    /// Registered handlers.
+    registrations: [Option<FiqRegistration>; FIQ_HANDLER_CAPACITY],
+}
+
+impl FiqHandler {
+    /// Create handler without registrations.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            registrations: [None; FIQ_HANDLER_CAPACITY],
+        }
+    }
+
+    /// Register handler for given interrupt.
+    ///
+    /// Replaces previously registered handler of the interrupt.
+    /// FIQ must be masked while registering.
+    ///
+    /// # Errors
+    ///
+    /// - Every handler slot is in use.
+    pub fn register(&mut self, irq: Irq, handler: fn()) -> Result<(), Error> {
+        let registration = FiqRegistration { irq, handler };
+        if let Some(slot) = self.find_slot(irq) {
+            *slot = Some(registration);
+            return Ok(());
+        }
+        let Some(slot) = self.registrations.iter_mut().find(|slot| slot.is_none()) else {
+            return Err(Error::FiqHandlersFull);
+        };
+        *slot = Some(registration);
+        Ok(())
+    }
+
+    /// Remove handler of given interrupt.
+    ///
+    /// FIQ must be masked while unregistering.
+    pub fn unregister(&mut self, irq: Irq) {
+        if let Some(slot) = self.find_slot(irq) {
+            *slot = None;
+        }
+    }
+
+    /// Get registered handler for given interrupt.
+    #[must_use]
+    pub fn get_handler(&self, irq: Irq) -> Option<fn()> {
+        self.registrations
+            .iter()
+            .flatten()
+            .find(|registration| registration.irq.as_u32() == irq.as_u32())
+            .map(|registration| registration.handler)
+    }
+
+    /// Find slot holding given interrupt's handler.
+    fn find_slot(&mut self, irq: Irq) -> Option<&mut Option<FiqRegistration>> {
+        self.registrations
+            .iter_mut()
+            .find(|slot| slot.is_some_and(|registration| registration.irq.as_u32() == irq.as_u32()))
+    }
+}
+
+impl Default for FiqHandler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Fast interrupt request handler.
+pub static mut FIQ_HANDLER: FiqHandler = FiqHandler::new();
+
+/// Handle fast interrupt request.
+///
+/// This function is called from assembly fast interrupt handler.
+/// Only secure interrupts are acknowledged.
+/// Secure interrupts without a fast interrupt handler are handled with their interrupt request handler.
+#[no_mangle]
+#[inline(never)]
+extern "C" fn handle_fiq() {
+    use crate::common::bus::Mmio;
+    use crate::interrupt::icc::Icc;
+
+    // Interrupt handler runs outside of the program's ownership of peripherals.
+    // Acknowledging and completing an interrupt does not interfere with other users of `ICC`.
+    let icc = Icc::with_bus(unsafe { Mmio::steal() });
+    // Pending non-secure interrupt is left to interrupt request handler.
+    let iar = match icc.acknowledge_secure_interrupt() {
+        Ok(iar) => iar,
+        Err(Error::UnknownIrq(interrupt_id)) => {
+            // Unknown interrupt has no handler, but it is active until completed.
+            icc.complete_interrupt_id(interrupt_id);
+            return;
+        }
+        // Spurious interrupt is not active, so it is not completed.
+        Err(_) => return,
+    };
+    (find_handler(iar))();
+    icc.complete_interrupt(iar);
+}
+
+/// Find handler for acknowledged secure interrupt.
+///
+/// Secure interrupts without a fast interrupt handler are handled with their interrupt request handler.
+/// FIQ must be masked.
+pub(crate) fn find_handler(iar: InterruptAcknowledge) -> fn() {
+    // Safety:
+    // Handlers are registered with FIQ masked.
+    let fiq_handler = unsafe { &*core::ptr::addr_of!(FIQ_HANDLER) };
+    fiq_handler
+        .get_handler(iar.irq())
+        .unwrap_or_else(|| irq::find_handler(iar))
+}
@@ -1,6 +1,44 @@
 //! Interrupt request handler.
 
 use crate::interrupt::icc::InterruptAcknowledge;
+use crate::interrupt::irq_numbers::Irq;
+
+/// Interrupt request handler registered at link time.
+///
+/// Created by `#[interrupt]` attribute macro
+/// and placed to `.interrupt_handlers` section by the linker.
+/// Runtime walks the section as an array, so layout is fixed.
+#[repr(C)]
+pub struct InterruptRegistration {
+    /// Handled interrupt.
+    pub irq: Irq,
+
+    /// Handler function.
+    pub handler: fn(),
+}
+
+/// Interrupt request handlers registered at link time.
+fn registrations() -> &'static [InterruptRegistration] {
+    #[cfg(target_arch = "arm")]
+    {
+        extern "C" {
+            static _interrupt_handlers_start: [u8; 0];
+            static _interrupt_handlers_final: [u8; 0];
+        }
+        // Safety:
+        // Linker script places registrations between the symbols.
+        unsafe {
+            let start =
+                core::ptr::addr_of!(_interrupt_handlers_start).cast::<InterruptRegistration>();
+            let end =
+                core::ptr::addr_of!(_interrupt_handlers_final).cast::<InterruptRegistration>();
+            let length = end.offset_from(start);
+            core::slice::from_raw_parts(start, length as usize)
+        }
+    }
+    #[cfg(not(target_arch = "arm"))]
+    &[]
+}
 
 /// Interrupt request handler.
 pub struct IrqHandler {
@@ -288,13 +326,75 @@ pub static mut IRQ_HANDLER: IrqHandler = IrqHandler {
 /// Handle interrupt request.
 ///
 /// This function is called from assembly interrupt handler.
+///
+/// With `nested-interrupts` feature, handler runs in system mode with interrupt requests unmasked,
+/// so interrupts with higher [priority](crate::interrupt::InterruptPriority) preempt it.
+///
+/// With FIQ signaling enabled, a secure interrupt may become pending between IRQ entry and acknowledgement,
+/// so it is acknowledged here instead of FIQ handler.
+/// Such an interrupt is dispatched to its [fast interrupt handler](super::fiq) with FIQ masked.
 #[no_mangle]
 #[inline(never)]
-fn handle_irq() {
-    use crate::interrupt::icc::ICC;
+extern "C" fn handle_irq() {
+    use crate::common::bus::Mmio;
+    use crate::interrupt::cpu;
+    use crate::interrupt::gic::Gic;
+    use crate::interrupt::icc::Icc;
+    use crate::interrupt::Error;
 
-    let iar = unsafe { ICC.acknowledge_interrupt() };
-    let handler = unsafe { IRQ_HANDLER.get_handler(iar) };
+    // Interrupt handler runs outside of the program's ownership of peripherals.
+    // Acknowledging and completing an interrupt does not interfere with other users of `ICC`.
+    let icc = Icc::with_bus(unsafe { Mmio::steal() });
+    let iar = match icc.acknowledge_interrupt() {
+        Ok(iar) => iar,
+        Err(Error::UnknownIrq(interrupt_id)) => {
+            // Unknown interrupt has no handler, but it is active until completed.
+            icc.complete_interrupt_id(interrupt_id);
+            return;
+        }
+        // Spurious interrupt is not active, so it is not completed.
+        Err(_) => return,
+    };
+    // Reading distributor's security register does not interfere with other users of `GIC`.
+    let gic = Gic::with_bus(unsafe { Mmio::steal() });
+    if icc.is_fiq_enabled() && gic.is_interrupt_secure(iar.irq()) {
+        cpu::free(|_| (super::fiq::find_handler(iar))());
+        icc.complete_interrupt(iar);
+        return;
+    }
+    let handler = find_handler(iar);
+    #[cfg(all(feature = "nested-interrupts", target_arch = "arm"))]
+    // Safety:
+    // Acknowledged interrupt is active,
+    // so GIC signals only interrupts with higher priority until the interrupt is completed.
+    // Entry stub has saved interrupt context to system mode's stack.
+    unsafe {
+        core::arch::asm!("cpsie i")
+    };
     (handler)();
-    unsafe { ICC.complete_interrupt(iar) };
+    #[cfg(all(feature = "nested-interrupts", target_arch = "arm"))]
+    // Safety:
+    // Entry stub expects interrupts to be masked when returning.
+    unsafe {
+        core::arch::asm!("cpsid i")
+    };
+    icc.complete_interrupt(iar);
+}
+
+/// Find handler for acknowledged interrupt.
+///
+/// Handlers registered at link time take precedence over [`IRQ_HANDLER`].
+pub(crate) fn find_handler(iar: InterruptAcknowledge) -> fn() {
+    registrations()
+        .iter()
+        .find(|registration| registration.irq.as_u32() == iar.irq().as_u32())
+        .map_or_else(
+            || {
+                // Safety:
+                // Handlers are function pointers, which are replaced with single stores.
+                let irq_handler = unsafe { &*core::ptr::addr_of!(IRQ_HANDLER) };
+                irq_handler.get_handler(iar)
+            },
+            |registration| registration.handler,
+        )
 }
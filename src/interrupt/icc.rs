@@ -3,26 +3,35 @@
 //! # How to use?
 //!
 //! ```ignore
-//! ICC.enable(false);
-//! ICC.set_interrupt_priority_filter(InterruptPriorityFilter::AllowAll);
-//! ICC.enable(true);
+//! let icc = peripherals.icc;
+//! icc.toggle(false);
+//! icc.set_interrupt_priority_filter(InterruptPriorityFilter::AllowAll);
+//! icc.toggle(true);
 //! ```
 //!
 //! You must configure [`GIC`](super::gic) to enable routing of interrupts to processor core.
 
+pub mod registers;
+
 use super::irq_numbers::Irq;
 use super::irq_numbers::PpiIrq;
 use super::irq_numbers::SgiIrq;
 use super::irq_numbers::SpiIrq;
+use super::Error;
 use super::InterruptPriority;
-use crate::common::bitman::ReadBitwiseRange;
-use crate::common::bitman::WriteBitwise;
-use crate::common::memman::clear_address_bit;
-use crate::common::memman::read_address_bits;
-use crate::common::memman::read_from_address;
-use crate::common::memman::set_address_bit;
-use crate::common::memman::write_address_bits;
-use crate::common::memman::write_to_address;
+use crate::common::bus::Bus;
+use crate::common::bus::Mmio;
+use crate::common::register::Value;
+use core::ops::RangeInclusive;
+use registers::aliased_binary_point;
+use registers::binary_point;
+use registers::control;
+use registers::end_of_interrupt;
+use registers::implementer_identification;
+use registers::interrupt_acknowledge;
+use registers::priority_mask;
+use registers::running_priority;
+use registers::IccRegisters;
 
 /// Which interrupt priorities are handled.
 #[derive(Clone, Copy)]
@@ -71,101 +80,180 @@ pub enum InterruptAcknowledge {
 }
 
 impl InterruptAcknowledge {
-    pub fn from_u32(value: u32) -> Self {
-        let interrupt_id = value.read_bits(0..=9);
-        match Irq::from_u32(interrupt_id) {
+    /// Decode interrupt acknowledge register.
+    ///
+    /// # Errors
+    ///
+    /// - Register holds a spurious interrupt identifier.
+    /// - Register holds an unknown interrupt identifier.
+    pub fn from_u32(value: u32) -> Result<Self, Error> {
+        let value = Value::<interrupt_acknowledge::Spec>::from_bits(value);
+        let interrupt_id = value.read(interrupt_acknowledge::ACK_INT_ID);
+        if SPURIOUS_INTERRUPT_IDS.contains(&interrupt_id) {
+            return Err(Error::SpuriousInterrupt);
+        }
+        let result = match Irq::from_u32(interrupt_id)? {
             Irq::Sgi(sgi) => {
                 // Solve which processor requested this interrupt.
-                let cpu_id = value.read_bits(10..=12);
+                let cpu_id = value.read(interrupt_acknowledge::CPU_ID);
                 Self::Sgi { sgi, cpu_id }
             }
             Irq::Ppi(ppi) => Self::Ppi { ppi },
             Irq::Spi(spi) => Self::Spi { spi },
-        }
+        };
+        Ok(result)
     }
 
-    pub fn as_u32(self) -> u32 {
-        let mut result = 0;
+    /// Acknowledged interrupt.
+    #[inline]
+    #[must_use]
+    pub fn irq(self) -> Irq {
         match self {
-            Self::Sgi { sgi, cpu_id } => {
-                let interrupt_id = sgi.as_u32();
-                result = result.write_bits(0, interrupt_id, 10);
-                result = result.write_bits(10, cpu_id, 3);
-            }
-            Self::Ppi { ppi } => {
-                let interrupt_id = ppi.as_u32();
-                result = result.write_bits(0, interrupt_id, 10);
-            }
-            Self::Spi { spi } => {
-                let interrupt_id = spi.as_u32();
-                result = result.write_bits(0, interrupt_id, 10);
-            }
+            Self::Sgi { sgi, cpu_id: _ } => Irq::Sgi(sgi),
+            Self::Ppi { ppi } => Irq::Ppi(ppi),
+            Self::Spi { spi } => Irq::Spi(spi),
         }
-        result
+    }
+
+    pub fn as_u32(self) -> u32 {
+        let value = Value::<end_of_interrupt::Spec>::from_bits(0);
+        let value = match self {
+            Self::Sgi { sgi, cpu_id } => value
+                .write(end_of_interrupt::EOI_INT_ID, sgi.as_u32())
+                .write(end_of_interrupt::CPU_ID, cpu_id),
+            Self::Ppi { ppi } => value.write(end_of_interrupt::EOI_INT_ID, ppi.as_u32()),
+            Self::Spi { spi } => value.write(end_of_interrupt::EOI_INT_ID, spi.as_u32()),
+        };
+        value.bits()
     }
 }
 
 /// CPU interrupt interface.
 ///
 /// `ICC` is responsible for signaling interrupts to CPU.
-pub struct Icc {
-    address_interface_control: *mut u32,
-    address_interrupt_priority_mask: *mut u32,
-    address_binary_point: *mut u32,
-    address_interrupt_acknowledge: *mut u32,
-    address_end_of_interrupt: *mut u32,
-    address_running_priority: *mut u32,
-    address_highest_pending_interrupt: *mut u32,
-    address_aliased_non_secure_binary_point_register: *mut u32,
-    address_implementer_identification: *mut u32,
+pub struct Icc<B = Mmio> {
+    /// CPU interface registers.
+    registers: IccRegisters<B>,
 }
 
-impl Icc {
+impl<B> Icc<B> {
+    /// Create interface for CPU interrupt interface using given register access.
+    #[must_use]
+    pub const fn with_bus(bus: B) -> Self {
+        Self {
+            registers: IccRegisters::new(ADDRESS_BASE, bus),
+        }
+    }
+
+    /// CPU interface registers.
+    #[inline]
+    #[must_use]
+    pub fn registers(&self) -> &IccRegisters<B> {
+        &self.registers
+    }
+}
+
+impl<B: Bus> Icc<B> {
     // TODO: other controls
 
     /// Enable or disable `ICC`.
     #[inline]
     pub fn toggle(&self, enable: bool) {
-        let action = if enable {
-            set_address_bit
-        } else {
-            clear_address_bit
-        };
-        // Secure interrupts.
-        action(self.address_interface_control, 0);
-        // Non-secure interrupts.
-        action(self.address_interface_control, 1);
+        self.registers.control().modify(|r| {
+            r
+                // Secure interrupts.
+                .set(control::ENABLE_SECURE, enable)
+                // Non-secure interrupts.
+                .set(control::ENABLE_NON_SECURE, enable)
+        });
+    }
+
+    /// Enable or disable signaling of secure interrupts with FIQ.
+    ///
+    /// When enabled, IRQ handler, which runs in secure state, may also acknowledge non-secure interrupts.
+    /// FIQ handler acknowledges only secure interrupts, see [`Self::acknowledge_secure_interrupt`].
+    ///
+    /// IRQ handler may still acknowledge a secure interrupt which became pending when IRQ entry raced FIQ.
+    /// IRQ handler dispatches such an interrupt to its fast interrupt handler.
+    #[inline]
+    pub fn toggle_fiq(&self, enable: bool) {
+        self.registers
+            .control()
+            .modify(|r| r.set(control::FIQ_EN, enable).set(control::ACK_CTL, enable));
+    }
+
+    /// True if secure interrupts are signaled with FIQ.
+    #[inline]
+    #[must_use]
+    pub fn is_fiq_enabled(&self) -> bool {
+        self.registers.control().read().is_set(control::FIQ_EN)
     }
 
     /// Set which interrupts are handled.
     #[inline]
     pub fn set_interrupt_priority_filter(&self, value: InterruptPriorityFilter) {
         let value = value.as_u8();
-        write_address_bits(self.address_interrupt_priority_mask, 0..=7, value as u32);
+        self.registers
+            .priority_mask()
+            .modify(|r| r.write(priority_mask::PRIORITY, value as u32));
     }
 
     // TODO: helpers, set priority filter to minimum, maximum etc
 
     // TODO: what is this?
     pub fn set_binary_point(&self, value: u8) {
-        // TODO: value is 2 bits
-        write_address_bits(self.address_binary_point, 0..=2, value as u32)
+        self.registers
+            .binary_point()
+            .modify(|r| r.write(binary_point::BINARY_POINT, value as u32));
     }
 
     // TODO: what is this?
     pub fn get_binary_point(&self) -> u8 {
-        // TODO: value is 2 bits
-        read_address_bits(self.address_binary_point, 0..=2) as u8
+        self.registers
+            .binary_point()
+            .read()
+            .read(binary_point::BINARY_POINT) as u8
     }
 
     /// Accept interrupt from `GIC`.
     ///
     /// After acknowledgement, the `GIC` updates interrupt's state.
     /// Next state is either *active* or *active and pending*.
+    ///
+    /// # Errors
+    ///
+    /// - No interrupt is pending, or interrupt was acknowledged elsewhere.
+    ///   Spurious interrupt must not be completed.
+    /// - Acknowledge register holds an unknown interrupt identifier.
     #[inline]
-    pub fn acknowledge_interrupt(&self) -> InterruptAcknowledge {
+    pub fn acknowledge_interrupt(&self) -> Result<InterruptAcknowledge, Error> {
         // IAR must be read once.
-        let iar = read_from_address(self.address_interrupt_acknowledge);
+        let iar = self.registers.interrupt_acknowledge().read().bits();
+        InterruptAcknowledge::from_u32(iar)
+    }
+
+    /// Accept secure interrupt from `GIC`.
+    ///
+    /// Acknowledgement of non-secure interrupts is disabled while acknowledge register is read,
+    /// so a pending non-secure interrupt is left to IRQ handler.
+    /// Interrupts must be masked.
+    ///
+    /// # Errors
+    ///
+    /// - No secure interrupt is pending.
+    ///   Spurious interrupt must not be completed.
+    /// - Acknowledge register holds an unknown interrupt identifier.
+    #[inline]
+    pub fn acknowledge_secure_interrupt(&self) -> Result<InterruptAcknowledge, Error> {
+        // Control register is banked per core and interrupts are masked,
+        // so it can be written without read-modify-write lock.
+        let control = self.registers.control().read();
+        self.registers
+            .control()
+            .write_bits(control.set(control::ACK_CTL, false).bits());
+        // IAR must be read once.
+        let iar = self.registers.interrupt_acknowledge().read().bits();
+        self.registers.control().write_bits(control.bits());
         InterruptAcknowledge::from_u32(iar)
     }
 
@@ -174,49 +262,64 @@ impl Icc {
     pub fn complete_interrupt(&self, value: InterruptAcknowledge) {
         // EOIR must be written once.
         let value = value.as_u32();
-        write_to_address(self.address_end_of_interrupt, value);
+        self.registers.end_of_interrupt().write_bits(value);
     }
 
-    /// Get priority of highest priority interrupt that is active.
-    pub fn running_priority(&self) -> InterruptPriority {
-        let value = read_address_bits(self.address_running_priority, 0..=7);
-        InterruptPriority::from_u8(value as u8)
+    /// Signal to `GIC` that handling of interrupt with given identifier is complete.
+    ///
+    /// Use this to complete interrupts which could not be decoded.
+    #[inline]
+    pub fn complete_interrupt_id(&self, interrupt_id: u32) {
+        self.registers
+            .end_of_interrupt()
+            .write_with_zero(|r| r.write(end_of_interrupt::EOI_INT_ID, interrupt_id));
     }
 
-    /// Set priority of highest priority interrupt that is active.
-    pub fn set_running_priority(&self, value: InterruptPriority) {
-        let value = value.as_u8();
-        write_address_bits(self.address_running_priority, 0..=7, value as u32)
+    /// Get priority of highest priority interrupt that is active.
+    ///
+    /// Returns `None` when no interrupt is active.
+    ///
+    /// # Errors
+    ///
+    /// - Register holds an invalid priority.
+    pub fn running_priority(&self) -> Result<Option<InterruptPriority>, Error> {
+        let value = self
+            .registers
+            .running_priority()
+            .read()
+            .read(running_priority::PRIORITY);
+        match value {
+            // Idle priority.
+            0xFF => Ok(None),
+            value => InterruptPriority::from_u8(value as u8).map(Some),
+        }
     }
 
     /// Get highest pending interrupt.
+    ///
+    /// # Errors
+    ///
+    /// - Register holds an unknown interrupt identifier.
     // TODO: is it possible to alias IAR to HPI?
-    pub fn highest_pending_interrupt(&self) -> InterruptAcknowledge {
-        let iar = read_from_address(self.address_highest_pending_interrupt);
+    pub fn highest_pending_interrupt(&self) -> Result<InterruptAcknowledge, Error> {
+        // Highest pending interrupt register has the same layout as IAR.
+        let iar = self.registers.highest_pending().read().bits();
         InterruptAcknowledge::from_u32(iar)
     }
 
-    /// Set highest pending interrupt.
-    // TODO: is it possible to alias IAR to HPI?
-    pub fn set_highest_pending_interrupt(&self, iar: InterruptAcknowledge) {
-        let value = iar.as_u32();
-        write_to_address(self.address_highest_pending_interrupt, value);
-    }
-
     // TODO: what is this?
     pub fn set_non_secure_binary_point(&self, value: u8) {
-        // TODO: value is 2 bits
-        write_address_bits(
-            self.address_aliased_non_secure_binary_point_register,
-            0..=2,
-            value as u32,
-        )
+        self.registers
+            .aliased_binary_point()
+            .modify(|r| r.write(aliased_binary_point::BINARY_POINT, value as u32));
     }
 
     // TODO: what is this?
     pub fn get_non_secure_binary_point(&self) -> u8 {
-        // TODO: value is 2 bits
-        read_address_bits(self.address_aliased_non_secure_binary_point_register, 0..=2) as u8
+        self.registers
+            .aliased_binary_point()
+            .read()
+            .read(aliased_binary_point::BINARY_POINT) as u8
     }
 
     // TODO: maybe give identification as a struct
@@ -224,39 +327,118 @@ impl Icc {
     #[inline]
     #[must_use]
     pub fn implementer(&self) -> u32 {
-        read_address_bits(self.address_implementer_identification, 0..=11)
+        self.registers
+            .implementer_identification()
+            .read()
+            .read(implementer_identification::IMPLEMENTER)
     }
 
     #[inline]
     #[must_use]
     pub fn revision_number(&self) -> u32 {
-        read_address_bits(self.address_implementer_identification, 12..=15)
+        self.registers
+            .implementer_identification()
+            .read()
+            .read(implementer_identification::REVISION)
     }
 
     #[inline]
     #[must_use]
     pub fn architecture_version(&self) -> u32 {
-        read_address_bits(self.address_implementer_identification, 16..=19)
+        self.registers
+            .implementer_identification()
+            .read()
+            .read(implementer_identification::ARCHITECTURE)
     }
 
     #[inline]
     #[must_use]
     pub fn part_number(&self) -> u32 {
-        read_address_bits(self.address_implementer_identification, 20..=31)
+        self.registers
+            .implementer_identification()
+            .read()
+            .read(implementer_identification::PART)
     }
 }
 
 const ADDRESS_BASE: u32 = 0xF8F0_0100;
 
-/// CPU interrupt interface.
-pub static mut ICC: Icc = Icc {
-    address_interface_control: (ADDRESS_BASE + 0x00) as *mut u32,
-    address_interrupt_priority_mask: (ADDRESS_BASE + 0x04) as *mut u32,
-    address_binary_point: (ADDRESS_BASE + 0x08) as *mut u32,
-    address_interrupt_acknowledge: (ADDRESS_BASE + 0x0C) as *mut u32,
-    address_end_of_interrupt: (ADDRESS_BASE + 0x10) as *mut u32,
-    address_running_priority: (ADDRESS_BASE + 0x14) as *mut u32,
-    address_highest_pending_interrupt: (ADDRESS_BASE + 0x18) as *mut u32,
-    address_aliased_non_secure_binary_point_register: (ADDRESS_BASE + 0x1C) as *mut u32,
-    address_implementer_identification: (ADDRESS_BASE + 0xFC) as *mut u32,
-};
+/// Interrupt identifiers returned when no interrupt is acknowledged.
+///
+/// - 1022: highest priority pending interrupt is non-secure and may not be acknowledged.
+/// - 1023: no interrupt is pending.
+const SPURIOUS_INTERRUPT_IDS: RangeInclusive<u32> = 1022..=1023;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::bus::SimulatedBus;
+
+    #[test]
+    fn spurious_and_unknown_identifiers_are_errors() {
+        assert!(matches!(
+            InterruptAcknowledge::from_u32(1023),
+            Err(Error::SpuriousInterrupt)
+        ));
+        assert!(matches!(
+            InterruptAcknowledge::from_u32(1022),
+            Err(Error::SpuriousInterrupt)
+        ));
+        assert!(matches!(
+            InterruptAcknowledge::from_u32(20),
+            Err(Error::UnknownIrq(20))
+        ));
+        assert!(matches!(
+            InterruptAcknowledge::from_u32(59),
+            Ok(InterruptAcknowledge::Spi { spi: SpiIrq::Uart0 })
+        ));
+    }
+
+    #[test]
+    fn secure_acknowledge_restores_control() {
+        let bus = SimulatedBus::<4, 4>::new();
+        let icc = Icc::with_bus(&bus);
+        let control = 0xF8F0_0100 as *mut u32;
+        // Both groups enabled, non-secure acknowledge and FIQ signaling enabled.
+        bus.preload(control, 0b1111);
+        bus.preload(0xF8F0_010C as *mut u32, 1022);
+
+        assert!(matches!(
+            icc.acknowledge_secure_interrupt(),
+            Err(Error::SpuriousInterrupt)
+        ));
+        assert!(bus.writes().eq([(control, 0b1011), (control, 0b1111)]));
+    }
+
+    #[test]
+    fn running_priority_is_none_when_idle() {
+        let bus = SimulatedBus::<4, 4>::new();
+        let icc = Icc::with_bus(&bus);
+        let running_priority = 0xF8F0_0114 as *mut u32;
+
+        bus.preload(running_priority, 0xFF);
+        assert!(matches!(icc.running_priority(), Ok(None)));
+        bus.preload(running_priority, 0x08);
+        assert!(matches!(
+            icc.running_priority(),
+            Ok(Some(InterruptPriority::Priority1))
+        ));
+        bus.preload(running_priority, 0x04);
+        assert!(icc.running_priority().is_err());
+    }
+
+    #[test]
+    fn fiq_signaling_lets_irq_acknowledge_non_secure_interrupts() {
+        let bus = SimulatedBus::<4, 4>::new();
+        let icc = Icc::with_bus(&bus);
+        let control = 0xF8F0_0100 as *mut u32;
+        bus.preload(control, 0b0011);
+
+        icc.toggle_fiq(true);
+        assert_eq!(bus.value(control), 0b1111);
+        assert!(icc.is_fiq_enabled());
+        icc.toggle_fiq(false);
+        assert_eq!(bus.value(control), 0b0011);
+        assert!(!icc.is_fiq_enabled());
+    }
+}
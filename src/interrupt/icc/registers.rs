@@ -0,0 +1,82 @@
+//! CPU interrupt interface registers.
+
+crate::register_block! {
+    /// CPU interrupt interface registers.
+    pub IccRegisters {
+        /// CPU interface control register.
+        0x00 => control: ReadWrite, reset = 0x0000_0000 {
+            /// Enable signaling of secure interrupts.
+            ENABLE_SECURE: 0, 1;
+            /// Enable signaling of non-secure interrupts.
+            ENABLE_NON_SECURE: 1, 1;
+            /// Secure acknowledge of non-secure interrupts.
+            ACK_CTL: 2, 1;
+            /// Signal secure interrupts with FIQ.
+            FIQ_EN: 3, 1;
+            /// Use secure binary point register for non-secure interrupts.
+            SBPR: 4, 1;
+        }
+
+        /// Interrupt priority mask register.
+        0x04 => priority_mask: ReadWrite, reset = 0x0000_0000 {
+            /// Interrupts with lower priority are not signaled.
+            PRIORITY: 0, 8;
+        }
+
+        /// Binary point register.
+        0x08 => binary_point: ReadWrite, reset = 0x0000_0002 {
+            /// Split of priority to group priority and subpriority.
+            BINARY_POINT: 0, 3;
+        }
+
+        /// Interrupt acknowledge register.
+        ///
+        /// Reading acknowledges the interrupt.
+        0x0C => interrupt_acknowledge: ReadOnly, reset = 0x0000_03FF {
+            /// Interrupt identifier.
+            ACK_INT_ID: 0, 10;
+            /// CPU which requested the software generated interrupt.
+            CPU_ID: 10, 3;
+        }
+
+        /// End of interrupt register.
+        0x10 => end_of_interrupt: WriteOnly, reset = 0x0000_0000 {
+            /// Interrupt identifier.
+            EOI_INT_ID: 0, 10;
+            /// CPU which requested the software generated interrupt.
+            CPU_ID: 10, 3;
+        }
+
+        /// Running priority register.
+        0x14 => running_priority: ReadOnly, reset = 0x0000_00FF {
+            /// Priority of highest priority interrupt that is active.
+            PRIORITY: 0, 8;
+        }
+
+        /// Highest pending interrupt register.
+        0x18 => highest_pending: ReadOnly, reset = 0x0000_03FF {
+            /// Interrupt identifier.
+            PEND_INT_ID: 0, 10;
+            /// CPU which requested the software generated interrupt.
+            CPU_ID: 10, 3;
+        }
+
+        /// Aliased non-secure binary point register.
+        0x1C => aliased_binary_point: ReadWrite, reset = 0x0000_0003 {
+            /// Split of priority to group priority and subpriority.
+            BINARY_POINT: 0, 3;
+        }
+
+        /// CPU interface implementer identification register.
+        0xFC => implementer_identification: ReadOnly, reset = 0x3901_243B {
+            /// Implementer.
+            IMPLEMENTER: 0, 12;
+            /// Revision number.
+            REVISION: 12, 4;
+            /// Architecture version.
+            ARCHITECTURE: 16, 4;
+            /// Part number.
+            PART: 20, 12;
+        }
+    }
+}
@@ -1,5 +1,7 @@
 //! Interrupt request numbers.
 
+use super::Error;
+
 /// Software generated interrupts.
 pub mod sgi {
     pub const IRQ_SGI_0: u32 = 0;
@@ -22,6 +24,7 @@ pub mod sgi {
 
 /// Software generated interrupt.
 #[derive(Clone, Copy)]
+#[repr(u32)]
 pub enum SgiIrq {
     Sgi0,
     Sgi1,
@@ -109,6 +112,7 @@ pub mod ppi {
 
 /// Private peripheral interrupt.
 #[derive(Clone, Copy)]
+#[repr(u32)]
 pub enum PpiIrq {
     GlobalTimer,
     NFiq,
@@ -209,6 +213,7 @@ pub mod spi {
 
 /// Shared peripheral interrupt.
 #[derive(Clone, Copy)]
+#[repr(u32)]
 pub enum SpiIrq {
     Cpu0,
     Cpu1,
@@ -410,6 +415,7 @@ impl SpiIrq {
 
 /// Interrupt request.
 #[derive(Clone, Copy)]
+#[repr(C)]
 pub enum Irq {
     /// Software generated interrupt.
     Sgi(SgiIrq),
@@ -422,15 +428,20 @@ pub enum Irq {
 }
 
 impl Irq {
-    pub fn from_u32(value: u32) -> Self {
+    /// Transform from interrupt identifier.
+    ///
+    /// # Errors
+    ///
+    /// - Value is not a known interrupt identifier.
+    pub fn from_u32(value: u32) -> Result<Self, Error> {
         if let Ok(sgi) = SgiIrq::from_u32(value) {
-            Self::Sgi(sgi)
+            Ok(Self::Sgi(sgi))
         } else if let Ok(ppi) = PpiIrq::from_u32(value) {
-            Self::Ppi(ppi)
+            Ok(Self::Ppi(ppi))
         } else if let Ok(spi) = SpiIrq::from_u32(value) {
-            Self::Spi(spi)
+            Ok(Self::Spi(spi))
         } else {
-            panic!("Unknown IRQ number: {value}");
+            Err(Error::UnknownIrq(value))
         }
     }
 
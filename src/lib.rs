@@ -3,6 +3,18 @@
 #![no_std]
 #![allow(unused)]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
 pub mod common;
+pub mod console;
+#[cfg(feature = "alloc")]
+pub mod heap;
 pub mod interrupt;
+pub mod multicore;
 pub mod peripheral;
+pub mod sync;
+
+pub use pynq_z1_bsp_macros::entry;
+pub use pynq_z1_bsp_macros::exception;
+pub use pynq_z1_bsp_macros::interrupt;
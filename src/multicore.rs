@@ -0,0 +1,136 @@
+//! Multi-core support.
+//!
+//! After boot, CPU 1 waits in boot ROM for an event.
+//! When woken up, it jumps to the address stored at [`CPU1_WAKE_UP_ADDRESS`].
+//! [`start_cpu1`] stores runtime's CPU 1 entry there and wakes CPU 1 up.
+//! Runtime sets CPU 1's own stacks, MMU and caches, and [CPU interrupt interface](crate::interrupt::icc),
+//! and calls the given function.
+//!
+//! Each core has banked CPU interrupt interface, private timers
+//! and distributor registers for software generated and private peripheral interrupts,
+//! so CPU 1 must configure them itself.
+//!
+//! # How to use?
+//!
+//! ```ignore
+//! fn main_cpu1() -> ! {
+//!     sprintln!("Hello from CPU {}", multicore::cpu_id());
+//!     loop {}
+//! }
+//!
+//! multicore::start_cpu1(main_cpu1)?;
+//! ```
+
+use crate::common::bus::Mmio;
+use crate::common::memman::write_to_address;
+use crate::interrupt::icc::Icc;
+use crate::interrupt::icc::InterruptPriorityFilter;
+use crate::peripheral::slcr::Cpu;
+use core::sync::atomic::AtomicBool;
+use core::sync::atomic::AtomicUsize;
+use core::sync::atomic::Ordering;
+
+/// Boot ROM reads CPU 1's entry address from here after an event.
+pub const CPU1_WAKE_UP_ADDRESS: u32 = 0xFFFF_FFF0;
+
+/// Multi-core error.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// CPU 1 has already been started.
+    AlreadyStarted,
+}
+
+impl core::fmt::Display for Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::AlreadyStarted => write!(f, "CPU 1 has already been started"),
+        }
+    }
+}
+
+/// True after CPU 1 has been started.
+static CPU1_STARTED: AtomicBool = AtomicBool::new(false);
+
+/// Function called on CPU 1, stored as an address.
+static CPU1_ENTRY: AtomicUsize = AtomicUsize::new(0);
+
+/// Identifier of the core executing this function.
+///
+/// Read from multiprocessor affinity register.
+#[inline]
+#[must_use]
+pub fn cpu_id() -> u32 {
+    #[cfg(target_arch = "arm")]
+    {
+        let mpidr: u32;
+        // Safety:
+        // Reading multiprocessor affinity register does not cause side-effects.
+        unsafe { core::arch::asm!("mrc p15, 0, {}, c0, c0, 5", out(reg) mpidr) };
+        mpidr & 0b11
+    }
+    #[cfg(not(target_arch = "arm"))]
+    0
+}
+
+/// Core executing this function.
+#[inline]
+#[must_use]
+pub fn current_cpu() -> Cpu {
+    if cpu_id() == 0 {
+        Cpu::Cpu0
+    } else {
+        Cpu::Cpu1
+    }
+}
+
+/// Start executing given function on CPU 1.
+///
+/// CPU 1 must be waiting in boot ROM, as it is after boot.
+/// Memory must be initialized, so call this from CPU 0's program.
+///
+/// # Errors
+///
+/// - CPU 1 has already been started.
+pub fn start_cpu1(entry: fn() -> !) -> Result<(), Error> {
+    if CPU1_STARTED.swap(true, Ordering::AcqRel) {
+        return Err(Error::AlreadyStarted);
+    }
+    CPU1_ENTRY.store(entry as usize, Ordering::Release);
+
+    #[cfg(target_arch = "arm")]
+    {
+        extern "C" {
+            fn _boot_cpu1();
+        }
+        // Safety:
+        // CPU 1 wake-up address is in on-chip memory, which is always mapped.
+        unsafe {
+            write_to_address(
+                CPU1_WAKE_UP_ADDRESS as *mut u32,
+                _boot_cpu1 as *const () as usize as u32,
+            )
+        };
+        // Safety:
+        // Entry address is written to memory before CPU 1 is woken up.
+        unsafe { core::arch::asm!("dsb", "sev") };
+    }
+    Ok(())
+}
+
+/// Prepare CPU 1 and call its entry.
+///
+/// This function is called from assembly CPU 1 boot code after stacks are set.
+#[no_mangle]
+#[inline(never)]
+extern "C" fn enter_cpu1() -> ! {
+    // CPU interface is banked, so this configures CPU 1's interface only.
+    let icc = Icc::with_bus(unsafe { Mmio::steal() });
+    icc.set_interrupt_priority_filter(InterruptPriorityFilter::AllowAll);
+    icc.toggle(true);
+
+    let entry = CPU1_ENTRY.load(Ordering::Acquire);
+    // Safety:
+    // Entry was stored from a function pointer before CPU 1 was woken up.
+    let entry: fn() -> ! = unsafe { core::mem::transmute(entry) };
+    entry()
+}
@@ -1,4 +1,18 @@
 //! Interfaces for peripherals.
+//!
+//! Peripherals are owned.
+//! [`Peripherals::take`] hands out the interface for each peripheral once,
+//! so two parts of a program can not configure the same peripheral at the same time.
+//!
+//! # How to use?
+//!
+//! ```ignore
+//! let peripherals = Peripherals::take().unwrap();
+//! let uart = peripherals.uart0;
+//! uart.configure(&peripherals.slcr).unwrap();
+//! let (timer0, timer1, timer2) = peripherals.ttc0.split();
+//! let banks = peripherals.gpio.split();
+//! ```
 
 pub mod axi;
 pub mod gpio;
@@ -6,3 +20,105 @@ pub mod led;
 pub mod slcr;
 pub mod timers;
 pub mod uart;
+
+use crate::common::bus::Mmio;
+use crate::interrupt::gic::Gic;
+use crate::interrupt::icc::Icc;
+use core::sync::atomic::AtomicBool;
+use core::sync::atomic::Ordering;
+use gpio::Gpio;
+use slcr::Slcr;
+use timers::timer_global::Comparator;
+use timers::timer_global::TimerGlobal;
+use timers::timer_private::TimerPrivate;
+use timers::timer_ttc::TripleTimerCounter;
+use timers::timer_ttc::ADDRESS_BASE_TTC0;
+use timers::timer_ttc::ADDRESS_BASE_TTC1;
+use timers::timer_watchdog::private_watchdog_timer::PrivateWatchdogTimer;
+use uart::DeviceIndex as UartDeviceIndex;
+use uart::Uart;
+
+/// True if peripherals have been taken.
+static TAKEN: AtomicBool = AtomicBool::new(false);
+
+/// Processing system peripherals.
+pub struct Peripherals {
+    /// UART 0.
+    pub uart0: Uart,
+
+    /// UART 1.
+    pub uart1: Uart,
+
+    /// General purpose input and output.
+    pub gpio: Gpio,
+
+    /// General interrupt controller.
+    pub gic: Gic,
+
+    /// CPU interrupt interface.
+    pub icc: Icc,
+
+    /// System level control registers.
+    pub slcr: Slcr,
+
+    /// Global timer.
+    pub timer_global: TimerGlobal,
+
+    /// Global timer's comparator.
+    pub comparator: Comparator,
+
+    /// Processor core's private timer.
+    pub timer_private: TimerPrivate,
+
+    /// Processor core's private watchdog timer.
+    pub private_watchdog_timer: PrivateWatchdogTimer,
+
+    /// Triple timer counter 0.
+    pub ttc0: TripleTimerCounter,
+
+    /// Triple timer counter 1.
+    pub ttc1: TripleTimerCounter,
+}
+
+impl Peripherals {
+    /// Take peripherals.
+    ///
+    /// Returns `None` if peripherals have already been taken.
+    #[inline]
+    #[must_use]
+    pub fn take() -> Option<Self> {
+        if TAKEN.swap(true, Ordering::AcqRel) {
+            None
+        } else {
+            Some(unsafe { Self::steal() })
+        }
+    }
+
+    /// Create peripherals whether or not they have been taken.
+    ///
+    /// # Safety
+    ///
+    /// Returned interfaces may alias interfaces owned elsewhere.
+    /// Caller must ensure that aliasing interfaces do not access the same registers concurrently.
+    /// Use this in panic and interrupt handlers only.
+    #[inline]
+    #[must_use]
+    pub unsafe fn steal() -> Self {
+        TAKEN.store(true, Ordering::Release);
+        let bus = Mmio::steal();
+        Self {
+            uart0: Uart::with_bus(UartDeviceIndex::Uart0, bus),
+            uart1: Uart::with_bus(UartDeviceIndex::Uart1, bus),
+            gpio: Gpio::with_bus(bus),
+            gic: Gic::with_bus(bus),
+            icc: Icc::with_bus(bus),
+            slcr: Slcr::with_bus(bus),
+            timer_global: TimerGlobal::with_bus(bus),
+            comparator: Comparator::with_bus(bus),
+            timer_private: TimerPrivate::with_bus(bus),
+            private_watchdog_timer: PrivateWatchdogTimer::with_bus(bus),
+            ttc0: TripleTimerCounter::with_bus(ADDRESS_BASE_TTC0, bus),
+            ttc1: TripleTimerCounter::with_bus(ADDRESS_BASE_TTC1, bus),
+        }
+    }
+}
@@ -1,9 +1,41 @@
-use crate::common::memman::clear_address_bit;
-use crate::common::memman::read_address_bit;
-use crate::common::memman::set_address_bit;
-use crate::common::memman::write_to_address;
+pub mod registers;
+
+use crate::common::bus::Bus;
+use crate::common::bus::Mmio;
+use crate::common::snapshot::Snapshot;
+use registers::data;
+use registers::global_interrupt_enable;
+use registers::ip_interrupt_enable;
+use registers::ip_interrupt_status;
+use registers::tri;
+use registers::AxiGpioRegisters;
+
+/// AXI GPIO error.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// Channel does not have given pin.
+    InvalidPin(u32),
+
+    /// Pin is configured as output, so it can not be read.
+    PinIsOutput(u32),
+
+    /// Pin is configured as input, so it can not be written.
+    PinIsInput(u32),
+
+    /// Device is configured with a single channel, so channel 2 can not be used.
+    SingleChannel,
+}
 
-// TODO: add error strings
+impl core::fmt::Display for Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::InvalidPin(index) => write!(f, "given pin {index} does not exist"),
+            Self::PinIsOutput(index) => write!(f, "can not read output pin {index}"),
+            Self::PinIsInput(index) => write!(f, "can not write to input pin {index}"),
+            Self::SingleChannel => write!(f, "device does not have channel 2"),
+        }
+    }
+}
 
 #[derive(Clone, Copy)]
 pub enum PinDirection {
@@ -68,109 +100,202 @@ impl core::fmt::Display for Channels {
     }
 }
 
-pub struct Channel {
-    address_data: *mut u32,
-    address_control: *mut u32,
+pub struct Channel<B = Mmio> {
+    registers: AxiGpioRegisters<B>,
+    channel: ChannelIndex,
     width: u32,
 }
 
 impl Channel {
-    pub fn new(address_data: *mut u32, address_control: *mut u32, width: u32) -> Self {
+    /// Create interface for channel of AXI GPIO block at given base address.
+    ///
+    /// # Safety
+    ///
+    /// Address must be the base address of a mapped AXI GPIO block in programmable logic,
+    /// which is not accessed through another interface.
+    pub unsafe fn new(address_base: *mut u32, channel: ChannelIndex, width: u32) -> Self {
+        // Safety:
+        // Programmable logic is not owned by `Peripherals`, caller guarantees exclusive access.
+        Self::with_bus(address_base, channel, width, unsafe { Mmio::steal() })
+    }
+}
+
+impl<B> Channel<B> {
+    /// Create interface for channel using given register access.
+    pub fn with_bus(address_base: *mut u32, channel: ChannelIndex, width: u32, bus: B) -> Self {
         Self {
-            address_data,
-            address_control,
+            registers: AxiGpioRegisters::new(address_base as u32, bus),
+            channel,
             width,
         }
     }
+}
+
+impl<B: Bus> Channel<B> {
+    /// Get interface for single pin of this channel.
+    ///
+    /// # Errors
+    ///
+    /// - Channel does not have given pin.
+    pub fn pin(&self, index: u32) -> Result<Pin<'_, B>, Error> {
+        self.check_pin(index)?;
+        Ok(Pin {
+            channel: self,
+            index,
+        })
+    }
 
     pub fn address_data(&self) -> *mut u32 {
-        self.address_data
+        self.registers.data(self.channel.as_u32()).address()
     }
 
     pub fn address_control(&self) -> *mut u32 {
-        self.address_control
+        self.registers.tri(self.channel.as_u32()).address()
     }
 
     pub fn width(&self) -> u32 {
         self.width
     }
 
-    pub fn pin_direction(&self, index: u32) -> Result<PinDirection, ()> {
+    /// Check that channel has given pin.
+    fn check_pin(&self, index: u32) -> Result<(), Error> {
         if (0..self.width).contains(&index) {
-            let value = read_address_bit(self.address_control, index);
-            let direction = PinDirection::from_bool(value);
-            Ok(direction)
+            Ok(())
         } else {
-            Err(())
+            Err(Error::InvalidPin(index))
         }
     }
 
-    pub fn read_pin(&self, index: u32) -> Result<bool, ()> {
-        if (0..self.width).contains(&index) {
-            match self.pin_direction(index).unwrap() {
-                PinDirection::Output => Err(()),
-                PinDirection::Input => Ok(read_address_bit(self.address_data, index)),
-            }
-        } else {
-            Err(())
+    pub fn pin_direction(&self, index: u32) -> Result<PinDirection, Error> {
+        self.check_pin(index)?;
+        let value = self
+            .registers
+            .tri(self.channel.as_u32())
+            .read()
+            .is_set(tri::pin(index));
+        Ok(PinDirection::from_bool(value))
+    }
+
+    pub fn read_pin(&self, index: u32) -> Result<bool, Error> {
+        match self.pin_direction(index)? {
+            PinDirection::Output => Err(Error::PinIsOutput(index)),
+            PinDirection::Input => Ok(self
+                .registers
+                .data(self.channel.as_u32())
+                .read()
+                .is_set(data::pin(index))),
         }
     }
 
-    pub fn write_pin(&self, index: u32, value: bool) -> Result<(), &'static str> {
-        if (0..self.width).contains(&index) {
-            match self.pin_direction(index).unwrap() {
-                PinDirection::Output => {
-                    let action = if value {
-                        set_address_bit
-                    } else {
-                        clear_address_bit
-                    };
-                    action(self.address_data, index);
-                    Ok(())
-                }
-                PinDirection::Input => Err("can not write to input pin"),
+    pub fn write_pin(&self, index: u32, value: bool) -> Result<(), Error> {
+        match self.pin_direction(index)? {
+            PinDirection::Output => {
+                self.registers
+                    .data(self.channel.as_u32())
+                    .modify(|r| r.set(data::pin(index), value));
+                Ok(())
             }
-        } else {
-            Err("given pin does not exist")
+            PinDirection::Input => Err(Error::PinIsInput(index)),
         }
     }
 
-    pub fn set_pin_direction(&self, index: u32, direction: PinDirection) -> Result<(), ()> {
-        if (0..self.width).contains(&index) {
-            let action = if direction.as_bool() {
-                set_address_bit
-            } else {
-                clear_address_bit
-            };
-            action(self.address_control, index);
-            Ok(())
-        } else {
-            Err(())
-        }
+    pub fn set_pin_direction(&self, index: u32, direction: PinDirection) -> Result<(), Error> {
+        self.check_pin(index)?;
+        self.registers
+            .tri(self.channel.as_u32())
+            .modify(|r| r.set(tri::pin(index), direction.as_bool()));
+        Ok(())
     }
 
     pub fn reset(&self) {
-        write_to_address(self.address_control, 0);
-        write_to_address(self.address_control, 0);
+        self.registers.tri(self.channel.as_u32()).write_bits(0);
+        self.registers.tri(self.channel.as_u32()).write_bits(0);
     }
 }
 
-impl core::fmt::Display for Channel {
+impl<B: Bus> core::fmt::Display for Channel<B> {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        let value_data = unsafe { core::ptr::read_volatile(self.address_data) };
-        let value_control = unsafe { core::ptr::read_volatile(self.address_control) };
+        let value_data = self.registers.data(self.channel.as_u32()).read().bits();
+        let value_control = self.registers.tri(self.channel.as_u32()).read().bits();
         write!(
             f,
             "data @ 0x{:0>8X}=0b{:0>32b}, control @ 0x{:0>8X}=0b{:0>32b}, width={}",
-            self.address_data as u32,
+            self.address_data() as u32,
             value_data,
-            self.address_control as u32,
+            self.address_control() as u32,
             value_control,
             self.width
         )
     }
 }
 
+/// Interface for single pin of an AXI GPIO channel.
+///
+/// Implements [`embedded_hal::digital`] traits.
+/// Reading an output pin or writing an input pin returns an error.
+pub struct Pin<'a, B = Mmio> {
+    /// Channel which has this pin.
+    channel: &'a Channel<B>,
+
+    /// Pin index.
+    index: u32,
+}
+
+impl<B> Pin<'_, B> {
+    /// Pin index.
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+}
+
+impl embedded_hal::digital::Error for Error {
+    fn kind(&self) -> embedded_hal::digital::ErrorKind {
+        embedded_hal::digital::ErrorKind::Other
+    }
+}
+
+impl<B> embedded_hal::digital::ErrorType for Pin<'_, B> {
+    type Error = Error;
+}
+
+impl<B: Bus> embedded_hal::digital::InputPin for Pin<'_, B> {
+    fn is_high(&mut self) -> Result<bool, Self::Error> {
+        self.channel.read_pin(self.index)
+    }
+
+    fn is_low(&mut self) -> Result<bool, Self::Error> {
+        self.channel.read_pin(self.index).map(|value| !value)
+    }
+}
+
+impl<B: Bus> embedded_hal::digital::OutputPin for Pin<'_, B> {
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        self.channel.write_pin(self.index, false)
+    }
+
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        self.channel.write_pin(self.index, true)
+    }
+}
+
+impl<B: Bus> embedded_hal::digital::StatefulOutputPin for Pin<'_, B> {
+    fn is_set_high(&mut self) -> Result<bool, Self::Error> {
+        match self.channel.pin_direction(self.index)? {
+            PinDirection::Output => Ok(self
+                .channel
+                .registers
+                .data(self.channel.channel.as_u32())
+                .read()
+                .is_set(data::pin(self.index))),
+            PinDirection::Input => Err(Error::PinIsInput(self.index)),
+        }
+    }
+
+    fn is_set_low(&mut self) -> Result<bool, Self::Error> {
+        self.is_set_high().map(|value| !value)
+    }
+}
+
 #[derive(Clone, Copy, PartialEq)]
 pub enum ChannelIndex {
     Channel1,
@@ -199,161 +324,174 @@ impl core::fmt::Display for ChannelIndex {
     }
 }
 
-pub struct InterruptMechanism {
-    address_global_interrupt_enable: *mut u32,
-    address_ip_interrupt_enable: *mut u32,
-    address_ip_interrupt_status: *mut u32,
+pub struct InterruptMechanism<B = Mmio> {
+    registers: AxiGpioRegisters<B>,
     channels: Channels,
 }
 
 impl InterruptMechanism {
-    pub fn new(
-        address_global_interrupt_enable: *mut u32,
-        address_ip_interrupt_enable: *mut u32,
-        address_ip_interrupt_status: *mut u32,
-        channels: Channels,
-    ) -> Self {
+    /// Create interface for interrupt mechanism of AXI GPIO block at given base address.
+    ///
+    /// # Safety
+    ///
+    /// Address must be the base address of a mapped AXI GPIO block in programmable logic,
+    /// which is not accessed through another interface.
+    pub unsafe fn new(address_base: *mut u32, channels: Channels) -> Self {
+        // Safety:
+        // Programmable logic is not owned by `Peripherals`, caller guarantees exclusive access.
+        Self::with_bus(address_base, channels, unsafe { Mmio::steal() })
+    }
+}
+
+impl<B> InterruptMechanism<B> {
+    /// Create interface for interrupt mechanism using given register access.
+    pub fn with_bus(address_base: *mut u32, channels: Channels, bus: B) -> Self {
         Self {
-            address_global_interrupt_enable,
-            address_ip_interrupt_enable,
-            address_ip_interrupt_status,
+            registers: AxiGpioRegisters::new(address_base as u32, bus),
             channels,
         }
     }
+}
 
+impl<B: Bus> InterruptMechanism<B> {
     /// True if interrupts are enabled.
     pub fn is_interrupts_enabled(&self) -> bool {
-        read_address_bit(self.address_global_interrupt_enable, 31)
+        self.registers
+            .global_interrupt_enable()
+            .read()
+            .is_set(global_interrupt_enable::GIE)
     }
 
     /// Enable or disable interrupts.
     pub fn toggle_interrupts(&self, enable: bool) {
-        let action = if enable {
-            set_address_bit
-        } else {
-            clear_address_bit
-        };
-        action(self.address_global_interrupt_enable, 31);
+        self.registers
+            .global_interrupt_enable()
+            .modify(|r| r.set(global_interrupt_enable::GIE, enable));
     }
 
     /// Check that the device is configured with dual channels if channel 2 is used.
-    fn check_channel_configuration(&self, channel: ChannelIndex) -> Result<(), ()> {
-        if channel == ChannelIndex::Channel2 {
-            if self.channels != Channels::Dual {
-                return Err(());
-            }
+    fn check_channel_configuration(&self, channel: ChannelIndex) -> Result<(), Error> {
+        if channel == ChannelIndex::Channel2 && self.channels != Channels::Dual {
+            return Err(Error::SingleChannel);
         }
         Ok(())
     }
 
     /// True if channel interrupts are enabled.
-    pub fn is_channel_interrupts_enabled(&self, channel: ChannelIndex) -> Result<bool, ()> {
-        if let Err(_) = self.check_channel_configuration(channel) {
-            Err(())
-        } else {
-            let index = channel.as_u32();
-            Ok(read_address_bit(self.address_ip_interrupt_enable, index))
-        }
+    pub fn is_channel_interrupts_enabled(&self, channel: ChannelIndex) -> Result<bool, Error> {
+        self.check_channel_configuration(channel)?;
+        let field = ip_interrupt_enable::channel(channel.as_u32());
+        Ok(self.registers.ip_interrupt_enable().read().is_set(field))
     }
 
     /// Enable or disable channel interrupts.
-    pub fn toggle_channel_interrupts(&self, channel: ChannelIndex, enable: bool) -> Result<(), ()> {
-        if let Err(_) = self.check_channel_configuration(channel) {
-            Err(())
-        } else {
-            let index = channel.as_u32();
-            let action = if enable {
-                set_address_bit
-            } else {
-                clear_address_bit
-            };
-            action(self.address_ip_interrupt_enable, index);
-            Ok(())
-        }
+    pub fn toggle_channel_interrupts(
+        &self,
+        channel: ChannelIndex,
+        enable: bool,
+    ) -> Result<(), Error> {
+        self.check_channel_configuration(channel)?;
+        let field = ip_interrupt_enable::channel(channel.as_u32());
+        self.registers
+            .ip_interrupt_enable()
+            .modify(|r| r.set(field, enable));
+        Ok(())
     }
 
     /// True if channel interrupt is signaled.
-    pub fn read_channel_interrupt_status(&self, channel: ChannelIndex) -> Result<bool, ()> {
-        if let Err(_) = self.check_channel_configuration(channel) {
-            Err(())
-        } else {
-            let index = channel.as_u32();
-            let status = read_address_bit(self.address_ip_interrupt_status, index);
-            Ok(status)
-        }
-    }
-
-    pub fn clear_channel_interrupt(&self, channel: ChannelIndex) -> Result<(), ()> {
-        if let Err(_) = self.check_channel_configuration(channel) {
-            Err(())
-        } else {
-            let index = channel.as_u32();
-            set_address_bit(self.address_ip_interrupt_status, index);
-            Ok(())
-        }
+    pub fn read_channel_interrupt_status(&self, channel: ChannelIndex) -> Result<bool, Error> {
+        self.check_channel_configuration(channel)?;
+        let field = ip_interrupt_status::channel(channel.as_u32());
+        let status = self.registers.ip_interrupt_status().read().is_set(field);
+        Ok(status)
+    }
+
+    pub fn clear_channel_interrupt(&self, channel: ChannelIndex) -> Result<(), Error> {
+        self.check_channel_configuration(channel)?;
+        // Status bits toggle on write, so write only the cleared bit.
+        let field = ip_interrupt_status::channel(channel.as_u32());
+        self.registers
+            .ip_interrupt_status()
+            .write_with_zero(|r| r.set(field, true));
+        Ok(())
     }
 
     pub fn reset(&self) {
-        write_to_address(self.address_global_interrupt_enable, 0);
-        write_to_address(self.address_ip_interrupt_enable, 0);
-        write_to_address(self.address_ip_interrupt_status, 0xFFFF_FFFF);
+        self.registers.global_interrupt_enable().reset();
+        self.registers.ip_interrupt_enable().reset();
+        self.registers.ip_interrupt_status().write_bits(0xFFFF_FFFF);
     }
 }
 
-impl core::fmt::Display for InterruptMechanism {
+impl<B: Bus> core::fmt::Display for InterruptMechanism<B> {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        let value_gie = unsafe { core::ptr::read_volatile(self.address_global_interrupt_enable) };
-        let value_iie = unsafe { core::ptr::read_volatile(self.address_ip_interrupt_enable) };
-        let value_iis = unsafe { core::ptr::read_volatile(self.address_ip_interrupt_status) };
+        let gie = self.registers.global_interrupt_enable();
+        let iie = self.registers.ip_interrupt_enable();
+        let iis = self.registers.ip_interrupt_status();
 
         write!(
             f,
             "gie @ 0x{:X}=0b{:b}, iie @ 0x{:X}=0b{:b}, iis @ 0x{:X}=0b{:b}, channels={}",
-            self.address_global_interrupt_enable as u32,
-            value_gie,
-            self.address_ip_interrupt_enable as u32,
-            value_iie,
-            self.address_ip_interrupt_status as u32,
-            value_iis,
+            gie.address() as u32,
+            gie.read().bits(),
+            iie.address() as u32,
+            iie.read().bits(),
+            iis.address() as u32,
+            iis.read().bits(),
             self.channels,
         )
     }
 }
 
-fn solve_address(address: *mut u32, offset: u32) -> *mut u32 {
-    (address as u32 + offset) as *mut u32
-}
-
-pub struct AxiGpio {
+pub struct AxiGpio<B = Mmio> {
     address_base: *mut u32,
-    channel_1: Channel,
-    channel_2: Option<Channel>,
-    interrupt_mechanism: Option<InterruptMechanism>,
+    channel_1: Channel<B>,
+    channel_2: Option<Channel<B>>,
+    interrupt_mechanism: Option<InterruptMechanism<B>>,
 }
 
 impl AxiGpio {
-    pub fn new(address_base: *mut u32, channels: Channels, interrupts: bool, width: u32) -> Self {
-        let address_data_1 = solve_address(address_base, 0x000);
-        let address_control_1 = solve_address(address_base, 0x004);
-        let channel_1 = Channel::new(address_data_1, address_control_1, width);
+    /// Create interface for AXI GPIO block at given base address.
+    ///
+    /// # Safety
+    ///
+    /// Address must be the base address of a mapped AXI GPIO block in programmable logic,
+    /// which is not accessed through another interface.
+    pub unsafe fn new(
+        address_base: *mut u32,
+        channels: Channels,
+        interrupts: bool,
+        width: u32,
+    ) -> Self {
+        // Safety:
+        // Programmable logic is not owned by `Peripherals`, caller guarantees exclusive access.
+        Self::with_bus(address_base, channels, interrupts, width, unsafe {
+            Mmio::steal()
+        })
+    }
+}
+
+impl<B: Copy> AxiGpio<B> {
+    /// Create interface for AXI GPIO using given register access.
+    pub fn with_bus(
+        address_base: *mut u32,
+        channels: Channels,
+        interrupts: bool,
+        width: u32,
+        bus: B,
+    ) -> Self {
+        let channel_1 = Channel::with_bus(address_base, ChannelIndex::Channel1, width, bus);
         let channel_2 = match channels {
             Channels::Single => None,
-            Channels::Dual => {
-                let address_data_2 = solve_address(address_base, 0x008);
-                let address_control_2 = solve_address(address_base, 0x00C);
-                Some(Channel::new(address_data_2, address_control_2, width))
-            }
+            Channels::Dual => Some(Channel::with_bus(
+                address_base,
+                ChannelIndex::Channel2,
+                width,
+                bus,
+            )),
         };
         let interrupt_mechanism = if interrupts {
-            let address_global_interrupt_enable = solve_address(address_base, 0x11C);
-            let address_ip_interrupt_enable = solve_address(address_base, 0x128);
-            let address_ip_interrupt_status = solve_address(address_base, 0x120);
-            Some(InterruptMechanism::new(
-                address_global_interrupt_enable,
-                address_ip_interrupt_enable,
-                address_ip_interrupt_status,
-                channels,
-            ))
+            Some(InterruptMechanism::with_bus(address_base, channels, bus))
         } else {
             None
         };
@@ -364,20 +502,22 @@ impl AxiGpio {
             interrupt_mechanism,
         }
     }
+}
 
+impl<B: Bus> AxiGpio<B> {
     pub fn address_base(&self) -> *mut u32 {
         self.address_base
     }
 
-    pub fn channel_1(&self) -> &Channel {
+    pub fn channel_1(&self) -> &Channel<B> {
         &self.channel_1
     }
 
-    pub fn channel_2(&self) -> &Option<Channel> {
+    pub fn channel_2(&self) -> &Option<Channel<B>> {
         &self.channel_2
     }
 
-    pub fn interrupt_mechanism(&self) -> &Option<InterruptMechanism> {
+    pub fn interrupt_mechanism(&self) -> &Option<InterruptMechanism<B>> {
         &self.interrupt_mechanism
     }
 
@@ -392,7 +532,97 @@ impl AxiGpio {
     }
 }
 
-impl core::fmt::Display for AxiGpio {
+/// Saved AXI GPIO channel configuration.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ChannelState {
+    data: u32,
+    tri: u32,
+}
+
+impl<B: Bus> Snapshot for Channel<B> {
+    type State = ChannelState;
+
+    fn save(&self) -> ChannelState {
+        let index = self.channel.as_u32();
+        ChannelState {
+            data: self.registers.data(index).read().bits(),
+            tri: self.registers.tri(index).read().bits(),
+        }
+    }
+
+    /// Restore AXI GPIO channel configuration.
+    ///
+    /// Output values are written before directions, so pins do not glitch.
+    fn restore(&self, state: &ChannelState) {
+        let index = self.channel.as_u32();
+        self.registers.data(index).write_bits(state.data);
+        self.registers.tri(index).write_bits(state.tri);
+    }
+}
+
+/// Saved AXI GPIO interrupt mechanism configuration.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct InterruptMechanismState {
+    global_interrupt_enable: u32,
+    ip_interrupt_enable: u32,
+}
+
+impl<B: Bus> Snapshot for InterruptMechanism<B> {
+    type State = InterruptMechanismState;
+
+    fn save(&self) -> InterruptMechanismState {
+        InterruptMechanismState {
+            global_interrupt_enable: self.registers.global_interrupt_enable().read().bits(),
+            ip_interrupt_enable: self.registers.ip_interrupt_enable().read().bits(),
+        }
+    }
+
+    fn restore(&self, state: &InterruptMechanismState) {
+        self.registers
+            .ip_interrupt_enable()
+            .write_bits(state.ip_interrupt_enable);
+        self.registers
+            .global_interrupt_enable()
+            .write_bits(state.global_interrupt_enable);
+    }
+}
+
+/// Saved AXI GPIO configuration.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AxiGpioState {
+    channel_1: ChannelState,
+    channel_2: Option<ChannelState>,
+    interrupt_mechanism: Option<InterruptMechanismState>,
+}
+
+impl<B: Bus> Snapshot for AxiGpio<B> {
+    type State = AxiGpioState;
+
+    fn save(&self) -> AxiGpioState {
+        AxiGpioState {
+            channel_1: self.channel_1.save(),
+            channel_2: self.channel_2.as_ref().map(Snapshot::save),
+            interrupt_mechanism: self.interrupt_mechanism.as_ref().map(Snapshot::save),
+        }
+    }
+
+    /// Restore AXI GPIO configuration.
+    ///
+    /// Parts which were not present when saved are left as is.
+    fn restore(&self, state: &AxiGpioState) {
+        self.channel_1.restore(&state.channel_1);
+        if let (Some(channel), Some(saved)) = (&self.channel_2, &state.channel_2) {
+            channel.restore(saved);
+        }
+        if let (Some(mechanism), Some(saved)) =
+            (&self.interrupt_mechanism, &state.interrupt_mechanism)
+        {
+            mechanism.restore(saved);
+        }
+    }
+}
+
+impl<B: Bus> core::fmt::Display for AxiGpio<B> {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match &self.channel_2 {
             Some(channel_2) => match &self.interrupt_mechanism {
@@ -437,3 +667,27 @@ impl core::fmt::Display for AxiGpio {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::bus::SimulatedBus;
+
+    #[test]
+    fn restore_writes_saved_configuration_back() {
+        let bus = SimulatedBus::<8, 16>::new();
+        let gpio = AxiGpio::with_bus(0x4120_0000 as *mut u32, Channels::Dual, true, 4, &bus);
+        let mechanism = gpio.interrupt_mechanism().as_ref().unwrap();
+        gpio.channel_1().write_pin(0, true).unwrap();
+        mechanism.toggle_interrupts(true);
+
+        let saved = gpio.save();
+        gpio.channel_1().write_pin(0, false).unwrap();
+        gpio.channel_1()
+            .set_pin_direction(1, PinDirection::Input)
+            .unwrap();
+        mechanism.toggle_interrupts(false);
+        gpio.restore(&saved);
+        assert_eq!(gpio.save(), saved);
+    }
+}
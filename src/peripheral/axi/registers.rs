@@ -0,0 +1,38 @@
+//! AXI GPIO registers.
+//!
+//! AXI GPIO is implemented in programmable logic, so base address depends on the design.
+
+crate::register_block! {
+    /// AXI GPIO registers.
+    pub AxiGpioRegisters {
+        /// Channel data registers.
+        0x000 [2; 0x008] => data: ReadWrite, reset = 0x0000_0000 {
+            /// Pin values.
+            pin[32]: 0, 1;
+        }
+
+        /// Channel tri-state control registers.
+        0x004 [2; 0x008] => tri: ReadWrite, reset = 0xFFFF_FFFF {
+            /// Pin is configured as input.
+            pin[32]: 0, 1;
+        }
+
+        /// Global interrupt enable register.
+        0x11C => global_interrupt_enable: ReadWrite, reset = 0x0000_0000 {
+            /// Enable interrupts.
+            GIE: 31, 1;
+        }
+
+        /// IP interrupt status register.
+        0x120 => ip_interrupt_status: ReadWrite, reset = 0x0000_0000 {
+            /// Channel interrupt is signaled, write high to clear.
+            channel[2]: 0, 1;
+        }
+
+        /// IP interrupt enable register.
+        0x128 => ip_interrupt_enable: ReadWrite, reset = 0x0000_0000 {
+            /// Enable channel interrupt.
+            channel[2]: 0, 1;
+        }
+    }
+}
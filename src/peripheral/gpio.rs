@@ -5,22 +5,99 @@
 //! - Bank 1 controls 22 MIO pins.
 //! - Bank 2 controls 32 EMIO pins.
 //! - Bank 3 controls 32 EMIO pins.
+//!
+//! Pins of a bank share its registers.
+//! Outputs are written through maskable output data registers, which touch only the written pin,
+//! and other per-pin configuration is modified with interrupts masked and a bank lock held,
+//! so pins of the same bank can be used on both cores and in interrupt handlers.
+//!
+//! # How to use?
+//!
+//! ```ignore
+//! let banks = peripherals.gpio.split();
+//! banks.mio0.set_direction(7, PinDirection::Output)?;
+//! banks.mio0.toggle_output(7, true)?;
+//! banks.mio0.write_output(7, true)?;
+//! ```
+//!
+//! Single pins implement [`embedded_hal::digital`] traits,
+//! so they can be given to drivers written against `embedded-hal`.
+//!
+//! ```ignore
+//! let mut led = banks.mio0.pin(7)?;
+//! led.set_high()?;
+//! ```
+//!
+//! Alternatively, take each pin as its own type.
+//! Pin's mode is part of its type,
+//! so writing to an input or using an invalid index does not compile.
+//!
+//! ```ignore
+//! let pins = peripherals.gpio.into_pins();
+//! let mut led = pins.mio7.into_output(false);
+//! led.set_high();
+//! let button = pins.mio50.into_input();
+//! let switch = pins.emio54.into_interrupt(
+//!     InterruptType::Edge,
+//!     InterruptPolarity::ActiveHighOrRisingEdge,
+//!     InterruptEdgeTriggeringMode::Single,
+//! );
+//! ```
 
-// TODO: no runtime panics, use results
 // TODO: separate mio and emio to substructs
 
-use crate::common::memman::clear_address_bit;
-use crate::common::memman::read_address_bit;
-use crate::common::memman::set_address_bit;
+pub mod registers;
+
+use crate::common::bus::Bus;
+use crate::common::bus::Mmio;
+use crate::common::snapshot::Snapshot;
+use crate::sync::mutex::Mutex;
+use core::convert::Infallible;
+use core::marker::PhantomData;
+use core::ops::Not;
 use core::ops::RangeInclusive;
 use core::ops::Rem;
+use registers::direction_mode;
+use registers::input_data;
+use registers::interrupt_any_edge_sensitive;
+use registers::interrupt_disable;
+use registers::interrupt_enable;
+use registers::interrupt_polarity;
+use registers::interrupt_status;
+use registers::interrupt_type;
+use registers::maskable_output_data_lsw;
+use registers::maskable_output_data_msw;
+use registers::output_data;
+use registers::output_enable;
+use registers::GpioRegisters;
+
+/// GPIO error.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// Index is not a MIO pin.
+    InvalidMioPin(u32),
 
-pub enum MioPin {
-    // TODO
+    /// Index is not an EMIO pin.
+    InvalidEmioPin(u32),
+
+    /// Pin is not in bank.
+    PinNotInBank {
+        /// MIO or EMIO index of pin.
+        pin: u32,
+
+        /// Bank index.
+        bank: u32,
+    },
 }
 
-pub enum EmioPin {
-    // TODO
+impl core::fmt::Display for Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::InvalidMioPin(index) => write!(f, "invalid MIO pin index: {index}"),
+            Self::InvalidEmioPin(index) => write!(f, "invalid EMIO pin index: {index}"),
+            Self::PinNotInBank { pin, bank } => write!(f, "pin {pin} is not in GPIO bank {bank}"),
+        }
+    }
 }
 
 /// GPIO pin direction.
@@ -99,325 +176,390 @@ impl InterruptEdgeTriggeringMode {
     }
 }
 
-// TODO: hide fields to pub(crate)
-/// Interface for a GPIO bank.
-#[derive(Clone, Copy)]
-pub struct Bank {
-    pub address_maskable_output_data_lsw: *mut u32,
-    pub address_maskable_output_data_msw: *mut u32,
-    pub address_output_data: *mut u32,
-    pub address_input_data: *mut u32,
-    pub address_direction_mode: *mut u32,
-    pub address_output_enable: *mut u32,
-    pub address_interrupt_mask_status: *mut u32,
-    pub address_interrupt_enable: *mut u32,
-    pub address_interrupt_disable: *mut u32,
-    pub address_interrupt_status: *mut u32,
-    pub address_interrupt_type: *mut u32,
-    pub address_interrupt_polarity: *mut u32,
-    pub address_interrupt_any_edge_sensitive: *mut u32,
-}
-
 /// Interface for a GPIO peripheral.
-pub struct Gpio {
+///
+/// Pin accessors return an error if the index is not a MIO or EMIO pin.
+pub struct Gpio<B = Mmio> {
+    registers: GpioRegisters<B>,
     pub mio_pin_range: RangeInclusive<u32>,
     pub emio_pin_range: RangeInclusive<u32>,
-    pub banks: [Bank; 4],
     pub bank_pin_ranges: [RangeInclusive<u32>; 4],
     pub mio_bank_indices: RangeInclusive<u32>,
     pub emio_bank_indices: RangeInclusive<u32>,
 }
 
-impl Gpio {
-    /// Get MIO bank by pin index.
+impl<B> Gpio<B> {
+    /// Create interface for GPIO peripheral using given register access.
+    #[must_use]
+    pub const fn with_bus(bus: B) -> Self {
+        Self {
+            registers: GpioRegisters::new(ADDRESS_GPIO_BASE, bus),
+            mio_pin_range: 0..=53,
+            emio_pin_range: 0..=63,
+            bank_pin_ranges: [0..=31, 32..=53, 0..=31, 32..=63],
+            mio_bank_indices: 0..=1,
+            emio_bank_indices: 2..=3,
+        }
+    }
+
+    /// GPIO registers.
+    #[inline]
+    #[must_use]
+    pub fn registers(&self) -> &GpioRegisters<B> {
+        &self.registers
+    }
+}
+
+impl<B: Bus> Gpio<B> {
+    /// Get MIO bank index by pin index.
     ///
-    /// # Panics
+    /// # Errors
     ///
-    /// Invalid index.
+    /// - Invalid index.
     #[inline]
-    #[must_use]
-    pub fn get_mio_bank_by_pin_index(&self, index: u32) -> &Bank {
+    pub fn get_mio_bank_by_pin_index(&self, index: u32) -> Result<u32, Error> {
         for bank_index in self.mio_bank_indices.clone() {
             let bank_pin_range = &self.bank_pin_ranges[bank_index as usize];
             if bank_pin_range.contains(&index) {
-                return &self.banks[bank_index as usize];
+                return Ok(bank_index);
             }
         }
-        panic!("Invalid MIO index: {}", index);
+        Err(Error::InvalidMioPin(index))
     }
 
-    /// Get EMIO bank by pin index.
+    /// Get EMIO bank index by pin index.
     ///
-    /// # Panics
+    /// # Errors
     ///
-    /// Invalid index.
+    /// - Invalid index.
     #[inline]
-    #[must_use]
-    pub fn get_emio_bank_by_pin_index(&self, index: u32) -> &Bank {
+    pub fn get_emio_bank_by_pin_index(&self, index: u32) -> Result<u32, Error> {
         for bank_index in self.emio_bank_indices.clone() {
             let bank_pin_range = &self.bank_pin_ranges[bank_index as usize];
             if bank_pin_range.contains(&index) {
-                return &self.banks[bank_index as usize];
+                return Ok(bank_index);
             }
         }
-        panic!("Invalid EMIO index: {}", index);
+        Err(Error::InvalidEmioPin(index))
     }
 
     /// Read MIO pin input.
     #[inline]
-    #[must_use]
-    pub fn read_mio_input(&self, index: u32) -> bool {
-        let bank = self.get_mio_bank_by_pin_index(index);
+    pub fn read_mio_input(&self, index: u32) -> Result<bool, Error> {
+        let bank = self.get_mio_bank_by_pin_index(index)?;
         let bit_index = index.rem(32);
-        read_address_bit(bank.address_input_data, bit_index)
+        Ok(self
+            .registers
+            .input_data(bank)
+            .read()
+            .is_set(input_data::pin(bit_index)))
     }
 
     /// Read EMIO pin input.
     #[inline]
-    #[must_use]
-    pub fn read_emio_input(&self, index: u32) -> bool {
-        let bank = self.get_emio_bank_by_pin_index(index);
+    pub fn read_emio_input(&self, index: u32) -> Result<bool, Error> {
+        let bank = self.get_emio_bank_by_pin_index(index)?;
         let bit_index = index.rem(32);
-        read_address_bit(bank.address_input_data, bit_index)
+        Ok(self
+            .registers
+            .input_data(bank)
+            .read()
+            .is_set(input_data::pin(bit_index)))
     }
 
     /// Write MIO pin output.
     #[inline]
-    pub fn write_mio_output(&self, index: u32, state: bool) {
-        let bank = self.get_mio_bank_by_pin_index(index);
+    pub fn write_mio_output(&self, index: u32, state: bool) -> Result<(), Error> {
+        let bank = self.get_mio_bank_by_pin_index(index)?;
         let bit_index = index.rem(32);
-        let action = if state {
-            set_address_bit
-        } else {
-            clear_address_bit
-        };
-        action(bank.address_output_data, bit_index);
+        write_output_masked(&self.registers, bank, bit_index, state);
+        Ok(())
     }
 
     /// Write EMIO pin output.
     #[inline]
-    pub fn write_emio_output(&self, index: u32, state: bool) {
-        let bank = self.get_emio_bank_by_pin_index(index);
+    pub fn write_emio_output(&self, index: u32, state: bool) -> Result<(), Error> {
+        let bank = self.get_emio_bank_by_pin_index(index)?;
         let bit_index = index.rem(32);
-        let action = if state {
-            set_address_bit
-        } else {
-            clear_address_bit
-        };
-        action(bank.address_output_data, bit_index);
+        write_output_masked(&self.registers, bank, bit_index, state);
+        Ok(())
     }
 
     /// Read MIO pin output.
     #[inline]
-    pub fn read_mio_output(&self, index: u32) -> bool {
-        let bank = self.get_mio_bank_by_pin_index(index);
+    pub fn read_mio_output(&self, index: u32) -> Result<bool, Error> {
+        let bank = self.get_mio_bank_by_pin_index(index)?;
         let bit_index = index.rem(32);
-        read_address_bit(bank.address_output_data, bit_index)
+        Ok(self
+            .registers
+            .output_data(bank)
+            .read()
+            .is_set(output_data::pin(bit_index)))
     }
 
     /// Read EMIO pin output.
     #[inline]
-    pub fn read_emio_output(&self, index: u32) -> bool {
-        let bank = self.get_emio_bank_by_pin_index(index);
+    pub fn read_emio_output(&self, index: u32) -> Result<bool, Error> {
+        let bank = self.get_emio_bank_by_pin_index(index)?;
         let bit_index = index.rem(32);
-        read_address_bit(bank.address_output_data, bit_index)
+        Ok(self
+            .registers
+            .output_data(bank)
+            .read()
+            .is_set(output_data::pin(bit_index)))
     }
 
     /// Enable MIO pin output.
     #[inline]
-    pub fn toggle_mio_output(&self, index: u32, enabled: bool) {
-        let bank = self.get_mio_bank_by_pin_index(index);
+    pub fn toggle_mio_output(&self, index: u32, enabled: bool) -> Result<(), Error> {
+        let bank = self.get_mio_bank_by_pin_index(index)?;
         let bit_index = index.rem(32);
-        let action = if enabled {
-            set_address_bit
-        } else {
-            clear_address_bit
-        };
-        action(bank.address_output_enable, bit_index);
+        modify_bank(bank, || {
+            self.registers
+                .output_enable(bank)
+                .modify(|r| r.set(output_enable::pin(bit_index), enabled));
+        });
+        Ok(())
     }
 
     /// Enable EMIO pin output.
     #[inline]
-    pub fn toggle_emio_output(&self, index: u32, enabled: bool) {
-        let bank = self.get_emio_bank_by_pin_index(index);
+    pub fn toggle_emio_output(&self, index: u32, enabled: bool) -> Result<(), Error> {
+        let bank = self.get_emio_bank_by_pin_index(index)?;
         let bit_index = index.rem(32);
-        let action = if enabled {
-            set_address_bit
-        } else {
-            clear_address_bit
-        };
-        action(bank.address_output_enable, bit_index);
+        modify_bank(bank, || {
+            self.registers
+                .output_enable(bank)
+                .modify(|r| r.set(output_enable::pin(bit_index), enabled));
+        });
+        Ok(())
     }
 
     /// Set MIO pin direction.
     #[inline]
-    pub fn set_mio_direction(&self, index: u32, direction: PinDirection) {
-        let bank = self.get_mio_bank_by_pin_index(index);
+    pub fn set_mio_direction(&self, index: u32, direction: PinDirection) -> Result<(), Error> {
+        let bank = self.get_mio_bank_by_pin_index(index)?;
         let bit_index = index.rem(32);
-        let action = match direction {
-            PinDirection::Input => clear_address_bit,
-            PinDirection::Output => set_address_bit,
+        let output = match direction {
+            PinDirection::Input => false,
+            PinDirection::Output => true,
         };
-        action(bank.address_direction_mode, bit_index);
+        modify_bank(bank, || {
+            self.registers
+                .direction_mode(bank)
+                .modify(|r| r.set(direction_mode::pin(bit_index), output));
+        });
+        Ok(())
     }
 
     /// Set EMIO pin direction.
     #[inline]
-    pub fn set_emio_direction(&self, index: u32, direction: PinDirection) {
-        let bank = self.get_emio_bank_by_pin_index(index);
+    pub fn set_emio_direction(&self, index: u32, direction: PinDirection) -> Result<(), Error> {
+        let bank = self.get_emio_bank_by_pin_index(index)?;
         let bit_index = index.rem(32);
-        let action = match direction {
-            PinDirection::Input => clear_address_bit,
-            PinDirection::Output => set_address_bit,
+        let output = match direction {
+            PinDirection::Input => false,
+            PinDirection::Output => true,
         };
-        action(bank.address_direction_mode, bit_index);
+        modify_bank(bank, || {
+            self.registers
+                .direction_mode(bank)
+                .modify(|r| r.set(direction_mode::pin(bit_index), output));
+        });
+        Ok(())
     }
 
     /// Enable or disable MIO pin interrupts.
     #[inline]
-    pub fn toggle_mio_interrupt(&self, index: u32, enabled: bool) {
-        let bank = self.get_mio_bank_by_pin_index(index);
+    pub fn toggle_mio_interrupt(&self, index: u32, enabled: bool) -> Result<(), Error> {
+        let bank = self.get_mio_bank_by_pin_index(index)?;
         let bit_index = index.rem(32);
-        let address = if enabled {
-            bank.address_interrupt_enable
+        if enabled {
+            self.registers
+                .interrupt_enable(bank)
+                .write_with_zero(|r| r.set(interrupt_enable::pin(bit_index), true));
         } else {
-            bank.address_interrupt_disable
-        };
-        set_address_bit(address, bit_index);
+            self.registers
+                .interrupt_disable(bank)
+                .write_with_zero(|r| r.set(interrupt_disable::pin(bit_index), true));
+        }
+        Ok(())
     }
 
     /// Enable or disable EMIO pin interrupts.
     #[inline]
-    pub fn toggle_emio_interrupt(&self, index: u32, enabled: bool) {
-        let bank = self.get_emio_bank_by_pin_index(index);
+    pub fn toggle_emio_interrupt(&self, index: u32, enabled: bool) -> Result<(), Error> {
+        let bank = self.get_emio_bank_by_pin_index(index)?;
         let bit_index = index.rem(32);
-        let address = if enabled {
-            bank.address_interrupt_enable
+        if enabled {
+            self.registers
+                .interrupt_enable(bank)
+                .write_with_zero(|r| r.set(interrupt_enable::pin(bit_index), true));
         } else {
-            bank.address_interrupt_disable
-        };
-        set_address_bit(address, bit_index);
+            self.registers
+                .interrupt_disable(bank)
+                .write_with_zero(|r| r.set(interrupt_disable::pin(bit_index), true));
+        }
+        Ok(())
     }
 
     /// Read MIO pin interrupt status.
     #[inline]
-    #[must_use]
-    pub fn read_mio_interrupt_status(&self, index: u32) -> bool {
-        let bank = self.get_mio_bank_by_pin_index(index);
+    pub fn read_mio_interrupt_status(&self, index: u32) -> Result<bool, Error> {
+        let bank = self.get_mio_bank_by_pin_index(index)?;
         let bit_index = index.rem(32);
-        read_address_bit(bank.address_interrupt_status, bit_index)
+        Ok(self
+            .registers
+            .interrupt_status(bank)
+            .read()
+            .is_set(interrupt_status::pin(bit_index)))
     }
 
     /// Clear MIO pin interrupt.
     #[inline]
-    pub fn clear_mio_interrupt(&self, index: u32) {
-        let bank = self.get_mio_bank_by_pin_index(index);
+    pub fn clear_mio_interrupt(&self, index: u32) -> Result<(), Error> {
+        let bank = self.get_mio_bank_by_pin_index(index)?;
         let bit_index = index.rem(32);
-        set_address_bit(bank.address_interrupt_status, bit_index);
+        self.registers
+            .interrupt_status(bank)
+            .write_with_zero(|r| r.set(interrupt_status::pin(bit_index), true));
+        Ok(())
     }
 
     /// Read EMIO pin interrupt status.
     #[inline]
-    #[must_use]
-    pub fn read_emio_interrupt_status(&self, index: u32) -> bool {
-        let bank = self.get_emio_bank_by_pin_index(index);
+    pub fn read_emio_interrupt_status(&self, index: u32) -> Result<bool, Error> {
+        let bank = self.get_emio_bank_by_pin_index(index)?;
         let bit_index = index.rem(32);
-        read_address_bit(bank.address_interrupt_status, bit_index)
+        Ok(self
+            .registers
+            .interrupt_status(bank)
+            .read()
+            .is_set(interrupt_status::pin(bit_index)))
     }
 
     /// Clear EMIO pin interrupt.
     #[inline]
-    pub fn clear_emio_interrupt(&self, index: u32) {
-        let bank = self.get_emio_bank_by_pin_index(index);
+    pub fn clear_emio_interrupt(&self, index: u32) -> Result<(), Error> {
+        let bank = self.get_emio_bank_by_pin_index(index)?;
         let bit_index = index.rem(32);
-        set_address_bit(bank.address_interrupt_status, bit_index);
+        self.registers
+            .interrupt_status(bank)
+            .write_with_zero(|r| r.set(interrupt_status::pin(bit_index), true));
+        Ok(())
     }
 
     /// Set MIO pin interrupt type.
     #[inline]
-    pub fn set_mio_interrupt_type(&self, index: u32, value: InterruptType) {
-        let bank = self.get_mio_bank_by_pin_index(index);
+    pub fn set_mio_interrupt_type(&self, index: u32, value: InterruptType) -> Result<(), Error> {
+        let bank = self.get_mio_bank_by_pin_index(index)?;
         let bit_index = index.rem(32);
-        let action = if value.as_bool() {
-            set_address_bit
-        } else {
-            clear_address_bit
-        };
-        action(bank.address_interrupt_type, bit_index);
+        modify_bank(bank, || {
+            self.registers
+                .interrupt_type(bank)
+                .modify(|r| r.set(interrupt_type::pin(bit_index), value.as_bool()));
+        });
+        Ok(())
     }
 
     /// Set EMIO pin interrupt type.
     #[inline]
-    pub fn set_emio_interrupt_type(&self, index: u32, value: InterruptType) {
-        let bank = self.get_emio_bank_by_pin_index(index);
+    pub fn set_emio_interrupt_type(&self, index: u32, value: InterruptType) -> Result<(), Error> {
+        let bank = self.get_emio_bank_by_pin_index(index)?;
         let bit_index = index.rem(32);
-        let action = if value.as_bool() {
-            set_address_bit
-        } else {
-            clear_address_bit
-        };
-        action(bank.address_interrupt_type, bit_index);
+        modify_bank(bank, || {
+            self.registers
+                .interrupt_type(bank)
+                .modify(|r| r.set(interrupt_type::pin(bit_index), value.as_bool()));
+        });
+        Ok(())
     }
 
     /// Set MIO pin interrupt polarity.
     #[inline]
-    pub fn set_mio_interrupt_polarity(&self, index: u32, value: InterruptPolarity) {
-        let bank = self.get_mio_bank_by_pin_index(index);
+    pub fn set_mio_interrupt_polarity(
+        &self,
+        index: u32,
+        value: InterruptPolarity,
+    ) -> Result<(), Error> {
+        let bank = self.get_mio_bank_by_pin_index(index)?;
         let bit_index = index.rem(32);
-        let action = if value.as_bool() {
-            set_address_bit
-        } else {
-            clear_address_bit
-        };
-        action(bank.address_interrupt_polarity, bit_index);
+        modify_bank(bank, || {
+            self.registers
+                .interrupt_polarity(bank)
+                .modify(|r| r.set(interrupt_polarity::pin(bit_index), value.as_bool()));
+        });
+        Ok(())
     }
 
     /// Set EMIO pin interrupt polarity.
     #[inline]
-    pub fn set_emio_interrupt_polarity(&self, index: u32, value: InterruptPolarity) {
-        let bank = self.get_emio_bank_by_pin_index(index);
+    pub fn set_emio_interrupt_polarity(
+        &self,
+        index: u32,
+        value: InterruptPolarity,
+    ) -> Result<(), Error> {
+        let bank = self.get_emio_bank_by_pin_index(index)?;
         let bit_index = index.rem(32);
-        let action = if value.as_bool() {
-            set_address_bit
-        } else {
-            clear_address_bit
-        };
-        action(bank.address_interrupt_polarity, bit_index);
+        modify_bank(bank, || {
+            self.registers
+                .interrupt_polarity(bank)
+                .modify(|r| r.set(interrupt_polarity::pin(bit_index), value.as_bool()));
+        });
+        Ok(())
     }
 
     /// Set MIO pin edge triggering mode.
     #[inline]
-    pub fn set_mio_edge_triggering_mode(&self, index: u32, value: InterruptEdgeTriggeringMode) {
-        let bank = self.get_mio_bank_by_pin_index(index);
+    pub fn set_mio_edge_triggering_mode(
+        &self,
+        index: u32,
+        value: InterruptEdgeTriggeringMode,
+    ) -> Result<(), Error> {
+        let bank = self.get_mio_bank_by_pin_index(index)?;
         let bit_index = index.rem(32);
-        let action = if value.as_bool() {
-            set_address_bit
-        } else {
-            clear_address_bit
-        };
-        action(bank.address_interrupt_any_edge_sensitive, bit_index);
+        modify_bank(bank, || {
+            self.registers
+                .interrupt_any_edge_sensitive(bank)
+                .modify(|r| {
+                    r.set(
+                        interrupt_any_edge_sensitive::pin(bit_index),
+                        value.as_bool(),
+                    )
+                });
+        });
+        Ok(())
     }
 
     /// Set EMIO pin edge triggering mode.
     #[inline]
-    pub fn set_emio_edge_triggering_mode(&self, index: u32, value: InterruptEdgeTriggeringMode) {
-        let bank = self.get_emio_bank_by_pin_index(index);
+    pub fn set_emio_edge_triggering_mode(
+        &self,
+        index: u32,
+        value: InterruptEdgeTriggeringMode,
+    ) -> Result<(), Error> {
+        let bank = self.get_emio_bank_by_pin_index(index)?;
         let bit_index = index.rem(32);
-        let action = if value.as_bool() {
-            set_address_bit
-        } else {
-            clear_address_bit
-        };
-        action(bank.address_interrupt_any_edge_sensitive, bit_index);
+        modify_bank(bank, || {
+            self.registers
+                .interrupt_any_edge_sensitive(bank)
+                .modify(|r| {
+                    r.set(
+                        interrupt_any_edge_sensitive::pin(bit_index),
+                        value.as_bool(),
+                    )
+                });
+        });
+        Ok(())
     }
 }
 
-impl core::fmt::Display for Gpio {
+impl<B: Bus> core::fmt::Display for Gpio<B> {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        let mio0_directions = unsafe { self.banks[0].address_direction_mode.read_volatile() };
-        let mio1_directions = unsafe { self.banks[1].address_direction_mode.read_volatile() };
-        let emio0_directions = unsafe { self.banks[2].address_direction_mode.read_volatile() };
-        let emio1_directions = unsafe { self.banks[3].address_direction_mode.read_volatile() };
+        let mio0_directions = self.registers.direction_mode(0).read().bits();
+        let mio1_directions = self.registers.direction_mode(1).read().bits();
+        let emio0_directions = self.registers.direction_mode(2).read().bits();
+        let emio1_directions = self.registers.direction_mode(3).read().bits();
         // TODO: maybe provide other info too
         write!(
             f,
@@ -427,101 +569,957 @@ impl core::fmt::Display for Gpio {
     }
 }
 
+impl<B: Copy> Gpio<B> {
+    /// Split into independent banks.
+    #[inline]
+    #[must_use]
+    pub fn split(self) -> GpioBanks<B> {
+        let bus = *self.registers.bus();
+        let [mio0, mio1, emio0, emio1] = self.bank_pin_ranges;
+        GpioBanks {
+            mio0: GpioBank::with_bus(0, mio0, bus),
+            mio1: GpioBank::with_bus(1, mio1, bus),
+            emio0: GpioBank::with_bus(2, emio0, bus),
+            emio1: GpioBank::with_bus(3, emio1, bus),
+        }
+    }
+
+    /// Split into independent pins.
+    #[inline]
+    #[must_use]
+    pub fn into_pins(self) -> Pins<B> {
+        Pins::with_bus(*self.registers.bus())
+    }
+}
+
+/// Banks of a GPIO peripheral.
+pub struct GpioBanks<B = Mmio> {
+    /// MIO pins 0..=31.
+    pub mio0: GpioBank<B>,
+
+    /// MIO pins 32..=53.
+    pub mio1: GpioBank<B>,
+
+    /// EMIO pins 0..=31.
+    pub emio0: GpioBank<B>,
+
+    /// EMIO pins 32..=63.
+    pub emio1: GpioBank<B>,
+}
+
+/// Interface for a bank of GPIO peripheral.
+///
+/// Banks use separate registers, so they can be used independently.
+/// Pins are addressed with their MIO or EMIO index.
+/// Pin accessors return an error if the pin is not in this bank.
+pub struct GpioBank<B = Mmio> {
+    /// GPIO registers.
+    registers: GpioRegisters<B>,
+
+    /// Bank index.
+    index: u32,
+
+    /// MIO or EMIO indices of pins in this bank.
+    pins: RangeInclusive<u32>,
+}
+
+impl<B> GpioBank<B> {
+    /// Create interface for bank of GPIO peripheral using given register access.
+    #[must_use]
+    const fn with_bus(index: u32, pins: RangeInclusive<u32>, bus: B) -> Self {
+        Self {
+            registers: GpioRegisters::new(ADDRESS_GPIO_BASE, bus),
+            index,
+            pins,
+        }
+    }
+
+    /// Bank index.
+    #[inline]
+    #[must_use]
+    pub const fn index(&self) -> u32 {
+        self.index
+    }
+
+    /// MIO or EMIO indices of pins in this bank.
+    #[inline]
+    #[must_use]
+    pub fn pins(&self) -> RangeInclusive<u32> {
+        self.pins.clone()
+    }
+
+    /// GPIO registers.
+    #[inline]
+    #[must_use]
+    pub fn registers(&self) -> &GpioRegisters<B> {
+        &self.registers
+    }
+
+    /// Solve pin's bit index in bank's registers.
+    ///
+    /// # Errors
+    ///
+    /// - Pin is not in this bank.
+    fn bit_index(&self, index: u32) -> Result<u32, Error> {
+        if self.pins.contains(&index) {
+            Ok(index - self.pins.start())
+        } else {
+            Err(Error::PinNotInBank {
+                pin: index,
+                bank: self.index,
+            })
+        }
+    }
+}
+
+impl<B: Bus> GpioBank<B> {
+    /// Get interface for single pin of this bank.
+    ///
+    /// # Errors
+    ///
+    /// - Pin is not in this bank.
+    #[inline]
+    pub fn pin(&self, index: u32) -> Result<Pin<'_, B>, Error> {
+        self.bit_index(index)?;
+        Ok(Pin { bank: self, index })
+    }
+
+    /// Read pin input.
+    #[inline]
+    pub fn read_input(&self, index: u32) -> Result<bool, Error> {
+        let bit_index = self.bit_index(index)?;
+        Ok(self
+            .registers
+            .input_data(self.index)
+            .read()
+            .is_set(input_data::pin(bit_index)))
+    }
+
+    /// Write pin output.
+    #[inline]
+    pub fn write_output(&self, index: u32, state: bool) -> Result<(), Error> {
+        let bit_index = self.bit_index(index)?;
+        write_output_masked(&self.registers, self.index, bit_index, state);
+        Ok(())
+    }
+
+    /// Read pin output.
+    #[inline]
+    pub fn read_output(&self, index: u32) -> Result<bool, Error> {
+        let bit_index = self.bit_index(index)?;
+        Ok(self
+            .registers
+            .output_data(self.index)
+            .read()
+            .is_set(output_data::pin(bit_index)))
+    }
+
+    /// Enable pin output.
+    #[inline]
+    pub fn toggle_output(&self, index: u32, enabled: bool) -> Result<(), Error> {
+        let bit_index = self.bit_index(index)?;
+        modify_bank(self.index, || {
+            self.registers
+                .output_enable(self.index)
+                .modify(|r| r.set(output_enable::pin(bit_index), enabled));
+        });
+        Ok(())
+    }
+
+    /// Set pin direction.
+    #[inline]
+    pub fn set_direction(&self, index: u32, direction: PinDirection) -> Result<(), Error> {
+        let bit_index = self.bit_index(index)?;
+        let output = match direction {
+            PinDirection::Input => false,
+            PinDirection::Output => true,
+        };
+        modify_bank(self.index, || {
+            self.registers
+                .direction_mode(self.index)
+                .modify(|r| r.set(direction_mode::pin(bit_index), output));
+        });
+        Ok(())
+    }
+
+    /// Enable or disable pin interrupts.
+    #[inline]
+    pub fn toggle_interrupt(&self, index: u32, enabled: bool) -> Result<(), Error> {
+        let bit_index = self.bit_index(index)?;
+        if enabled {
+            self.registers
+                .interrupt_enable(self.index)
+                .write_with_zero(|r| r.set(interrupt_enable::pin(bit_index), true));
+        } else {
+            self.registers
+                .interrupt_disable(self.index)
+                .write_with_zero(|r| r.set(interrupt_disable::pin(bit_index), true));
+        }
+        Ok(())
+    }
+
+    /// Read pin interrupt status.
+    #[inline]
+    pub fn read_interrupt_status(&self, index: u32) -> Result<bool, Error> {
+        let bit_index = self.bit_index(index)?;
+        Ok(self
+            .registers
+            .interrupt_status(self.index)
+            .read()
+            .is_set(interrupt_status::pin(bit_index)))
+    }
+
+    /// Clear pin interrupt.
+    #[inline]
+    pub fn clear_interrupt(&self, index: u32) -> Result<(), Error> {
+        let bit_index = self.bit_index(index)?;
+        self.registers
+            .interrupt_status(self.index)
+            .write_with_zero(|r| r.set(interrupt_status::pin(bit_index), true));
+        Ok(())
+    }
+
+    /// Set pin interrupt type.
+    #[inline]
+    pub fn set_interrupt_type(&self, index: u32, value: InterruptType) -> Result<(), Error> {
+        let bit_index = self.bit_index(index)?;
+        modify_bank(self.index, || {
+            self.registers
+                .interrupt_type(self.index)
+                .modify(|r| r.set(interrupt_type::pin(bit_index), value.as_bool()));
+        });
+        Ok(())
+    }
+
+    /// Set pin interrupt polarity.
+    #[inline]
+    pub fn set_interrupt_polarity(
+        &self,
+        index: u32,
+        value: InterruptPolarity,
+    ) -> Result<(), Error> {
+        let bit_index = self.bit_index(index)?;
+        modify_bank(self.index, || {
+            self.registers
+                .interrupt_polarity(self.index)
+                .modify(|r| r.set(interrupt_polarity::pin(bit_index), value.as_bool()));
+        });
+        Ok(())
+    }
+
+    /// Set pin edge triggering mode.
+    #[inline]
+    pub fn set_edge_triggering_mode(
+        &self,
+        index: u32,
+        value: InterruptEdgeTriggeringMode,
+    ) -> Result<(), Error> {
+        let bit_index = self.bit_index(index)?;
+        modify_bank(self.index, || {
+            self.registers
+                .interrupt_any_edge_sensitive(self.index)
+                .modify(|r| {
+                    r.set(
+                        interrupt_any_edge_sensitive::pin(bit_index),
+                        value.as_bool(),
+                    )
+                });
+        });
+        Ok(())
+    }
+}
+
+/// Saved GPIO bank configuration.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GpioBankState {
+    output_data: u32,
+    direction_mode: u32,
+    output_enable: u32,
+    interrupt_mask: u32,
+    interrupt_type: u32,
+    interrupt_polarity: u32,
+    interrupt_any_edge_sensitive: u32,
+}
+
+impl<B: Bus> Snapshot for GpioBank<B> {
+    type State = GpioBankState;
+
+    fn save(&self) -> GpioBankState {
+        let bank = self.index;
+        GpioBankState {
+            output_data: self.registers.output_data(bank).read().bits(),
+            direction_mode: self.registers.direction_mode(bank).read().bits(),
+            output_enable: self.registers.output_enable(bank).read().bits(),
+            interrupt_mask: self.registers.interrupt_mask_status(bank).read().bits(),
+            interrupt_type: self.registers.interrupt_type(bank).read().bits(),
+            interrupt_polarity: self.registers.interrupt_polarity(bank).read().bits(),
+            interrupt_any_edge_sensitive: self
+                .registers
+                .interrupt_any_edge_sensitive(bank)
+                .read()
+                .bits(),
+        }
+    }
+
+    /// Restore GPIO bank configuration.
+    ///
+    /// Output values are written before outputs are enabled, so pins do not glitch.
+    fn restore(&self, state: &GpioBankState) {
+        let bank = self.index;
+        self.registers
+            .interrupt_disable(bank)
+            .write_bits(0xFFFF_FFFF);
+        self.registers
+            .output_data(bank)
+            .write_bits(state.output_data);
+        self.registers
+            .direction_mode(bank)
+            .write_bits(state.direction_mode);
+        self.registers
+            .output_enable(bank)
+            .write_bits(state.output_enable);
+        self.registers
+            .interrupt_type(bank)
+            .write_bits(state.interrupt_type);
+        self.registers
+            .interrupt_polarity(bank)
+            .write_bits(state.interrupt_polarity);
+        self.registers
+            .interrupt_any_edge_sensitive(bank)
+            .write_bits(state.interrupt_any_edge_sensitive);
+        // Changing interrupt type may have raised interrupts.
+        self.registers
+            .interrupt_status(bank)
+            .write_bits(0xFFFF_FFFF);
+        // Mask register holds high bits for disabled interrupts.
+        self.registers
+            .interrupt_enable(bank)
+            .write_bits(state.interrupt_mask.not());
+    }
+}
+
+/// Interface for single pin of a GPIO bank.
+///
+/// Implements [`embedded_hal::digital`] traits.
+/// Pin's direction and output enable must be configured through its bank.
+pub struct Pin<'a, B = Mmio> {
+    /// Bank which has this pin.
+    bank: &'a GpioBank<B>,
+
+    /// MIO or EMIO index of pin.
+    index: u32,
+}
+
+impl<B> Pin<'_, B> {
+    /// MIO or EMIO index of pin.
+    #[inline]
+    #[must_use]
+    pub const fn index(&self) -> u32 {
+        self.index
+    }
+}
+
+impl embedded_hal::digital::Error for Error {
+    fn kind(&self) -> embedded_hal::digital::ErrorKind {
+        embedded_hal::digital::ErrorKind::Other
+    }
+}
+
+impl<B> embedded_hal::digital::ErrorType for Pin<'_, B> {
+    type Error = Error;
+}
+
+impl<B: Bus> embedded_hal::digital::InputPin for Pin<'_, B> {
+    fn is_high(&mut self) -> Result<bool, Self::Error> {
+        self.bank.read_input(self.index)
+    }
+
+    fn is_low(&mut self) -> Result<bool, Self::Error> {
+        self.bank.read_input(self.index).map(Not::not)
+    }
+}
+
+impl<B: Bus> embedded_hal::digital::OutputPin for Pin<'_, B> {
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        self.bank.write_output(self.index, false)
+    }
+
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        self.bank.write_output(self.index, true)
+    }
+}
+
+impl<B: Bus> embedded_hal::digital::StatefulOutputPin for Pin<'_, B> {
+    fn is_set_high(&mut self) -> Result<bool, Self::Error> {
+        self.bank.read_output(self.index)
+    }
+
+    fn is_set_low(&mut self) -> Result<bool, Self::Error> {
+        self.bank.read_output(self.index).map(Not::not)
+    }
+}
+
+/// Pin is not configured.
+///
+/// After reset, pins are inputs without interrupts.
+pub struct Unconfigured;
+
+/// Pin reads voltage.
+pub struct Input;
+
+/// Pin drives voltage.
+pub struct Output;
+
+/// Pin reads voltage and signals interrupts.
+pub struct Interrupt;
+
+/// Expected panic message for pin accessors.
+///
+/// Pin's index is checked at compile time, so accessors can not fail.
+const CHECKED_INDEX: &str = "Pin index is checked at compile time.";
+
+/// Implement typestate pin.
+macro_rules! typestate_pin {
+    (
+        $(#[$meta:meta])*
+        $pin:ident, $first:literal..=$last:literal, $kind:literal,
+        $read_input:ident,
+        $write_output:ident,
+        $read_output:ident,
+        $toggle_output:ident,
+        $set_direction:ident,
+        $toggle_interrupt:ident,
+        $read_interrupt_status:ident,
+        $clear_interrupt:ident,
+        $set_interrupt_type:ident,
+        $set_interrupt_polarity:ident,
+        $set_edge_triggering_mode:ident
+    ) => {
+        $(#[$meta])*
+        pub struct $pin<const N: u32, MODE = Unconfigured, B = Mmio> {
+            /// Register access.
+            bus: B,
+
+            /// Pin's mode.
+            mode: PhantomData<MODE>,
+        }
+
+        impl<const N: u32, MODE, B> $pin<N, MODE, B> {
+            /// Fails to compile if index is not valid.
+            #[allow(unused_comparisons)]
+            const VALID: () = assert!(
+                $first <= N && N <= $last,
+                concat!("Invalid ", $kind, " pin index.")
+            );
+
+            /// Pin's index in the bank's numbering of this pin kind.
+            const INDEX: u32 = N - $first;
+
+            /// Create pin using given register access.
+            const fn with_bus(bus: B) -> Self {
+                #[allow(clippy::let_unit_value)]
+                let () = Self::VALID;
+                Self {
+                    bus,
+                    mode: PhantomData,
+                }
+            }
+
+            /// Pin's index.
+            #[inline]
+            #[must_use]
+            pub const fn index(&self) -> u32 {
+                N
+            }
+        }
+
+        impl<const N: u32, MODE, B: Bus + Copy> $pin<N, MODE, B> {
+            /// Interface for GPIO peripheral.
+            fn gpio(&self) -> Gpio<B> {
+                Gpio::with_bus(self.bus)
+            }
+
+            /// Configure pin as input.
+            ///
+            /// Output and interrupts are disabled.
+            #[inline]
+            #[must_use]
+            pub fn into_input(self) -> $pin<N, Input, B> {
+                let gpio = self.gpio();
+                gpio.$toggle_interrupt(Self::INDEX, false).expect(CHECKED_INDEX);
+                gpio.$toggle_output(Self::INDEX, false).expect(CHECKED_INDEX);
+                gpio.$set_direction(Self::INDEX, PinDirection::Input).expect(CHECKED_INDEX);
+                $pin::with_bus(self.bus)
+            }
+
+            /// Configure pin as output.
+            ///
+            /// Output is set to given state before it is enabled.
+            #[inline]
+            #[must_use]
+            pub fn into_output(self, state: bool) -> $pin<N, Output, B> {
+                let gpio = self.gpio();
+                gpio.$toggle_interrupt(Self::INDEX, false).expect(CHECKED_INDEX);
+                gpio.$write_output(Self::INDEX, state).expect(CHECKED_INDEX);
+                gpio.$set_direction(Self::INDEX, PinDirection::Output).expect(CHECKED_INDEX);
+                gpio.$toggle_output(Self::INDEX, true).expect(CHECKED_INDEX);
+                $pin::with_bus(self.bus)
+            }
+
+            /// Configure pin as input which signals interrupts.
+            ///
+            /// Pending interrupt is cleared before interrupts are enabled.
+            #[inline]
+            #[must_use]
+            pub fn into_interrupt(
+                self,
+                kind: InterruptType,
+                polarity: InterruptPolarity,
+                edges: InterruptEdgeTriggeringMode,
+            ) -> $pin<N, Interrupt, B> {
+                let gpio = self.gpio();
+                gpio.$toggle_interrupt(Self::INDEX, false).expect(CHECKED_INDEX);
+                gpio.$toggle_output(Self::INDEX, false).expect(CHECKED_INDEX);
+                gpio.$set_direction(Self::INDEX, PinDirection::Input).expect(CHECKED_INDEX);
+                gpio.$set_interrupt_type(Self::INDEX, kind).expect(CHECKED_INDEX);
+                gpio.$set_interrupt_polarity(Self::INDEX, polarity).expect(CHECKED_INDEX);
+                gpio.$set_edge_triggering_mode(Self::INDEX, edges).expect(CHECKED_INDEX);
+                gpio.$clear_interrupt(Self::INDEX).expect(CHECKED_INDEX);
+                gpio.$toggle_interrupt(Self::INDEX, true).expect(CHECKED_INDEX);
+                $pin::with_bus(self.bus)
+            }
+        }
+
+        impl<const N: u32, B: Bus + Copy> $pin<N, Input, B> {
+            /// True if pin's voltage is high.
+            #[inline]
+            #[must_use]
+            pub fn is_high(&self) -> bool {
+                self.gpio().$read_input(Self::INDEX).expect(CHECKED_INDEX)
+            }
+
+            /// True if pin's voltage is low.
+            #[inline]
+            #[must_use]
+            pub fn is_low(&self) -> bool {
+                self.is_high().not()
+            }
+        }
+
+        impl<const N: u32, B: Bus + Copy> $pin<N, Output, B> {
+            /// Drive pin high or low.
+            #[inline]
+            pub fn set_state(&mut self, state: bool) {
+                self.gpio().$write_output(Self::INDEX, state).expect(CHECKED_INDEX);
+            }
+
+            /// Drive pin high.
+            #[inline]
+            pub fn set_high(&mut self) {
+                self.set_state(true);
+            }
+
+            /// Drive pin low.
+            #[inline]
+            pub fn set_low(&mut self) {
+                self.set_state(false);
+            }
+
+            /// Invert driven state.
+            #[inline]
+            pub fn toggle(&mut self) {
+                let state = self.is_set_high();
+                self.set_state(state.not());
+            }
+
+            /// True if pin is driven high.
+            #[inline]
+            #[must_use]
+            pub fn is_set_high(&self) -> bool {
+                self.gpio().$read_output(Self::INDEX).expect(CHECKED_INDEX)
+            }
+
+            /// True if pin is driven low.
+            #[inline]
+            #[must_use]
+            pub fn is_set_low(&self) -> bool {
+                self.is_set_high().not()
+            }
+        }
+
+        impl<const N: u32, B: Bus + Copy> $pin<N, Interrupt, B> {
+            /// True if pin's voltage is high.
+            #[inline]
+            #[must_use]
+            pub fn is_high(&self) -> bool {
+                self.gpio().$read_input(Self::INDEX).expect(CHECKED_INDEX)
+            }
+
+            /// True if pin's voltage is low.
+            #[inline]
+            #[must_use]
+            pub fn is_low(&self) -> bool {
+                self.is_high().not()
+            }
+
+            /// True if pin has signaled an interrupt.
+            #[inline]
+            #[must_use]
+            pub fn is_interrupt_pending(&self) -> bool {
+                self.gpio().$read_interrupt_status(Self::INDEX).expect(CHECKED_INDEX)
+            }
+
+            /// Clear pin's interrupt.
+            #[inline]
+            pub fn clear_interrupt(&mut self) {
+                self.gpio().$clear_interrupt(Self::INDEX).expect(CHECKED_INDEX);
+            }
+        }
+
+        impl<const N: u32, MODE, B> embedded_hal::digital::ErrorType for $pin<N, MODE, B> {
+            type Error = Infallible;
+        }
+
+        impl<const N: u32, B: Bus + Copy> embedded_hal::digital::InputPin for $pin<N, Input, B> {
+            fn is_high(&mut self) -> Result<bool, Self::Error> {
+                Ok($pin::<N, Input, B>::is_high(self))
+            }
+
+            fn is_low(&mut self) -> Result<bool, Self::Error> {
+                Ok($pin::<N, Input, B>::is_low(self))
+            }
+        }
+
+        impl<const N: u32, B: Bus + Copy> embedded_hal::digital::InputPin
+            for $pin<N, Interrupt, B>
+        {
+            fn is_high(&mut self) -> Result<bool, Self::Error> {
+                Ok($pin::<N, Interrupt, B>::is_high(self))
+            }
+
+            fn is_low(&mut self) -> Result<bool, Self::Error> {
+                Ok($pin::<N, Interrupt, B>::is_low(self))
+            }
+        }
+
+        impl<const N: u32, B: Bus + Copy> embedded_hal::digital::OutputPin for $pin<N, Output, B> {
+            fn set_low(&mut self) -> Result<(), Self::Error> {
+                $pin::<N, Output, B>::set_low(self);
+                Ok(())
+            }
+
+            fn set_high(&mut self) -> Result<(), Self::Error> {
+                $pin::<N, Output, B>::set_high(self);
+                Ok(())
+            }
+        }
+
+        impl<const N: u32, B: Bus + Copy> embedded_hal::digital::StatefulOutputPin
+            for $pin<N, Output, B>
+        {
+            fn is_set_high(&mut self) -> Result<bool, Self::Error> {
+                Ok($pin::<N, Output, B>::is_set_high(self))
+            }
+
+            fn is_set_low(&mut self) -> Result<bool, Self::Error> {
+                Ok($pin::<N, Output, B>::is_set_low(self))
+            }
+        }
+    };
+}
+
+typestate_pin!(
+    /// MIO pin with index `N` in 0..=53.
+    MioPin, 0..=53, "MIO",
+    read_mio_input,
+    write_mio_output,
+    read_mio_output,
+    toggle_mio_output,
+    set_mio_direction,
+    toggle_mio_interrupt,
+    read_mio_interrupt_status,
+    clear_mio_interrupt,
+    set_mio_interrupt_type,
+    set_mio_interrupt_polarity,
+    set_mio_edge_triggering_mode
+);
+
+typestate_pin!(
+    /// EMIO pin with index `N` in 54..=117.
+    ///
+    /// EMIO pin `N` is EMIO pin `N - 54` in [`Gpio`]'s EMIO accessors.
+    EmioPin, 54..=117, "EMIO",
+    read_emio_input,
+    write_emio_output,
+    read_emio_output,
+    toggle_emio_output,
+    set_emio_direction,
+    toggle_emio_interrupt,
+    read_emio_interrupt_status,
+    clear_emio_interrupt,
+    set_emio_interrupt_type,
+    set_emio_interrupt_polarity,
+    set_emio_edge_triggering_mode
+);
+
+/// Declare pins of GPIO peripheral.
+macro_rules! pins {
+    (
+        mio { $($mio:ident: $mio_index:literal,)* }
+        emio { $($emio:ident: $emio_index:literal,)* }
+    ) => {
+        /// Pins of a GPIO peripheral.
+        pub struct Pins<B = Mmio> {
+            $(
+                #[doc = concat!("MIO pin ", stringify!($mio_index), ".")]
+                pub $mio: MioPin<$mio_index, Unconfigured, B>,
+            )*
+            $(
+                #[doc = concat!("EMIO pin ", stringify!($emio_index), ".")]
+                pub $emio: EmioPin<$emio_index, Unconfigured, B>,
+            )*
+        }
+
+        impl<B: Copy> Pins<B> {
+            /// Create pins using given register access.
+            const fn with_bus(bus: B) -> Self {
+                Self {
+                    $($mio: MioPin::with_bus(bus),)*
+                    $($emio: EmioPin::with_bus(bus),)*
+                }
+            }
+        }
+    };
+}
+
+pins! {
+    mio {
+        mio0: 0,
+        mio1: 1,
+        mio2: 2,
+        mio3: 3,
+        mio4: 4,
+        mio5: 5,
+        mio6: 6,
+        mio7: 7,
+        mio8: 8,
+        mio9: 9,
+        mio10: 10,
+        mio11: 11,
+        mio12: 12,
+        mio13: 13,
+        mio14: 14,
+        mio15: 15,
+        mio16: 16,
+        mio17: 17,
+        mio18: 18,
+        mio19: 19,
+        mio20: 20,
+        mio21: 21,
+        mio22: 22,
+        mio23: 23,
+        mio24: 24,
+        mio25: 25,
+        mio26: 26,
+        mio27: 27,
+        mio28: 28,
+        mio29: 29,
+        mio30: 30,
+        mio31: 31,
+        mio32: 32,
+        mio33: 33,
+        mio34: 34,
+        mio35: 35,
+        mio36: 36,
+        mio37: 37,
+        mio38: 38,
+        mio39: 39,
+        mio40: 40,
+        mio41: 41,
+        mio42: 42,
+        mio43: 43,
+        mio44: 44,
+        mio45: 45,
+        mio46: 46,
+        mio47: 47,
+        mio48: 48,
+        mio49: 49,
+        mio50: 50,
+        mio51: 51,
+        mio52: 52,
+        mio53: 53,
+    }
+    emio {
+        emio54: 54,
+        emio55: 55,
+        emio56: 56,
+        emio57: 57,
+        emio58: 58,
+        emio59: 59,
+        emio60: 60,
+        emio61: 61,
+        emio62: 62,
+        emio63: 63,
+        emio64: 64,
+        emio65: 65,
+        emio66: 66,
+        emio67: 67,
+        emio68: 68,
+        emio69: 69,
+        emio70: 70,
+        emio71: 71,
+        emio72: 72,
+        emio73: 73,
+        emio74: 74,
+        emio75: 75,
+        emio76: 76,
+        emio77: 77,
+        emio78: 78,
+        emio79: 79,
+        emio80: 80,
+        emio81: 81,
+        emio82: 82,
+        emio83: 83,
+        emio84: 84,
+        emio85: 85,
+        emio86: 86,
+        emio87: 87,
+        emio88: 88,
+        emio89: 89,
+        emio90: 90,
+        emio91: 91,
+        emio92: 92,
+        emio93: 93,
+        emio94: 94,
+        emio95: 95,
+        emio96: 96,
+        emio97: 97,
+        emio98: 98,
+        emio99: 99,
+        emio100: 100,
+        emio101: 101,
+        emio102: 102,
+        emio103: 103,
+        emio104: 104,
+        emio105: 105,
+        emio106: 106,
+        emio107: 107,
+        emio108: 108,
+        emio109: 109,
+        emio110: 110,
+        emio111: 111,
+        emio112: 112,
+        emio113: 113,
+        emio114: 114,
+        emio115: 115,
+        emio116: 116,
+        emio117: 117,
+    }
+}
+
 /// Base address for memory mapped GPIO.
 const ADDRESS_GPIO_BASE: u32 = 0xE000_A000;
-/// GPIO bank 0 base address.
-const ADDRESS_BANK0_BASE: u32 = ADDRESS_GPIO_BASE + 0x204;
-/// GPIO bank 1 base address.
-const ADDRESS_BANK1_BASE: u32 = ADDRESS_GPIO_BASE + 0x244;
-/// GPIO bank 2 base address.
-const ADDRESS_BANK2_BASE: u32 = ADDRESS_GPIO_BASE + 0x284;
-/// GPIO bank 3 base address.
-const ADDRESS_BANK3_BASE: u32 = ADDRESS_GPIO_BASE + 0x2C4;
-
-/// GPIO bank 0.
-static mut BANK0: Bank = Bank {
-    // These addresses are not grouped by the bank.
-    address_maskable_output_data_lsw: (ADDRESS_GPIO_BASE + 0x000) as *mut u32,
-    address_maskable_output_data_msw: (ADDRESS_GPIO_BASE + 0x004) as *mut u32,
-    address_output_data: (ADDRESS_GPIO_BASE + 0x040) as *mut u32,
-    address_input_data: (ADDRESS_GPIO_BASE + 0x060) as *mut u32,
-    // These addresses are grouped by the bank.
-    address_direction_mode: (ADDRESS_BANK0_BASE + 0x000) as *mut u32,
-    address_output_enable: (ADDRESS_BANK0_BASE + 0x004) as *mut u32,
-    address_interrupt_mask_status: (ADDRESS_BANK0_BASE + 0x008) as *mut u32,
-    address_interrupt_enable: (ADDRESS_BANK0_BASE + 0x00C) as *mut u32,
-    address_interrupt_disable: (ADDRESS_BANK0_BASE + 0x010) as *mut u32,
-    address_interrupt_status: (ADDRESS_BANK0_BASE + 0x014) as *mut u32,
-    address_interrupt_type: (ADDRESS_BANK0_BASE + 0x018) as *mut u32,
-    address_interrupt_polarity: (ADDRESS_BANK0_BASE + 0x01C) as *mut u32,
-    address_interrupt_any_edge_sensitive: (ADDRESS_BANK0_BASE + 0x020) as *mut u32,
-};
-
-/// GPIO bank 1.
-static mut BANK1: Bank = Bank {
-    // These addresses are not grouped by the bank.
-    address_maskable_output_data_lsw: (ADDRESS_GPIO_BASE + 0x008) as *mut u32,
-    address_maskable_output_data_msw: (ADDRESS_GPIO_BASE + 0x00C) as *mut u32,
-    address_output_data: (ADDRESS_GPIO_BASE + 0x044) as *mut u32,
-    address_input_data: (ADDRESS_GPIO_BASE + 0x064) as *mut u32,
-    // These addresses are grouped by the bank.
-    address_direction_mode: (ADDRESS_BANK1_BASE + 0x000) as *mut u32,
-    address_output_enable: (ADDRESS_BANK1_BASE + 0x004) as *mut u32,
-    address_interrupt_mask_status: (ADDRESS_BANK1_BASE + 0x008) as *mut u32,
-    address_interrupt_enable: (ADDRESS_BANK1_BASE + 0x00C) as *mut u32,
-    address_interrupt_disable: (ADDRESS_BANK1_BASE + 0x010) as *mut u32,
-    address_interrupt_status: (ADDRESS_BANK1_BASE + 0x014) as *mut u32,
-    address_interrupt_type: (ADDRESS_BANK1_BASE + 0x018) as *mut u32,
-    address_interrupt_polarity: (ADDRESS_BANK1_BASE + 0x01C) as *mut u32,
-    address_interrupt_any_edge_sensitive: (ADDRESS_BANK1_BASE + 0x020) as *mut u32,
-};
-
-/// GPIO bank 2.
-static mut BANK2: Bank = Bank {
-    // These addresses are not grouped by the bank.
-    address_maskable_output_data_lsw: (ADDRESS_GPIO_BASE + 0x010) as *mut u32,
-    address_maskable_output_data_msw: (ADDRESS_GPIO_BASE + 0x014) as *mut u32,
-    address_output_data: (ADDRESS_GPIO_BASE + 0x048) as *mut u32,
-    address_input_data: (ADDRESS_GPIO_BASE + 0x068) as *mut u32,
-    // These addresses are grouped by the bank.
-    address_direction_mode: (ADDRESS_BANK2_BASE + 0x000) as *mut u32,
-    address_output_enable: (ADDRESS_BANK2_BASE + 0x004) as *mut u32,
-    address_interrupt_mask_status: (ADDRESS_BANK2_BASE + 0x008) as *mut u32,
-    address_interrupt_enable: (ADDRESS_BANK2_BASE + 0x00C) as *mut u32,
-    address_interrupt_disable: (ADDRESS_BANK2_BASE + 0x010) as *mut u32,
-    address_interrupt_status: (ADDRESS_BANK2_BASE + 0x014) as *mut u32,
-    address_interrupt_type: (ADDRESS_BANK2_BASE + 0x018) as *mut u32,
-    address_interrupt_polarity: (ADDRESS_BANK2_BASE + 0x01C) as *mut u32,
-    address_interrupt_any_edge_sensitive: (ADDRESS_BANK2_BASE + 0x020) as *mut u32,
-};
-
-/// GPIO bank 3.
-static mut BANK3: Bank = Bank {
-    // These addresses are not grouped by the bank.
-    address_maskable_output_data_lsw: (ADDRESS_GPIO_BASE + 0x018) as *mut u32,
-    address_maskable_output_data_msw: (ADDRESS_GPIO_BASE + 0x01C) as *mut u32,
-    address_output_data: (ADDRESS_GPIO_BASE + 0x04C) as *mut u32,
-    address_input_data: (ADDRESS_GPIO_BASE + 0x06C) as *mut u32,
-    // These addresses are grouped by the bank.
-    address_direction_mode: (ADDRESS_BANK3_BASE + 0x000) as *mut u32,
-    address_output_enable: (ADDRESS_BANK3_BASE + 0x004) as *mut u32,
-    address_interrupt_mask_status: (ADDRESS_BANK3_BASE + 0x008) as *mut u32,
-    address_interrupt_enable: (ADDRESS_BANK3_BASE + 0x00C) as *mut u32,
-    address_interrupt_disable: (ADDRESS_BANK3_BASE + 0x010) as *mut u32,
-    address_interrupt_status: (ADDRESS_BANK3_BASE + 0x014) as *mut u32,
-    address_interrupt_type: (ADDRESS_BANK3_BASE + 0x018) as *mut u32,
-    address_interrupt_polarity: (ADDRESS_BANK3_BASE + 0x01C) as *mut u32,
-    address_interrupt_any_edge_sensitive: (ADDRESS_BANK3_BASE + 0x020) as *mut u32,
-};
-
-/// GPIO peripheral.
-pub static mut GPIO: Gpio = unsafe {
-    Gpio {
-        mio_pin_range: 0..=53,
-        emio_pin_range: 0..=63,
-        banks: [BANK0, BANK1, BANK2, BANK3],
-        bank_pin_ranges: [0..=31, 32..=53, 0..=31, 32..=63],
-        mio_bank_indices: 0..=1,
-        emio_bank_indices: 2..=3,
-    }
-};
+
+/// Held while bank's configuration register is read, modified and written.
+///
+/// Pins of a bank share its registers, and pins may be used on both cores and in interrupt handlers.
+static BANK_LOCKS: [Mutex<()>; 4] = [const { Mutex::new(()) }; 4];
+
+/// Modify bank's configuration register with interrupts masked and bank lock held.
+fn modify_bank(bank: u32, f: impl FnOnce()) {
+    BANK_LOCKS[bank as usize].lock(|()| f());
+}
+
+/// Write pin's output through maskable output data register.
+///
+/// Other pins of the bank are masked, so their outputs are not read nor written.
+fn write_output_masked<B: Bus>(
+    registers: &GpioRegisters<B>,
+    bank: u32,
+    bit_index: u32,
+    state: bool,
+) {
+    let bit = bit_index % 16;
+    let mask = 0xFFFF & !(1 << bit);
+    let data = u32::from(state) << bit;
+    if bit_index < 16 {
+        registers.maskable_output_data_lsw(bank).write(|r| {
+            r.write(maskable_output_data_lsw::MASK, mask)
+                .write(maskable_output_data_lsw::DATA, data)
+        });
+    } else {
+        registers.maskable_output_data_msw(bank).write(|r| {
+            r.write(maskable_output_data_msw::MASK, mask)
+                .write(maskable_output_data_msw::DATA, data)
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::bus::SimulatedBus;
+
+    #[test]
+    fn output_is_written_before_it_is_enabled() {
+        let bus = SimulatedBus::<8, 8>::new();
+        let pins = Gpio::with_bus(&bus).into_pins();
+
+        let _pin = pins.mio7.into_output(true);
+        assert!(bus.writes().map(|(a, v)| (a as usize, v)).eq([
+            // Interrupt disabled.
+            (0xE000_A214, 0x80),
+            // Output data, other pins masked.
+            (0xE000_A000, 0xFF7F_0080),
+            // Direction.
+            (0xE000_A204, 0x80),
+            // Output enable.
+            (0xE000_A208, 0x80),
+        ]));
+    }
+
+    #[test]
+    fn pins_of_same_bank_do_not_overwrite_each_other() {
+        let bus = SimulatedBus::<8, 16>::new();
+        let pins = Gpio::with_bus(&bus).into_pins();
+
+        let mut first = pins.mio7.into_output(true);
+        let mut second = pins.mio20.into_output(true);
+        first.set_low();
+        second.set_low();
+        // Outputs are written through maskable registers, which touch only the pin's own bit.
+        assert!(bus
+            .writes()
+            .map(|(a, v)| (a as usize, v))
+            .filter(|(a, _)| *a < 0xE000_A040)
+            .eq([
+                (0xE000_A000, 0xFF7F_0080),
+                (0xE000_A004, 0xFFEF_0010),
+                (0xE000_A000, 0xFF7F_0000),
+                (0xE000_A004, 0xFFEF_0000),
+            ]));
+        // Configuration of the second pin kept the first pin's bits.
+        assert_eq!(bus.value(0xE000_A204 as *mut u32), 0x0010_0080);
+        assert_eq!(bus.value(0xE000_A208 as *mut u32), 0x0010_0080);
+
+        let _first = first.into_input();
+        assert_eq!(bus.value(0xE000_A204 as *mut u32), 0x0010_0000);
+        assert_eq!(bus.value(0xE000_A208 as *mut u32), 0x0010_0000);
+    }
+
+    #[test]
+    fn input_disables_output() {
+        let bus = SimulatedBus::<8, 8>::new();
+        bus.preload(0xE000_A204 as *mut u32, 0x80);
+        bus.preload(0xE000_A208 as *mut u32, 0x81);
+        let pins = Gpio::with_bus(&bus).into_pins();
+
+        let _pin = pins.mio7.into_input();
+        assert_eq!(bus.value(0xE000_A208 as *mut u32), 0x01);
+        assert_eq!(bus.value(0xE000_A204 as *mut u32), 0x00);
+        assert!(bus
+            .writes()
+            .map(|(a, _)| a as usize)
+            .eq([0xE000_A214, 0xE000_A208, 0xE000_A204]));
+    }
+}
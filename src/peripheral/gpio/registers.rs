@@ -0,0 +1,93 @@
+//! GPIO registers.
+//!
+//! Registers are arrays indexed by GPIO bank.
+
+crate::register_block! {
+    /// GPIO registers.
+    pub GpioRegisters {
+        /// Maskable output data registers for lower 16 pins of a bank.
+        0x000 [4; 0x008] => maskable_output_data_lsw: ReadWrite, reset = 0x0000_0000 {
+            /// Output values of pins.
+            DATA: 0, 16;
+            /// Pins which are not written.
+            MASK: 16, 16;
+        }
+
+        /// Maskable output data registers for upper 16 pins of a bank.
+        0x004 [4; 0x008] => maskable_output_data_msw: ReadWrite, reset = 0x0000_0000 {
+            /// Output values of pins.
+            DATA: 0, 16;
+            /// Pins which are not written.
+            MASK: 16, 16;
+        }
+
+        /// Output data registers.
+        0x040 [4; 0x004] => output_data: ReadWrite, reset = 0x0000_0000 {
+            /// Output value of pin.
+            pin[32]: 0, 1;
+        }
+
+        /// Input data registers.
+        0x060 [4; 0x004] => input_data: ReadOnly, reset = 0x0000_0000 {
+            /// Input value of pin.
+            pin[32]: 0, 1;
+        }
+
+        /// Direction mode registers.
+        0x204 [4; 0x040] => direction_mode: ReadWrite, reset = 0x0000_0000 {
+            /// Pin is output.
+            pin[32]: 0, 1;
+        }
+
+        /// Output enable registers.
+        0x208 [4; 0x040] => output_enable: ReadWrite, reset = 0x0000_0000 {
+            /// Pin's output is enabled.
+            pin[32]: 0, 1;
+        }
+
+        /// Interrupt mask status registers.
+        0x20C [4; 0x040] => interrupt_mask_status: ReadOnly, reset = 0x0000_0000 {
+            /// Pin's interrupt is masked.
+            pin[32]: 0, 1;
+        }
+
+        /// Interrupt enable registers.
+        0x210 [4; 0x040] => interrupt_enable: WriteOnly, reset = 0x0000_0000 {
+            /// Enable pin's interrupt.
+            pin[32]: 0, 1;
+        }
+
+        /// Interrupt disable registers.
+        0x214 [4; 0x040] => interrupt_disable: WriteOnly, reset = 0x0000_0000 {
+            /// Disable pin's interrupt.
+            pin[32]: 0, 1;
+        }
+
+        /// Interrupt status registers.
+        ///
+        /// Write high bit to clear interrupt.
+        0x218 [4; 0x040] => interrupt_status: ReadWrite, reset = 0x0000_0000 {
+            /// Pin's interrupt is pending.
+            pin[32]: 0, 1;
+        }
+
+        /// Interrupt type registers.
+        // Bank 1 resets to 0x003F_FFFF.
+        0x21C [4; 0x040] => interrupt_type: ReadWrite, reset = 0xFFFF_FFFF {
+            /// Pin's interrupt is edge-sensitive.
+            pin[32]: 0, 1;
+        }
+
+        /// Interrupt polarity registers.
+        0x220 [4; 0x040] => interrupt_polarity: ReadWrite, reset = 0x0000_0000 {
+            /// Pin's interrupt triggers on high voltage or rising edge.
+            pin[32]: 0, 1;
+        }
+
+        /// Interrupt any edge sensitive registers.
+        0x224 [4; 0x040] => interrupt_any_edge_sensitive: ReadWrite, reset = 0x0000_0000 {
+            /// Pin's interrupt triggers on both edges.
+            pin[32]: 0, 1;
+        }
+    }
+}
@@ -2,6 +2,10 @@
 
 pub mod rgb;
 
+use crate::common::bitman::WriteBitwise;
+use crate::common::bus::Bus;
+use crate::common::bus::Mmio;
+
 pub enum LedIndex {
     Led0,
     Led1,
@@ -21,38 +25,52 @@ impl LedIndex {
 }
 
 /// Interface for board LED.
-pub struct Led {
+pub struct Led<B = Mmio> {
+    /// Register access.
+    bus: B,
     /// Address to LED.
     address: *mut u32,
     /// Bit index to LED.
     index: u32,
 }
 
-impl Led {
+impl<B: Bus> Led<B> {
     /// Enable or disable LED.
     #[inline]
     pub fn toggle(&self, enable: bool) {
-        use crate::common::memman::clear_address_bit;
-        use crate::common::memman::set_address_bit;
-
-        let action = if enable {
-            set_address_bit
-        } else {
-            clear_address_bit
-        };
-        action(self.address, self.index);
+        // Safety:
+        // Caller of `Leds::configure` guarantees that address is a mapped LED register.
+        unsafe {
+            self.bus.modify(self.address, |old| {
+                old.write_bits(self.index, u32::from(enable), 1)
+            });
+        }
     }
 }
 
 /// Interface for board LEDs.
-pub struct Leds {
+pub struct Leds<B = Mmio> {
     /// Board LEDs.
-    pub leds: [Led; 4],
+    pub leds: [Led<B>; 4],
 }
 
 impl Leds {
     /// Configure LEDs base address and order.
-    pub fn configure(address: *mut u32, indices: [LedIndex; 4]) -> Self {
+    ///
+    /// # Safety
+    ///
+    /// Address must be a mapped LED register in programmable logic,
+    /// which is not accessed through another interface.
+    pub unsafe fn configure(address: *mut u32, indices: [LedIndex; 4]) -> Self {
+        // Safety:
+        // Programmable logic is not owned by `Peripherals`, caller guarantees exclusive access.
+        Self::configure_with_bus(address, indices, unsafe { Mmio::steal() })
+    }
+}
+
+impl<B: Copy> Leds<B> {
+    /// Configure LEDs base address and order using given register access.
+    pub fn configure_with_bus(address: *mut u32, indices: [LedIndex; 4], bus: B) -> Self {
         let mut led0_index = None;
         let mut led1_index = None;
         let mut led2_index = None;
@@ -77,27 +95,33 @@ impl Leds {
         Self {
             leds: [
                 Led {
+                    bus,
                     address,
                     index: led0_index.unwrap(),
                 },
                 Led {
+                    bus,
                     address,
                     index: led1_index.unwrap(),
                 },
                 Led {
+                    bus,
                     address,
                     index: led2_index.unwrap(),
                 },
                 Led {
+                    bus,
                     address,
                     index: led3_index.unwrap(),
                 },
             ],
         }
     }
+}
 
+impl<B: Bus> Leds<B> {
     /// Get LED by LED index.
-    pub fn get_led(&self, index: LedIndex) -> &Led {
+    pub fn get_led(&self, index: LedIndex) -> &Led<B> {
         let index = index.to_u32();
         &self.leds[index as usize]
     }
@@ -1,10 +1,12 @@
 //! Light-emitting diode with colors.
 
-use crate::common::memman::clear_address_bit;
-use crate::common::memman::set_address_bit;
+use crate::common::bitman::WriteBitwise;
+use crate::common::bus::Bus;
+use crate::common::bus::Mmio;
 
 /// Interface to board RGB LED.
-pub struct RgbLed {
+pub struct RgbLed<B = Mmio> {
+    bus: B,
     address_red: *mut u32,
     address_green: *mut u32,
     address_blue: *mut u32,
@@ -13,7 +15,7 @@ pub struct RgbLed {
     index_blue: u32,
 }
 
-impl RgbLed {
+impl<B: Bus> RgbLed<B> {
     /// Get addresses.
     #[inline]
     #[must_use]
@@ -31,34 +33,37 @@ impl RgbLed {
     /// Enable or disable red color.
     #[inline]
     pub fn toggle_red(&self, enable: bool) {
-        let action = if enable {
-            set_address_bit
-        } else {
-            clear_address_bit
-        };
-        action(self.address_red, self.index_red);
+        // Safety:
+        // RGB LED has no public constructor, so addresses are set by this crate.
+        unsafe {
+            self.bus.modify(self.address_red, |old| {
+                old.write_bits(self.index_red, u32::from(enable), 1)
+            });
+        }
     }
 
     /// Enable or disable green color.
     #[inline]
     pub fn toggle_green(&self, enable: bool) {
-        let action = if enable {
-            set_address_bit
-        } else {
-            clear_address_bit
-        };
-        action(self.address_green, self.index_green);
+        // Safety:
+        // RGB LED has no public constructor, so addresses are set by this crate.
+        unsafe {
+            self.bus.modify(self.address_green, |old| {
+                old.write_bits(self.index_green, u32::from(enable), 1)
+            });
+        }
     }
 
     /// Enable or disable blue color.
     #[inline]
     pub fn toggle_blue(&self, enable: bool) {
-        let action = if enable {
-            set_address_bit
-        } else {
-            clear_address_bit
-        };
-        action(self.address_blue, self.index_blue);
+        // Safety:
+        // RGB LED has no public constructor, so addresses are set by this crate.
+        unsafe {
+            self.bus.modify(self.address_blue, |old| {
+                old.write_bits(self.index_blue, u32::from(enable), 1)
+            });
+        }
     }
 
     /// Enable or disable colors.
@@ -77,11 +82,11 @@ impl RgbLed {
 }
 
 /// Interface to board RGB LEDs.
-pub struct RgbLeds {
-    leds: [RgbLed; 2],
+pub struct RgbLeds<B = Mmio> {
+    leds: [RgbLed<B>; 2],
 }
 
-impl RgbLeds {
+impl<B: Bus> RgbLeds<B> {
     // TODO
 
     /// Disable all board RGB LEDs.
@@ -2,14 +2,37 @@
 
 // TODO: substructs for pll_configuration, clock_control, etc
 
-use crate::common::bitman::SetBitwise;
-use crate::common::memman::clear_address_bit;
-use crate::common::memman::read_address_bit;
-use crate::common::memman::read_address_bits;
-use crate::common::memman::set_address_bit;
-use crate::common::memman::write_address_bits;
-use crate::common::memman::write_to_address;
+pub mod registers;
+
+use crate::common::bus::Bus;
+use crate::common::bus::Mmio;
+use crate::common::register::Field;
+use crate::common::timing::FREQUENCY_PS_CLK;
 use crate::peripheral::uart::DeviceIndex as UartDeviceIndex;
+use registers::amba_clock_control;
+use registers::arm_pll_control;
+use registers::boot_mode;
+use registers::central_interconnect_reset_control;
+use registers::cpu_reset_and_clock_control;
+use registers::ddr_pll_control;
+use registers::ddr_reset_control;
+use registers::dmac_reset_control;
+use registers::fpga_reset_control;
+use registers::gpio_reset_control;
+use registers::io_pll_control;
+use registers::ocm_reset_control;
+use registers::ps_reset_control;
+use registers::quad_spi_reset_control;
+use registers::reboot_status;
+use registers::secure_configuration_lock;
+use registers::smc_reset_control;
+use registers::uart_clock_control;
+use registers::uart_reset_control;
+use registers::watchdog_timer_reset_control;
+use registers::write_protection_lock;
+use registers::write_protection_status;
+use registers::write_protection_unlock;
+use registers::SlcrRegisters;
 
 #[derive(Clone, Copy)]
 pub enum Frst {
@@ -44,7 +67,14 @@ pub struct ResetCpuCommand {
     reset_cpu_peripherals: bool,
 }
 
+impl Default for ResetCpuCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl ResetCpuCommand {
+    #[must_use]
     pub fn new() -> Self {
         Self {
             reset_cpu0: false,
@@ -156,232 +186,189 @@ pub struct BootMode {
 }
 
 /// System level reset control registers.
-pub struct Reset {
-    address_ps_reset_control: *mut u32,
-    address_ddr_reset_control: *mut u32,
-    address_central_interconnect_reset_control: *mut u32,
-    address_dmac_reset_control: *mut u32,
-    address_usb_reset_control: *mut u32,
-    address_ethernet_reset_control: *mut u32,
-    address_sdio_reset_control: *mut u32,
-    address_spi_reset_control: *mut u32,
-    address_can_reset_control: *mut u32,
-    address_i2c_reset_control: *mut u32,
-    address_uart_reset_control: *mut u32,
-    address_gpio_reset_control: *mut u32,
-    address_quad_spi_reset_control: *mut u32,
-    address_smc_reset_control: *mut u32,
-    address_ocm_reset_control: *mut u32,
-    address_fpga_reset_control: *mut u32,
-    address_cpu_reset_and_clock_control: *mut u32,
-    address_watchdog_timer_reset_control: *mut u32,
-    // TODO other registers
-    address_reboot_status: *mut u32,
-    address_boot_mode: *mut u32,
+pub struct Reset<B = Mmio> {
+    /// System level control registers.
+    registers: SlcrRegisters<B>,
+}
+
+impl<B> Reset<B> {
+    /// Create interface for reset control registers using given register access.
+    #[must_use]
+    pub const fn with_bus(bus: B) -> Self {
+        Self {
+            registers: SlcrRegisters::new(ADDRESS_BASE, bus),
+        }
+    }
 }
 
-impl Reset {
+impl<B: Bus> Reset<B> {
     /// Reset entire system.
     pub fn reset(&self) {
         // Generates reset pulse, no need to clear.
-        set_address_bit(self.address_ps_reset_control, 0);
+        self.registers
+            .ps_reset_control()
+            .modify(|r| r.set(ps_reset_control::SOFT_RST, true));
     }
 
     pub fn reset_ddr(&self) {
-        let address = self.address_ddr_reset_control;
-        set_address_bit(address, 0);
+        let register = self.registers.ddr_reset_control();
+        register.modify(|r| r.set(ddr_reset_control::DDR_RST, true));
         // TODO: wait needed?
-        clear_address_bit(address, 0);
+        register.modify(|r| r.set(ddr_reset_control::DDR_RST, false));
     }
 
     pub fn reset_central_interconnect(&self) {
-        let address = self.address_central_interconnect_reset_control;
-        set_address_bit(address, 0);
+        let register = self.registers.central_interconnect_reset_control();
+        register.modify(|r| r.set(central_interconnect_reset_control::TOPSW_RST, true));
         // TODO: wait needed?
-        clear_address_bit(address, 0);
+        register.modify(|r| r.set(central_interconnect_reset_control::TOPSW_RST, false));
     }
 
     pub fn reset_dma_controller(&self) {
-        let address = self.address_dmac_reset_control;
-        set_address_bit(address, 0);
+        let register = self.registers.dmac_reset_control();
+        register.modify(|r| r.set(dmac_reset_control::DMAC_RST, true));
         // TODO: wait needed?
-        clear_address_bit(address, 0);
+        register.modify(|r| r.set(dmac_reset_control::DMAC_RST, false));
     }
 
     // TODO
     /*
     // TODO: usb enum
     pub fn reset_usb(&self) {
-        let address = self.address_usb_reset_control;
-        //set_address_bit(address, 0);
+        let register = self.registers.usb_reset_control();
         // TODO: wait needed?
-        //clear_address_bit(address, 0);
     }
 
     // TODO: ethernet enum
     pub fn reset_ethernet(&self) {
-        let address = self.address_ethernet_reset_control;
-        //set_address_bit(address, 0);
+        let register = self.registers.ethernet_reset_control();
         // TODO: wait needed?
-        //clear_address_bit(address, 0);
     }
 
     pub fn reset_sdio(&self) {
-        let address = self.address_sdio_reset_control;
+        let register = self.registers.sdio_reset_control();
     }
 
     pub fn reset_spi(&self) {
-        let address = self.address_spi_reset_control;
+        let register = self.registers.spi_reset_control();
     }
 
     pub fn reset_can(&self) {
-        let address = self.address_can_reset_control;
+        let register = self.registers.can_reset_control();
     }
 
     pub fn reset_i2c(&self) {
-        let address = self.address_i2c_reset_control;
+        let register = self.registers.i2c_reset_control();
     }
     */
 
     pub fn reset_uart(&self, uart: UartDeviceIndex, amba: bool, reference: bool) {
-        let address = self.address_uart_reset_control;
-        let mut value = 0u32;
-        match uart {
-            UartDeviceIndex::Uart0 => {
-                if amba {
-                    value = value.set_bit(0);
-                }
-                if reference {
-                    value = value.set_bit(2);
-                }
-            }
-            UartDeviceIndex::Uart1 => {
-                if amba {
-                    value = value.set_bit(1);
-                }
-                if reference {
-                    value = value.set_bit(3);
-                }
-            }
-        }
-        write_to_address(address, value);
+        let register = self.registers.uart_reset_control();
+        let (amba_field, reference_field) = match uart {
+            UartDeviceIndex::Uart0 => (
+                uart_reset_control::UART0_CPU1X_RST,
+                uart_reset_control::UART0_REF_RST,
+            ),
+            UartDeviceIndex::Uart1 => (
+                uart_reset_control::UART1_CPU1X_RST,
+                uart_reset_control::UART1_REF_RST,
+            ),
+        };
+        register.write_with_zero(|r| r.set(amba_field, amba).set(reference_field, reference));
         // TODO maybe wait
-        write_to_address(address, 0);
+        register.write_with_zero(|r| r);
     }
 
     pub fn reset_gpio(&self) {
-        let address = self.address_gpio_reset_control;
-        set_address_bit(address, 0);
+        let register = self.registers.gpio_reset_control();
+        register.modify(|r| r.set(gpio_reset_control::GPIO_CPU1X_RST, true));
         // TODO maybe wait
-        clear_address_bit(address, 0);
+        register.modify(|r| r.set(gpio_reset_control::GPIO_CPU1X_RST, false));
     }
 
     pub fn reset_quad_spi(&self, amba: bool, reference: bool) {
-        let address = self.address_quad_spi_reset_control;
-        let mut value = 0u32;
-        if amba {
-            value = value.set_bit(0);
-        }
-        if reference {
-            value = value.set_bit(1);
-        }
-        write_to_address(address, value);
+        let register = self.registers.quad_spi_reset_control();
+        register.write_with_zero(|r| {
+            r.set(quad_spi_reset_control::LQSPI_CPU1X_RST, amba)
+                .set(quad_spi_reset_control::QSPI_REF_RST, reference)
+        });
         // TODO maybe wait
-        write_to_address(address, 0);
+        register.write_with_zero(|r| r);
     }
 
     pub fn reset_smc(&self, amba: bool, reference: bool) {
-        let address = self.address_smc_reset_control;
-        let mut value = 0u32;
-        if amba {
-            value = value.set_bit(0);
-        }
-        if reference {
-            value = value.set_bit(1);
-        }
-        write_to_address(address, value);
+        let register = self.registers.smc_reset_control();
+        register.write_with_zero(|r| {
+            r.set(smc_reset_control::SMC_CPU1X_RST, amba)
+                .set(smc_reset_control::SMC_REF_RST, reference)
+        });
         // TODO maybe wait
-        write_to_address(address, 0);
+        register.write_with_zero(|r| r);
     }
 
     pub fn reset_ocm(&self) {
-        let address = self.address_ocm_reset_control;
-        set_address_bit(address, 0);
+        let register = self.registers.ocm_reset_control();
+        register.modify(|r| r.set(ocm_reset_control::OCM_RST, true));
         // TODO maybe wait
-        clear_address_bit(address, 0);
+        register.modify(|r| r.set(ocm_reset_control::OCM_RST, false));
     }
 
     pub fn reset_fpga(&self, frst: Frst) {
-        let address = self.address_fpga_reset_control;
-        let index = frst.as_u32();
-        set_address_bit(address, index);
+        let register = self.registers.fpga_reset_control();
+        let field = fpga_reset_control::fpga_out_rst(frst.as_u32());
+        register.modify(|r| r.set(field, true));
         // TODO maybe wait
-        clear_address_bit(address, index);
+        register.modify(|r| r.set(field, false));
     }
 
     pub fn reset_cpu(&self, command: ResetCpuCommand) {
-        let address = self.address_cpu_reset_and_clock_control;
-        let mut value = 0u32;
-        if command.reset_cpu0 {
-            value = value.set_bit(0);
-        }
-        if command.reset_cpu1 {
-            value = value.set_bit(1);
-        }
-        if command.stop_cpu0_clock {
-            value = value.set_bit(4);
-        }
-        if command.stop_cpu1_clock {
-            value = value.set_bit(5);
-        }
-        if command.reset_cpu_peripherals {
-            value = value.set_bit(8);
-        }
-        write_to_address(address, value);
+        self.registers
+            .cpu_reset_and_clock_control()
+            .write_with_zero(|r| {
+                r.set(cpu_reset_and_clock_control::A9_RST0, command.reset_cpu0)
+                    .set(cpu_reset_and_clock_control::A9_RST1, command.reset_cpu1)
+                    .set(
+                        cpu_reset_and_clock_control::A9_CLKSTOP0,
+                        command.stop_cpu0_clock,
+                    )
+                    .set(
+                        cpu_reset_and_clock_control::A9_CLKSTOP1,
+                        command.stop_cpu1_clock,
+                    )
+                    .set(
+                        cpu_reset_and_clock_control::PERI_RST,
+                        command.reset_cpu_peripherals,
+                    )
+            });
     }
 
     pub fn set_watchdog_reset_target(&self, watchdog: WatchdogIndex, route: WatchdogResetTarget) {
-        let address = self.address_watchdog_timer_reset_control;
-        let index = watchdog.as_u32();
-        let action = if route.as_bool() {
-            set_address_bit
-        } else {
-            clear_address_bit
-        };
-        action(address, index);
+        let field = watchdog_timer_reset_control::ctrl(watchdog.as_u32());
+        self.registers
+            .watchdog_timer_reset_control()
+            .modify(|r| r.set(field, route.as_bool()));
     }
 
     pub fn reboot_status(&self) -> RebootStatus {
-        let address = self.address_reboot_status;
-        let bootrom_error_code = read_address_bits(address, 0..=15) as u16;
-        let watchdog_timeout = read_address_bit(address, 16);
-        let watchdog0 = read_address_bit(address, 17);
-        let watchdog1 = read_address_bit(address, 18);
-        let slc_soft_reset = read_address_bit(address, 19);
-        let debug_system_reset = read_address_bit(address, 20);
-        let soft_reset = read_address_bit(address, 21);
-        let power_on_reset = read_address_bit(address, 22);
-        let reboot_state = read_address_bits(address, 24..=31) as u8;
+        let value = self.registers.reboot_status().read();
         RebootStatus {
-            bootrom_error_code,
-            watchdog_timeout,
-            watchdog0,
-            watchdog1,
-            slc_soft_reset,
-            debug_system_reset,
-            soft_reset,
-            power_on_reset,
-            reboot_state,
+            bootrom_error_code: value.read(reboot_status::BOOTROM_ERROR_CODE) as u16,
+            watchdog_timeout: value.is_set(reboot_status::SWDT_RST),
+            watchdog0: value.is_set(reboot_status::AWDT0_RST),
+            watchdog1: value.is_set(reboot_status::AWDT1_RST),
+            slc_soft_reset: value.is_set(reboot_status::SLC_RST),
+            debug_system_reset: value.is_set(reboot_status::DBG_RST),
+            soft_reset: value.is_set(reboot_status::SRST_B),
+            power_on_reset: value.is_set(reboot_status::POR),
+            reboot_state: value.read(reboot_status::REBOOT_STATE) as u8,
         }
     }
 
     pub fn boot_mode(&self) -> BootMode {
-        let address = self.address_boot_mode;
-        let boot_mode = read_address_bits(address, 0..=3);
-        let pll_bypass = read_address_bit(address, 4);
+        let value = self.registers.boot_mode().read();
         BootMode {
-            boot_mode,
-            pll_bypass,
+            boot_mode: value.read(boot_mode::BOOT_MODE),
+            pll_bypass: value.is_set(boot_mode::PLL_BYPASS),
         }
     }
 }
@@ -409,101 +396,241 @@ pub enum AmbaClockControl {
 }
 
 impl AmbaClockControl {
-    pub fn as_u32(self) -> u32 {
+    /// Clock's field in AMBA peripheral clock control register.
+    #[inline]
+    #[must_use]
+    pub const fn field(self) -> Field<amba_clock_control::Spec> {
         match self {
-            Self::DmaController => 0,
-            Self::UsbController0 => 2,
-            Self::UsbController1 => 3,
-            Self::Ethernet0 => 6,
-            Self::Ethernet1 => 7,
-            Self::Sdio0 => 10,
-            Self::Sdio1 => 11,
-            Self::Spi0 => 14,
-            Self::Spi1 => 15,
-            Self::Can0 => 16,
-            Self::Can1 => 17,
-            Self::Isc0 => 18,
-            Self::Isc1 => 19,
-            Self::Uart0 => 20,
-            Self::Uart1 => 21,
-            Self::Gpio => 22,
-            Self::QuadSpi => 23,
-            Self::Smc => 24,
+            Self::DmaController => amba_clock_control::DMA_CPU_2XCLKACT,
+            Self::UsbController0 => amba_clock_control::USB0_CPU_1XCLKACT,
+            Self::UsbController1 => amba_clock_control::USB1_CPU_1XCLKACT,
+            Self::Ethernet0 => amba_clock_control::GEM0_CPU_1XCLKACT,
+            Self::Ethernet1 => amba_clock_control::GEM1_CPU_1XCLKACT,
+            Self::Sdio0 => amba_clock_control::SDI0_CPU_1XCLKACT,
+            Self::Sdio1 => amba_clock_control::SDI1_CPU_1XCLKACT,
+            Self::Spi0 => amba_clock_control::SPI0_CPU_1XCLKACT,
+            Self::Spi1 => amba_clock_control::SPI1_CPU_1XCLKACT,
+            Self::Can0 => amba_clock_control::CAN0_CPU_1XCLKACT,
+            Self::Can1 => amba_clock_control::CAN1_CPU_1XCLKACT,
+            Self::Isc0 => amba_clock_control::I2C0_CPU_1XCLKACT,
+            Self::Isc1 => amba_clock_control::I2C1_CPU_1XCLKACT,
+            Self::Uart0 => amba_clock_control::UART0_CPU_1XCLKACT,
+            Self::Uart1 => amba_clock_control::UART1_CPU_1XCLKACT,
+            Self::Gpio => amba_clock_control::GPIO_CPU_1XCLKACT,
+            Self::QuadSpi => amba_clock_control::LQSPI_CPU_1XCLKACT,
+            Self::Smc => amba_clock_control::SMC_CPU_1XCLKACT,
+        }
+    }
+}
+
+/// Phase-locked loop.
+#[derive(Clone, Copy)]
+pub enum Pll {
+    /// Clocks processor cores.
+    Arm,
+
+    /// Clocks DDR memory controller.
+    Ddr,
+
+    /// Clocks I/O peripherals.
+    Io,
+}
+
+impl core::fmt::Display for Pll {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let name = match self {
+            Self::Arm => "ARM PLL",
+            Self::Ddr => "DDR PLL",
+            Self::Io => "IO PLL",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// System level control registers error.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// System level configuration registers are locked and they can not be unlocked.
+    Locked,
+
+    /// PLL feedback divisor produces a frequency which does not fit in 32 bits.
+    InvalidFeedbackDivisor(u32),
+}
+
+impl core::fmt::Display for Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Locked => write!(f, "system level configuration registers are locked"),
+            Self::InvalidFeedbackDivisor(fdiv) => {
+                write!(f, "PLL feedback divisor {fdiv} produces too high frequency")
+            }
         }
     }
 }
 
 /// System level control registers.
-pub struct Slcr {
-    pub address_secure_configuration_lock: *mut u32,
-    pub address_write_protection_lock: *mut u32,
-    pub address_write_protection_unlock: *mut u32,
-    pub address_write_protection_status: *mut u32,
+pub struct Slcr<B = Mmio> {
+    /// System level control registers.
+    registers: SlcrRegisters<B>,
     // TODO: PLL
     // TODO: clock control
-    pub address_amba_clock_control: *mut u32,
-    // TODO:
-    pub address_uart_clock_control: *mut u32,
-    // TODO:
-    reset: Reset,
+    reset: Reset<B>,
+}
+
+impl<B: Copy> Slcr<B> {
+    /// Create interface for system level control registers using given register access.
+    #[must_use]
+    pub const fn with_bus(bus: B) -> Self {
+        Self {
+            registers: SlcrRegisters::new(ADDRESS_BASE, bus),
+            reset: Reset::with_bus(bus),
+        }
+    }
+}
+
+impl<B> Slcr<B> {
+    /// System level control registers.
+    #[inline]
+    #[must_use]
+    pub fn registers(&self) -> &SlcrRegisters<B> {
+        &self.registers
+    }
 }
 
-impl Slcr {
+impl<B: Bus> Slcr<B> {
     /// True if all writes to secure configuration registers are ignored.
     pub fn is_secure_configuration_registers_locked(&self) -> bool {
-        read_address_bit(self.address_secure_configuration_lock, 0)
+        self.registers
+            .secure_configuration_lock()
+            .read()
+            .is_set(secure_configuration_lock::LOCK)
     }
 
     /// Lock secure configuration registers.
     pub fn lock_secure_configuration_registers(&self) {
-        set_address_bit(self.address_secure_configuration_lock, 0);
+        self.registers
+            .secure_configuration_lock()
+            .modify(|r| r.set(secure_configuration_lock::LOCK, true));
     }
 
     /// Lock or unlock system level configuration registers.
     pub fn toggle_system_level_configuration_registers(&self, lock: bool) {
-        let (address, key) = if lock {
-            (self.address_write_protection_lock, 0x767B)
+        if lock {
+            self.registers
+                .write_protection_lock()
+                .write(|r| r.write(write_protection_lock::LOCK_KEY, 0x767B));
         } else {
-            (self.address_write_protection_unlock, 0xDF0D)
-        };
-        write_address_bits(address, 0..=15, key);
+            self.registers
+                .write_protection_unlock()
+                .write(|r| r.write(write_protection_unlock::UNLOCK_KEY, 0xDF0D));
+        }
     }
 
     /// True if system level configuration registers are locked.
     pub fn is_system_level_configuration_registers_locked(&self) -> bool {
-        read_address_bit(self.address_write_protection_status, 0)
+        self.registers
+            .write_protection_status()
+            .read()
+            .is_set(write_protection_status::LOCKED)
+    }
+
+    /// Unlock system level configuration registers if they are locked.
+    ///
+    /// # Errors
+    ///
+    /// - Registers are still locked after unlocking.
+    pub fn unlock_system_level_configuration_registers(&self) -> Result<(), Error> {
+        if self.is_system_level_configuration_registers_locked() {
+            self.toggle_system_level_configuration_registers(false);
+        }
+        if self.is_system_level_configuration_registers_locked() {
+            return Err(Error::Locked);
+        }
+        Ok(())
     }
 
     // TODO: PLL, clock control, etc.
 
-    pub fn toggle_amba_clocks(&self, target: AmbaClockControl, enable: bool) {
-        let index = target.as_u32();
-        let action = if enable {
-            set_address_bit
+    /// Output frequency of given PLL.
+    ///
+    /// Bypassed PLL outputs `PS_CLK` as is.
+    ///
+    /// # Errors
+    ///
+    /// - Feedback divisor produces frequency which does not fit in 32 bits.
+    pub fn pll_frequency(&self, pll: Pll) -> Result<u32, Error> {
+        // All PLL control registers share the same layout.
+        let (force, qualified, fdiv) = match pll {
+            Pll::Arm => {
+                let value = self.registers.arm_pll_control().read();
+                (
+                    value.is_set(arm_pll_control::PLL_BYPASS_FORCE),
+                    value.is_set(arm_pll_control::PLL_BYPASS_QUAL),
+                    value.read(arm_pll_control::PLL_FDIV),
+                )
+            }
+            Pll::Ddr => {
+                let value = self.registers.ddr_pll_control().read();
+                (
+                    value.is_set(ddr_pll_control::PLL_BYPASS_FORCE),
+                    value.is_set(ddr_pll_control::PLL_BYPASS_QUAL),
+                    value.read(ddr_pll_control::PLL_FDIV),
+                )
+            }
+            Pll::Io => {
+                let value = self.registers.io_pll_control().read();
+                (
+                    value.is_set(io_pll_control::PLL_BYPASS_FORCE),
+                    value.is_set(io_pll_control::PLL_BYPASS_QUAL),
+                    value.read(io_pll_control::PLL_FDIV),
+                )
+            }
+        };
+        let bypass = force || (qualified && self.reset.boot_mode().pll_bypass);
+        if bypass {
+            Ok(FREQUENCY_PS_CLK)
         } else {
-            clear_address_bit
+            FREQUENCY_PS_CLK
+                .checked_mul(fdiv)
+                .ok_or(Error::InvalidFeedbackDivisor(fdiv))
+        }
+    }
+
+    /// Frequency of UART reference clock, shared by both UARTs.
+    ///
+    /// # Errors
+    ///
+    /// - Source PLL frequency can not be solved, see [`Self::pll_frequency`].
+    pub fn uart_reference_clock_frequency(&self) -> Result<u32, Error> {
+        let value = self.registers.uart_clock_control().read();
+        let pll = match value.read(uart_clock_control::SRCSEL) {
+            0b10 => Pll::Arm,
+            0b11 => Pll::Ddr,
+            _ => Pll::Io,
         };
-        action(self.address_amba_clock_control, index);
+        // Divisor zero is not allowed, treat it as no division.
+        let divisor = value.read(uart_clock_control::DIVISOR).max(1);
+        Ok(self.pll_frequency(pll)? / divisor)
+    }
+
+    pub fn toggle_amba_clocks(&self, target: AmbaClockControl, enable: bool) {
+        self.registers
+            .amba_clock_control()
+            .modify(|r| r.set(target.field(), enable));
     }
 
     /// Enable or disable UART 0 reference clock.
     pub fn toggle_uart_0_reference_clock(&self, enable: bool) {
-        let action = if enable {
-            set_address_bit
-        } else {
-            clear_address_bit
-        };
-        action(self.address_uart_clock_control, 0);
+        self.registers
+            .uart_clock_control()
+            .modify(|r| r.set(uart_clock_control::CLKACT0, enable));
     }
 
     /// Enable or disable UART 1 reference clock.
     pub fn toggle_uart_1_reference_clock(&self, enable: bool) {
-        let action = if enable {
-            set_address_bit
-        } else {
-            clear_address_bit
-        };
-        action(self.address_uart_clock_control, 1);
+        self.registers
+            .uart_clock_control()
+            .modify(|r| r.set(uart_clock_control::CLKACT1, enable));
     }
 
     /*
@@ -515,44 +642,51 @@ impl Slcr {
     */
 
     // TODO: maybe rename better... also reset registers...
-    pub fn reset(&self) -> &Reset {
+    pub fn reset(&self) -> &Reset<B> {
         &self.reset
     }
 }
 
 const ADDRESS_BASE: u32 = 0xF800_0000;
-const ADDRESS_BASE_RESET: u32 = ADDRESS_BASE + 0x200;
-
-/// System level configuration registers.
-pub static mut SLCR: Slcr = Slcr {
-    address_secure_configuration_lock: (ADDRESS_BASE + 0x000) as *mut u32,
-    address_write_protection_lock: (ADDRESS_BASE + 0x004) as *mut u32,
-    address_write_protection_unlock: (ADDRESS_BASE + 0x008) as *mut u32,
-    address_write_protection_status: (ADDRESS_BASE + 0x00C) as *mut u32,
-    address_amba_clock_control: (ADDRESS_BASE + 0x12C) as *mut u32,
-    address_uart_clock_control: (ADDRESS_BASE + 0x154) as *mut u32,
-    reset: Reset {
-        address_ps_reset_control: (ADDRESS_BASE_RESET + 0x00) as *mut u32,
-        address_ddr_reset_control: (ADDRESS_BASE_RESET + 0x04) as *mut u32,
-        address_central_interconnect_reset_control: (ADDRESS_BASE_RESET + 0x08) as *mut u32,
-        address_dmac_reset_control: (ADDRESS_BASE_RESET + 0x0C) as *mut u32,
-        address_usb_reset_control: (ADDRESS_BASE_RESET + 0x10) as *mut u32,
-        address_ethernet_reset_control: (ADDRESS_BASE_RESET + 0x14) as *mut u32,
-        address_sdio_reset_control: (ADDRESS_BASE_RESET + 0x18) as *mut u32,
-        address_spi_reset_control: (ADDRESS_BASE_RESET + 0x1C) as *mut u32,
-        address_can_reset_control: (ADDRESS_BASE_RESET + 0x20) as *mut u32,
-        address_i2c_reset_control: (ADDRESS_BASE_RESET + 0x24) as *mut u32,
-        address_uart_reset_control: (ADDRESS_BASE_RESET + 0x28) as *mut u32,
-        address_gpio_reset_control: (ADDRESS_BASE_RESET + 0x2C) as *mut u32,
-        address_quad_spi_reset_control: (ADDRESS_BASE_RESET + 0x30) as *mut u32,
-        address_smc_reset_control: (ADDRESS_BASE_RESET + 0x34) as *mut u32,
-        address_ocm_reset_control: (ADDRESS_BASE_RESET + 0x38) as *mut u32,
-        // TODO: why offset 0x3C has no register?
-        address_fpga_reset_control: (ADDRESS_BASE_RESET + 0x40) as *mut u32,
-        address_cpu_reset_and_clock_control: (ADDRESS_BASE_RESET + 0x44) as *mut u32,
-        address_watchdog_timer_reset_control: (ADDRESS_BASE_RESET + 0x4C) as *mut u32,
-        // TODO: why these offsets have no registers?
-        address_reboot_status: (ADDRESS_BASE_RESET + 0x58) as *mut u32,
-        address_boot_mode: (ADDRESS_BASE_RESET + 0x5C) as *mut u32,
-    },
-};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::bus::SimulatedBus;
+
+    #[test]
+    fn pll_frequency_follows_feedback_divisor_and_bypass() {
+        let bus = SimulatedBus::<4, 4>::new();
+        let slcr = Slcr::with_bus(&bus);
+        let io_pll_control = 0xF800_0108 as *mut u32;
+        let boot_mode = 0xF800_025C as *mut u32;
+
+        bus.preload(io_pll_control, 30 << 12);
+        assert_eq!(slcr.pll_frequency(Pll::Io), Ok(1_500_000_000));
+
+        // Forced bypass.
+        bus.preload(io_pll_control, 30 << 12 | 1 << 4);
+        assert_eq!(slcr.pll_frequency(Pll::Io), Ok(FREQUENCY_PS_CLK));
+
+        // Qualified bypass only applies when boot mode pin requests it.
+        bus.preload(io_pll_control, 30 << 12 | 1 << 3);
+        assert_eq!(slcr.pll_frequency(Pll::Io), Ok(1_500_000_000));
+        bus.preload(boot_mode, 1 << 4);
+        assert_eq!(slcr.pll_frequency(Pll::Io), Ok(FREQUENCY_PS_CLK));
+    }
+
+    #[test]
+    fn pll_frequency_rejects_overflowing_feedback_divisor() {
+        let bus = SimulatedBus::<4, 4>::new();
+        let slcr = Slcr::with_bus(&bus);
+        let arm_pll_control = 0xF800_0100 as *mut u32;
+
+        bus.preload(arm_pll_control, 85 << 12);
+        assert_eq!(slcr.pll_frequency(Pll::Arm), Ok(4_250_000_000));
+        bus.preload(arm_pll_control, 127 << 12);
+        assert_eq!(
+            slcr.pll_frequency(Pll::Arm),
+            Err(Error::InvalidFeedbackDivisor(127))
+        );
+    }
+}
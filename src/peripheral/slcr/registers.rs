@@ -0,0 +1,262 @@
+//! System level control registers.
+
+crate::register_block! {
+    /// System level control registers.
+    pub SlcrRegisters {
+        /// Secure configuration lock register.
+        0x000 => secure_configuration_lock: ReadWrite, reset = 0x0000_0000 {
+            /// Writes to secure configuration registers are ignored, cleared only by power on reset.
+            LOCK: 0, 1;
+        }
+
+        /// Write protection lock register.
+        0x004 => write_protection_lock: WriteOnly, reset = 0x0000_0000 {
+            /// Write `0x767B` to lock.
+            LOCK_KEY: 0, 16;
+        }
+
+        /// Write protection unlock register.
+        0x008 => write_protection_unlock: WriteOnly, reset = 0x0000_0000 {
+            /// Write `0xDF0D` to unlock.
+            UNLOCK_KEY: 0, 16;
+        }
+
+        /// Write protection status register.
+        0x00C => write_protection_status: ReadOnly, reset = 0x0000_0001 {
+            /// System level control registers are locked.
+            LOCKED: 0, 1;
+        }
+
+        /// ARM PLL control register.
+        0x100 => arm_pll_control: ReadWrite, reset = 0x0001_A008 {
+            /// Hold PLL in reset.
+            PLL_RESET: 0, 1;
+            /// Power down PLL.
+            PLL_PWRDWN: 1, 1;
+            /// Bypass PLL if boot mode pin requests it.
+            PLL_BYPASS_QUAL: 3, 1;
+            /// Bypass PLL regardless of boot mode pin.
+            PLL_BYPASS_FORCE: 4, 1;
+            /// Feedback divisor, PLL output is `PS_CLK` multiplied by this.
+            PLL_FDIV: 12, 7;
+        }
+
+        /// DDR PLL control register.
+        0x104 => ddr_pll_control: ReadWrite, reset = 0x0001_A008 {
+            /// Hold PLL in reset.
+            PLL_RESET: 0, 1;
+            /// Power down PLL.
+            PLL_PWRDWN: 1, 1;
+            /// Bypass PLL if boot mode pin requests it.
+            PLL_BYPASS_QUAL: 3, 1;
+            /// Bypass PLL regardless of boot mode pin.
+            PLL_BYPASS_FORCE: 4, 1;
+            /// Feedback divisor, PLL output is `PS_CLK` multiplied by this.
+            PLL_FDIV: 12, 7;
+        }
+
+        /// IO PLL control register.
+        0x108 => io_pll_control: ReadWrite, reset = 0x0001_A008 {
+            /// Hold PLL in reset.
+            PLL_RESET: 0, 1;
+            /// Power down PLL.
+            PLL_PWRDWN: 1, 1;
+            /// Bypass PLL if boot mode pin requests it.
+            PLL_BYPASS_QUAL: 3, 1;
+            /// Bypass PLL regardless of boot mode pin.
+            PLL_BYPASS_FORCE: 4, 1;
+            /// Feedback divisor, PLL output is `PS_CLK` multiplied by this.
+            PLL_FDIV: 12, 7;
+        }
+
+        /// AMBA peripheral clock control register.
+        0x12C => amba_clock_control: ReadWrite, reset = 0x01FF_CCCD {
+            /// DMA controller clock.
+            DMA_CPU_2XCLKACT: 0, 1;
+            /// USB controller 0 clock.
+            USB0_CPU_1XCLKACT: 2, 1;
+            /// USB controller 1 clock.
+            USB1_CPU_1XCLKACT: 3, 1;
+            /// Ethernet controller 0 clock.
+            GEM0_CPU_1XCLKACT: 6, 1;
+            /// Ethernet controller 1 clock.
+            GEM1_CPU_1XCLKACT: 7, 1;
+            /// SDIO controller 0 clock.
+            SDI0_CPU_1XCLKACT: 10, 1;
+            /// SDIO controller 1 clock.
+            SDI1_CPU_1XCLKACT: 11, 1;
+            /// SPI controller 0 clock.
+            SPI0_CPU_1XCLKACT: 14, 1;
+            /// SPI controller 1 clock.
+            SPI1_CPU_1XCLKACT: 15, 1;
+            /// CAN controller 0 clock.
+            CAN0_CPU_1XCLKACT: 16, 1;
+            /// CAN controller 1 clock.
+            CAN1_CPU_1XCLKACT: 17, 1;
+            /// I2C controller 0 clock.
+            I2C0_CPU_1XCLKACT: 18, 1;
+            /// I2C controller 1 clock.
+            I2C1_CPU_1XCLKACT: 19, 1;
+            /// UART controller 0 clock.
+            UART0_CPU_1XCLKACT: 20, 1;
+            /// UART controller 1 clock.
+            UART1_CPU_1XCLKACT: 21, 1;
+            /// GPIO controller clock.
+            GPIO_CPU_1XCLKACT: 22, 1;
+            /// Quad SPI controller clock.
+            LQSPI_CPU_1XCLKACT: 23, 1;
+            /// SMC controller clock.
+            SMC_CPU_1XCLKACT: 24, 1;
+        }
+
+        /// UART reference clock control register.
+        0x154 => uart_clock_control: ReadWrite, reset = 0x0000_3F03 {
+            /// UART 0 reference clock.
+            CLKACT0: 0, 1;
+            /// UART 1 reference clock.
+            CLKACT1: 1, 1;
+            /// Reference clock source.
+            SRCSEL: 4, 2;
+            /// Reference clock divisor.
+            DIVISOR: 8, 6;
+        }
+
+        /// PS software reset control register.
+        0x200 => ps_reset_control: ReadWrite, reset = 0x0000_0000 {
+            /// Reset entire system, generates reset pulse.
+            SOFT_RST: 0, 1;
+        }
+
+        /// DDR software reset control register.
+        0x204 => ddr_reset_control: ReadWrite, reset = 0x0000_0000 {
+            /// Reset DDR controller.
+            DDR_RST: 0, 1;
+        }
+
+        /// Central interconnect reset control register.
+        0x208 => central_interconnect_reset_control: ReadWrite, reset = 0x0000_0000 {
+            /// Reset central interconnect.
+            TOPSW_RST: 0, 1;
+        }
+
+        /// DMA controller software reset control register.
+        0x20C => dmac_reset_control: ReadWrite, reset = 0x0000_0000 {
+            /// Reset DMA controller.
+            DMAC_RST: 0, 1;
+        }
+
+        /// USB software reset control register.
+        0x210 => usb_reset_control: ReadWrite, reset = 0x0000_0000 {}
+
+        /// Ethernet software reset control register.
+        0x214 => ethernet_reset_control: ReadWrite, reset = 0x0000_0000 {}
+
+        /// SDIO software reset control register.
+        0x218 => sdio_reset_control: ReadWrite, reset = 0x0000_0000 {}
+
+        /// SPI software reset control register.
+        0x21C => spi_reset_control: ReadWrite, reset = 0x0000_0000 {}
+
+        /// CAN software reset control register.
+        0x220 => can_reset_control: ReadWrite, reset = 0x0000_0000 {}
+
+        /// I2C software reset control register.
+        0x224 => i2c_reset_control: ReadWrite, reset = 0x0000_0000 {}
+
+        /// UART software reset control register.
+        0x228 => uart_reset_control: ReadWrite, reset = 0x0000_0000 {
+            /// Reset UART 0 AMBA clock domain.
+            UART0_CPU1X_RST: 0, 1;
+            /// Reset UART 1 AMBA clock domain.
+            UART1_CPU1X_RST: 1, 1;
+            /// Reset UART 0 reference clock domain.
+            UART0_REF_RST: 2, 1;
+            /// Reset UART 1 reference clock domain.
+            UART1_REF_RST: 3, 1;
+        }
+
+        /// GPIO software reset control register.
+        0x22C => gpio_reset_control: ReadWrite, reset = 0x0000_0000 {
+            /// Reset GPIO.
+            GPIO_CPU1X_RST: 0, 1;
+        }
+
+        /// Quad SPI software reset control register.
+        0x230 => quad_spi_reset_control: ReadWrite, reset = 0x0000_0000 {
+            /// Reset quad SPI AMBA clock domain.
+            LQSPI_CPU1X_RST: 0, 1;
+            /// Reset quad SPI reference clock domain.
+            QSPI_REF_RST: 1, 1;
+        }
+
+        /// SMC software reset control register.
+        0x234 => smc_reset_control: ReadWrite, reset = 0x0000_0000 {
+            /// Reset SMC AMBA clock domain.
+            SMC_CPU1X_RST: 0, 1;
+            /// Reset SMC reference clock domain.
+            SMC_REF_RST: 1, 1;
+        }
+
+        /// OCM software reset control register.
+        0x238 => ocm_reset_control: ReadWrite, reset = 0x0000_0000 {
+            /// Reset on-chip memory.
+            OCM_RST: 0, 1;
+        }
+
+        /// FPGA software reset control register.
+        0x240 => fpga_reset_control: ReadWrite, reset = 0x01F3_3F0F {
+            /// Reset FPGA, see [`Frst`](super::super::Frst) for indices.
+            fpga_out_rst[4]: 0, 1;
+        }
+
+        /// CPU reset and clock control register.
+        0x244 => cpu_reset_and_clock_control: ReadWrite, reset = 0x0000_0000 {
+            /// Reset CPU 0.
+            A9_RST0: 0, 1;
+            /// Reset CPU 1.
+            A9_RST1: 1, 1;
+            /// Stop CPU 0 clock.
+            A9_CLKSTOP0: 4, 1;
+            /// Stop CPU 1 clock.
+            A9_CLKSTOP1: 5, 1;
+            /// Reset CPU peripherals.
+            PERI_RST: 8, 1;
+        }
+
+        /// Watchdog timer reset control register.
+        0x24C => watchdog_timer_reset_control: ReadWrite, reset = 0x0000_0000 {
+            /// Watchdog resets only its CPU, see [`WatchdogIndex`](super::super::WatchdogIndex) for indices.
+            ctrl[2]: 0, 1;
+        }
+
+        /// Reboot status register.
+        0x258 => reboot_status: ReadWrite, reset = 0x0040_0000 {
+            /// Error code written by BootROM.
+            BOOTROM_ERROR_CODE: 0, 16;
+            /// Last reset was caused by system watchdog timeout.
+            SWDT_RST: 16, 1;
+            /// Last reset was caused by watchdog timer 0.
+            AWDT0_RST: 17, 1;
+            /// Last reset was caused by watchdog timer 1.
+            AWDT1_RST: 18, 1;
+            /// Last reset was caused by system level control register.
+            SLC_RST: 19, 1;
+            /// Last reset was caused by debug system.
+            DBG_RST: 20, 1;
+            /// Last reset was soft reset.
+            SRST_B: 21, 1;
+            /// Last reset was power on reset.
+            POR: 22, 1;
+            /// Data that persists through all resets except power on reset.
+            REBOOT_STATE: 24, 8;
+        }
+
+        /// Boot mode strapping pins register.
+        0x25C => boot_mode: ReadOnly, reset = 0x0000_0000 {
+            /// Boot mode pins.
+            BOOT_MODE: 0, 4;
+            /// PLLs are bypassed.
+            PLL_BYPASS: 4, 1;
+        }
+    }
+}
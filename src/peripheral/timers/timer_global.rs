@@ -3,31 +3,33 @@
 //! # How to use?
 //!
 //! ```ignore
-//! TIMER_GLOBAL.toggle(false);
-//! TIMER_GLOBAL.clear_interrupt();
-//! TIMER_GLOBAL.set_mode(TimerModeGlobal::AutoReload);
-//! TIMER_GLOBAL.set_prescaler(0);
-//! TIMER_GLOBAL.set_count(CounterValue { upper: 0, lower: 0 });
-//! TIMER_GLOBAL.toggle_interrupt(false);
-//! TIMER_GLOBAL.toggle_comparator(false);
-//! COMPARATOR.set_comparator_value(CounterValue {
+//! let timer = peripherals.timer_global;
+//! let comparator = peripherals.comparator;
+//! timer.toggle(false);
+//! timer.clear_interrupt();
+//! timer.set_mode(TimerModeGlobal::AutoReload);
+//! timer.set_prescaler(0);
+//! timer.set_count(CounterValue { upper: 0, lower: 0 });
+//! timer.toggle_interrupt(false);
+//! timer.toggle_comparator(false);
+//! comparator.set_comparator_value(CounterValue {
 //!     upper: 0,
 //!     lower: 10_000_000,
 //! });
-//! COMPARATOR.set_auto_increment_value(10_000_000);
-//! TIMER_GLOBAL.toggle_comparator(true);
-//! TIMER_GLOBAL.toggle(true);
+//! comparator.set_auto_increment_value(10_000_000);
+//! timer.toggle_comparator(true);
+//! timer.toggle(true);
 //! ```
 
-use core::ops::Not;
+pub mod registers;
 
-use crate::common::memman::clear_address_bit;
-use crate::common::memman::read_address_bit;
-use crate::common::memman::read_address_bits;
-use crate::common::memman::read_from_address;
-use crate::common::memman::set_address_bit;
-use crate::common::memman::write_address_bits;
-use crate::common::memman::write_to_address;
+use crate::common::bus::Bus;
+use crate::common::bus::Mmio;
+use crate::common::snapshot::Snapshot;
+use core::ops::Not;
+use registers::control;
+use registers::interrupt_status;
+use registers::GlobalTimerRegisters;
 
 /// PYNQ-Z1 provides 50 MHz clock to Zynq's PS_CLK input.
 /// This enables the processor to operate at maximum frequency of 650 MHz.
@@ -35,6 +37,25 @@ use crate::common::memman::write_to_address;
 /// Thus increments per µsecond := 325 MHz / 1_000_000 = 325.
 const INCREMENTS_PER_USECOND: u32 = 325;
 
+/// Global timer error.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// Timer is disabled.
+    TimerDisabled,
+
+    /// Registers do not hold reset values after reset.
+    ResetFailed,
+}
+
+impl core::fmt::Display for Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::TimerDisabled => write!(f, "global timer is disabled"),
+            Self::ResetFailed => write!(f, "global timer registers were not reset"),
+        }
+    }
+}
+
 /// Global timer mode.
 #[derive(Clone, Copy)]
 pub enum TimerMode {
@@ -66,7 +87,7 @@ impl TimerMode {
 }
 
 /// 64-bit counter's value.
-#[derive(Clone, Copy, Eq)]
+#[derive(Clone, Copy, Debug, Eq)]
 pub struct CounterValue {
     /// Lower 32 bits of counter's value.
     pub upper: u32,
@@ -136,21 +157,29 @@ impl PartialOrd for CounterValue {
 */
 
 /// Interface to global timer.
-pub struct TimerGlobal {
-    /// Lower 32 bits of counter's 64-bit value.
-    address_counter_0: *mut u32,
-
-    /// Upper 32 bits of counter's 64-bit value.
-    address_counter_1: *mut u32,
+pub struct TimerGlobal<B = Mmio> {
+    /// Global timer registers.
+    registers: GlobalTimerRegisters<B>,
+}
 
-    /// Global timer control register.
-    address_control: *mut u32,
+impl<B> TimerGlobal<B> {
+    /// Create interface for global timer using given register access.
+    #[must_use]
+    pub const fn with_bus(bus: B) -> Self {
+        Self {
+            registers: GlobalTimerRegisters::new(ADDRESS_BASE, bus),
+        }
+    }
 
-    /// Global timer interrupt status register.
-    address_interrupt_status: *mut u32,
+    /// Global timer registers.
+    #[inline]
+    #[must_use]
+    pub fn registers(&self) -> &GlobalTimerRegisters<B> {
+        &self.registers
+    }
 }
 
-impl TimerGlobal {
+impl<B: Bus> TimerGlobal<B> {
     /// Set counter's value.
     #[inline]
     pub fn set_count(&self, value: CounterValue) {
@@ -158,8 +187,8 @@ impl TimerGlobal {
         if interrupt_enabled {
             self.toggle_interrupt(false);
         }
-        write_to_address(self.address_counter_0, value.lower);
-        write_to_address(self.address_counter_1, value.upper);
+        self.registers.counter_lower().write_bits(value.lower);
+        self.registers.counter_upper().write_bits(value.upper);
         if interrupt_enabled {
             self.toggle_interrupt(true);
         }
@@ -169,16 +198,16 @@ impl TimerGlobal {
     #[inline]
     #[must_use]
     pub fn get_count(&self) -> CounterValue {
-        let upper_old = read_from_address(self.address_counter_1);
-        let lower_old = read_from_address(self.address_counter_0);
-        let upper_new = read_from_address(self.address_counter_1);
+        let upper_old = self.registers.counter_upper().read().bits();
+        let lower_old = self.registers.counter_lower().read().bits();
+        let upper_new = self.registers.counter_upper().read().bits();
         if upper_old == upper_new {
             CounterValue {
                 upper: upper_old,
                 lower: lower_old,
             }
         } else {
-            let lower_new = read_from_address(self.address_counter_0);
+            let lower_new = self.registers.counter_lower().read().bits();
             CounterValue {
                 upper: upper_new,
                 lower: lower_new,
@@ -189,108 +218,115 @@ impl TimerGlobal {
     /// Set timer's clock prescaler.
     #[inline]
     pub fn set_prescaler(&self, value: u8) {
-        write_address_bits(self.address_control, 8..=15, value as u32);
+        self.registers
+            .control()
+            .modify(|r| r.write(control::PRESCALER, value as u32));
     }
 
     /// Get timer's clock prescaler.
     pub fn get_prescaler(&self) -> u8 {
-        let value = read_address_bits(self.address_control, 8..=15);
+        let value = self.registers.control().read().read(control::PRESCALER);
         value as u8
     }
 
     /// Set counter mode.
     #[inline]
     pub fn set_mode(&self, mode: TimerMode) {
-        let action = if mode.as_bool() {
-            set_address_bit
-        } else {
-            clear_address_bit
-        };
-        action(self.address_control, 3);
+        self.registers
+            .control()
+            .modify(|r| r.set(control::AUTO_INCREMENT, mode.as_bool()));
     }
 
     /// Get counter mode.
     pub fn get_mode(&self) -> TimerMode {
-        let value = read_address_bit(self.address_control, 3);
+        let value = self
+            .registers
+            .control()
+            .read()
+            .is_set(control::AUTO_INCREMENT);
         TimerMode::from_bool(value)
     }
 
     /// True if interrupt is enabled.
     pub fn is_interrupt_enabled(&self) -> bool {
-        read_address_bit(self.address_control, 2)
+        self.registers.control().read().is_set(control::IRQ_ENABLE)
     }
 
     /// Enable or disable timer interrupt.
     #[inline]
     pub fn toggle_interrupt(&self, enable: bool) {
-        let action = if enable {
-            set_address_bit
-        } else {
-            clear_address_bit
-        };
-        action(self.address_control, 2);
+        self.registers
+            .control()
+            .modify(|r| r.set(control::IRQ_ENABLE, enable));
     }
 
     /// True if comparator is enabled.
     pub fn is_comparator_enabled(&self) -> bool {
-        read_address_bit(self.address_control, 1)
+        self.registers.control().read().is_set(control::COMP_ENABLE)
     }
 
     /// Enable or disable comparison of counter's value with comparator's value.
     #[inline]
     pub fn toggle_comparator(&self, enable: bool) {
-        let action = if enable {
-            set_address_bit
-        } else {
-            clear_address_bit
-        };
-        action(self.address_control, 1);
+        self.registers
+            .control()
+            .modify(|r| r.set(control::COMP_ENABLE, enable));
     }
 
     /// True if timer is enabled.
     pub fn is_enabled(&self) -> bool {
-        read_address_bit(self.address_control, 0)
+        self.registers
+            .control()
+            .read()
+            .is_set(control::TIMER_ENABLE)
     }
 
     /// Enable or disable timer.
     #[inline]
     pub fn toggle(&self, enable: bool) {
-        let action = if enable {
-            set_address_bit
-        } else {
-            clear_address_bit
-        };
-        action(self.address_control, 0);
+        self.registers
+            .control()
+            .modify(|r| r.set(control::TIMER_ENABLE, enable));
     }
 
     /// True if counter has reached comparator's value.
     pub fn interrupt_status(&self) -> bool {
-        read_address_bit(self.address_interrupt_status, 0)
+        self.registers
+            .interrupt_status()
+            .read()
+            .is_set(interrupt_status::EVENT_FLAG)
     }
 
     /// Clear timer interrupt.
     #[inline]
     pub fn clear_interrupt(&self) {
-        set_address_bit(self.address_interrupt_status, 0);
+        // Writing low has no effect.
+        self.registers
+            .interrupt_status()
+            .write_with_zero(|r| r.set(interrupt_status::EVENT_FLAG, true));
     }
 
     /// Reset peripheral.
-    pub fn reset(&self) -> Result<(), ()> {
-        write_to_address(self.address_control, 0);
-        write_to_address(self.address_counter_0, 0);
-        write_to_address(self.address_counter_1, 0);
-        write_to_address(self.address_interrupt_status, 0xFFFF_FFFF);
-        if read_from_address(self.address_control) != 0 {
-            return Err(());
+    ///
+    /// # Errors
+    ///
+    /// - Registers do not hold reset values after reset.
+    pub fn reset(&self) -> Result<(), Error> {
+        self.registers.control().reset();
+        self.registers.counter_lower().reset();
+        self.registers.counter_upper().reset();
+        self.registers.interrupt_status().write_bits(0xFFFF_FFFF);
+        if self.registers.control().read().bits() != 0 {
+            return Err(Error::ResetFailed);
         }
-        if read_from_address(self.address_counter_0) != 0 {
-            return Err(());
+        if self.registers.counter_lower().read().bits() != 0 {
+            return Err(Error::ResetFailed);
         }
-        if read_from_address(self.address_counter_1) != 0 {
-            return Err(());
+        if self.registers.counter_upper().read().bits() != 0 {
+            return Err(Error::ResetFailed);
         }
-        if read_from_address(self.address_interrupt_status) != 0 {
-            return Err(());
+        if self.registers.interrupt_status().read().bits() != 0 {
+            return Err(Error::ResetFailed);
         }
         Ok(())
     }
@@ -298,9 +334,13 @@ impl TimerGlobal {
     /// Sleep given microseconds.
     ///
     /// This function blocks.
-    pub fn usleep(&self, useconds: u32) -> Result<(), ()> {
+    ///
+    /// # Errors
+    ///
+    /// - Timer is disabled.
+    pub fn usleep(&self, useconds: u32) -> Result<(), Error> {
         if self.is_enabled().not() {
-            return Err(());
+            return Err(Error::TimerDisabled);
         }
         // TODO: maybe also check if comparator is enabled?
         let mut count_now = self.get_count();
@@ -317,46 +357,114 @@ impl TimerGlobal {
     }
 }
 
-pub struct Comparator {
-    address_comparator_lower: *mut u32,
-    address_comparator_upper: *mut u32,
-    address_auto_increment: *mut u32,
+pub struct Comparator<B = Mmio> {
+    /// Global timer registers.
+    registers: GlobalTimerRegisters<B>,
+}
+
+impl<B> Comparator<B> {
+    /// Create interface for global timer comparator using given register access.
+    #[must_use]
+    pub const fn with_bus(bus: B) -> Self {
+        Self {
+            registers: GlobalTimerRegisters::new(ADDRESS_BASE, bus),
+        }
+    }
 }
 
-impl Comparator {
+impl<B: Bus> Comparator<B> {
     pub fn get_comparator_value(&self) -> CounterValue {
-        let lower = read_from_address(self.address_comparator_lower);
-        let upper = read_from_address(self.address_comparator_upper);
+        let lower = self.registers.comparator_lower().read().bits();
+        let upper = self.registers.comparator_upper().read().bits();
         CounterValue { lower, upper }
     }
 
     pub fn set_comparator_value(&self, value: CounterValue) {
-        write_to_address(self.address_comparator_lower, value.lower);
-        write_to_address(self.address_comparator_upper, value.upper);
+        self.registers.comparator_lower().write_bits(value.lower);
+        self.registers.comparator_upper().write_bits(value.upper);
     }
 
     pub fn get_auto_increment_value(&self) -> u32 {
-        read_from_address(self.address_auto_increment)
+        self.registers.auto_increment().read().bits()
     }
 
     pub fn set_auto_increment_value(&self, value: u32) {
-        write_to_address(self.address_auto_increment, value);
+        self.registers.auto_increment().write_bits(value);
+    }
+}
+
+/// Saved global timer configuration.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TimerGlobalState {
+    control: u32,
+}
+
+impl<B: Bus> Snapshot for TimerGlobal<B> {
+    type State = TimerGlobalState;
+
+    fn save(&self) -> TimerGlobalState {
+        TimerGlobalState {
+            control: self.registers.control().read().bits(),
+        }
+    }
+
+    /// Restore global timer configuration.
+    ///
+    /// Counter is a time base shared by both processor cores, so it is not restored.
+    fn restore(&self, state: &TimerGlobalState) {
+        self.registers.control().write_bits(state.control);
+    }
+}
+
+/// Saved global timer comparator configuration.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ComparatorState {
+    comparator: CounterValue,
+    auto_increment: u32,
+}
+
+impl<B: Bus> Snapshot for Comparator<B> {
+    type State = ComparatorState;
+
+    fn save(&self) -> ComparatorState {
+        ComparatorState {
+            comparator: self.get_comparator_value(),
+            auto_increment: self.get_auto_increment_value(),
+        }
+    }
+
+    fn restore(&self, state: &ComparatorState) {
+        self.set_comparator_value(state.comparator);
+        self.set_auto_increment_value(state.auto_increment);
     }
 }
 
 /// Application processing unit's base address.
 const ADDRESS_BASE: u32 = 0xF8F0_0000;
 
-/// Global timer.
-pub static mut TIMER_GLOBAL: TimerGlobal = TimerGlobal {
-    address_counter_0: (ADDRESS_BASE + 0x200) as *mut u32,
-    address_counter_1: (ADDRESS_BASE + 0x204) as *mut u32,
-    address_control: (ADDRESS_BASE + 0x208) as *mut u32,
-    address_interrupt_status: (ADDRESS_BASE + 0x20C) as *mut u32,
-};
-
-pub static mut COMPARATOR: Comparator = Comparator {
-    address_comparator_lower: (ADDRESS_BASE + 0x210) as *mut u32,
-    address_comparator_upper: (ADDRESS_BASE + 0x214) as *mut u32,
-    address_auto_increment: (ADDRESS_BASE + 0x218) as *mut u32,
-};
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::bus::SimulatedBus;
+
+    #[test]
+    fn restore_writes_saved_configuration_back() {
+        let bus = SimulatedBus::<8, 16>::new();
+        let timer = TimerGlobal::with_bus(&bus);
+        let comparator = Comparator::with_bus(&bus);
+        timer.set_prescaler(3);
+        comparator.set_comparator_value(CounterValue { upper: 1, lower: 2 });
+        comparator.set_auto_increment_value(3);
+
+        let saved_timer = timer.save();
+        let saved_comparator = comparator.save();
+        timer.set_prescaler(4);
+        timer.toggle_comparator(true);
+        comparator.set_comparator_value(CounterValue { upper: 5, lower: 6 });
+        comparator.set_auto_increment_value(7);
+        timer.restore(&saved_timer);
+        comparator.restore(&saved_comparator);
+        assert_eq!(timer.save(), saved_timer);
+        assert_eq!(comparator.save(), saved_comparator);
+    }
+}
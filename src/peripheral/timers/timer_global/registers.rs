@@ -0,0 +1,41 @@
+//! Global timer registers.
+
+crate::register_block! {
+    /// Global timer registers.
+    pub GlobalTimerRegisters {
+        /// Lower 32 bits of counter's 64-bit value.
+        0x200 => counter_lower: ReadWrite, reset = 0x0000_0000 {}
+
+        /// Upper 32 bits of counter's 64-bit value.
+        0x204 => counter_upper: ReadWrite, reset = 0x0000_0000 {}
+
+        /// Global timer control register.
+        0x208 => control: ReadWrite, reset = 0x0000_0000 {
+            /// Enable timer.
+            TIMER_ENABLE: 0, 1;
+            /// Enable comparison of counter's value with comparator's value.
+            COMP_ENABLE: 1, 1;
+            /// Enable timer interrupt.
+            IRQ_ENABLE: 2, 1;
+            /// Auto-increment comparator.
+            AUTO_INCREMENT: 3, 1;
+            /// Clock prescaler.
+            PRESCALER: 8, 8;
+        }
+
+        /// Global timer interrupt status register.
+        0x20C => interrupt_status: ReadWrite, reset = 0x0000_0000 {
+            /// Counter has reached comparator's value, write high to clear.
+            EVENT_FLAG: 0, 1;
+        }
+
+        /// Lower 32 bits of comparator's 64-bit value.
+        0x210 => comparator_lower: ReadWrite, reset = 0x0000_0000 {}
+
+        /// Upper 32 bits of comparator's 64-bit value.
+        0x214 => comparator_upper: ReadWrite, reset = 0x0000_0000 {}
+
+        /// Comparator auto-increment register.
+        0x218 => auto_increment: ReadWrite, reset = 0x0000_0000 {}
+    }
+}
@@ -1,10 +1,13 @@
 //! Processor core's private timer.
 
-use crate::common::memman::clear_address_bit;
-use crate::common::memman::read_from_address;
-use crate::common::memman::set_address_bit;
-use crate::common::memman::write_address_bits;
-use crate::common::memman::write_to_address;
+pub mod registers;
+
+use crate::common::bus::Bus;
+use crate::common::bus::Mmio;
+use crate::common::snapshot::Snapshot;
+use registers::control;
+use registers::interrupt_status;
+use registers::PrivateTimerRegisters;
 
 /// Application processing unit's base address.
 const ADDRESS_BASE: u32 = 0xF8F0_0000;
@@ -38,93 +41,99 @@ impl TimerMode {
 }
 
 /// Interface to private timer.
-pub struct TimerPrivate {
-    /// Contains the value copied to counter register when it decrements down to zero with auto reload mode enabled.
-    address_load: *mut u32,
-
-    /// Timer's current count.
+pub struct TimerPrivate<B = Mmio> {
+    /// Private timer registers.
     ///
-    /// If timer is enabled, then value is decremented.
-    /// If value reaches zero and timer interrupt is enabled, then timer interrupt status event flag is set.
-    address_counter: *mut u32,
+    /// - Load register contains the value copied to counter register when it decrements down to zero with auto reload mode enabled.
+    /// - Counter register contains timer's current count.
+    ///   If timer is enabled, then value is decremented.
+    ///   If value reaches zero and timer interrupt is enabled, then timer interrupt status event flag is set.
+    registers: PrivateTimerRegisters<B>,
+}
 
-    /// Private timer control register.
-    address_control: *mut u32,
+impl<B> TimerPrivate<B> {
+    /// Create interface for private timer using given register access.
+    #[must_use]
+    pub const fn with_bus(bus: B) -> Self {
+        Self {
+            registers: PrivateTimerRegisters::new(ADDRESS_BASE, bus),
+        }
+    }
 
-    /// Private timer interrupt status register.
-    address_interrupt_status: *mut u32,
+    /// Private timer registers.
+    #[inline]
+    #[must_use]
+    pub fn registers(&self) -> &PrivateTimerRegisters<B> {
+        &self.registers
+    }
 }
 
-impl TimerPrivate {
+impl<B: Bus> TimerPrivate<B> {
     /// Set counter's start value.
     #[inline]
     pub fn set_load(&self, value: u32) {
-        write_to_address(self.address_load, value);
+        self.registers.load().write_bits(value);
     }
 
     /// Get counter's start value.
     #[inline]
     #[must_use]
     pub fn get_load(&self) -> u32 {
-        read_from_address(self.address_load)
+        self.registers.load().read().bits()
     }
 
     /// Set counter's current value.
     #[inline]
     pub fn set_count(&self, value: u32) {
-        write_to_address(self.address_counter, value);
+        self.registers.counter().write_bits(value);
     }
 
     /// Get counter's current value.
     #[inline]
     #[must_use]
     pub fn get_count(&self) -> u32 {
-        read_from_address(self.address_counter)
+        self.registers.counter().read().bits()
     }
 
     /// Configure timer's clock prescaler.
     #[inline]
     pub fn set_prescaler(&self, value: u8) {
-        write_address_bits(self.address_control, 8..=15, value as u32);
+        self.registers
+            .control()
+            .modify(|r| r.write(control::PRESCALER, value as u32));
     }
 
     /// Enable or disable timer interrput.
     #[inline]
     pub fn toggle_interrupt(&self, enable: bool) {
-        let action = if enable {
-            set_address_bit
-        } else {
-            clear_address_bit
-        };
-        action(self.address_control, 2);
+        self.registers
+            .control()
+            .modify(|r| r.set(control::IRQ_ENABLE, enable));
     }
 
     /// Configure timer mode.
     #[inline]
     pub fn set_mode(&self, mode: TimerMode) {
-        let action = if mode.as_bool() {
-            set_address_bit
-        } else {
-            clear_address_bit
-        };
-        action(self.address_control, 1);
+        self.registers
+            .control()
+            .modify(|r| r.set(control::AUTO_RELOAD, mode.as_bool()));
     }
 
     /// Enable or disable timer.
     #[inline]
     pub fn toggle(&self, enable: bool) {
-        let action = if enable {
-            set_address_bit
-        } else {
-            clear_address_bit
-        };
-        action(self.address_control, 0);
+        self.registers
+            .control()
+            .modify(|r| r.set(control::TIMER_ENABLE, enable));
     }
 
     /// Clear timer interrupt.
     #[inline]
     pub fn clear_interrupt(&self) {
-        set_address_bit(self.address_interrupt_status, 0);
+        // Writing low has no effect.
+        self.registers
+            .interrupt_status()
+            .write_with_zero(|r| r.set(interrupt_status::EVENT_FLAG, true));
     }
 
     pub fn usleep(&self, useconds: u32) {
@@ -169,10 +178,56 @@ impl TimerPrivate {
     }
 }
 
-/// Private timer.
-pub static mut TIMER_PRIVATE: TimerPrivate = TimerPrivate {
-    address_load: (ADDRESS_BASE + 0x600) as *mut u32,
-    address_counter: (ADDRESS_BASE + 0x604) as *mut u32,
-    address_control: (ADDRESS_BASE + 0x608) as *mut u32,
-    address_interrupt_status: (ADDRESS_BASE + 0x60C) as *mut u32,
-};
+/// Saved private timer configuration.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TimerPrivateState {
+    load: u32,
+    counter: u32,
+    control: u32,
+}
+
+impl<B: Bus> Snapshot for TimerPrivate<B> {
+    type State = TimerPrivateState;
+
+    fn save(&self) -> TimerPrivateState {
+        TimerPrivateState {
+            load: self.registers.load().read().bits(),
+            counter: self.registers.counter().read().bits(),
+            control: self.registers.control().read().bits(),
+        }
+    }
+
+    /// Restore private timer configuration.
+    ///
+    /// Counter continues from its saved value.
+    fn restore(&self, state: &TimerPrivateState) {
+        self.registers
+            .control()
+            .modify(|r| r.set(control::TIMER_ENABLE, false));
+        self.registers.load().write_bits(state.load);
+        self.registers.counter().write_bits(state.counter);
+        self.registers.control().write_bits(state.control);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::bus::SimulatedBus;
+
+    #[test]
+    fn restore_writes_saved_configuration_back() {
+        let bus = SimulatedBus::<4, 16>::new();
+        let timer = TimerPrivate::with_bus(&bus);
+        timer.set_load(100);
+        timer.set_count(50);
+        timer.set_prescaler(3);
+
+        let saved = timer.save();
+        timer.set_load(200);
+        timer.set_count(150);
+        timer.toggle_interrupt(true);
+        timer.restore(&saved);
+        assert_eq!(timer.save(), saved);
+    }
+}
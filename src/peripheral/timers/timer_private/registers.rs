@@ -0,0 +1,30 @@
+//! Private timer registers.
+
+crate::register_block! {
+    /// Private timer registers.
+    pub PrivateTimerRegisters {
+        /// Private timer load register.
+        0x600 => load: ReadWrite, reset = 0x0000_0000 {}
+
+        /// Private timer counter register.
+        0x604 => counter: ReadWrite, reset = 0x0000_0000 {}
+
+        /// Private timer control register.
+        0x608 => control: ReadWrite, reset = 0x0000_0000 {
+            /// Enable timer.
+            TIMER_ENABLE: 0, 1;
+            /// Reload counter when it reaches zero.
+            AUTO_RELOAD: 1, 1;
+            /// Enable timer interrupt.
+            IRQ_ENABLE: 2, 1;
+            /// Clock prescaler.
+            PRESCALER: 8, 8;
+        }
+
+        /// Private timer interrupt status register.
+        0x60C => interrupt_status: ReadWrite, reset = 0x0000_0000 {
+            /// Counter has reached zero, write high to clear.
+            EVENT_FLAG: 0, 1;
+        }
+    }
+}
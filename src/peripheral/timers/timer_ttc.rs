@@ -1,15 +1,25 @@
 //! Triple timer counters.
 
+pub mod registers;
+
 use core::ops::Not;
 
-use crate::common::bitman::ReadBitwise;
-use crate::common::memman::clear_address_bit;
-use crate::common::memman::read_address_bit;
-use crate::common::memman::read_address_bits;
-use crate::common::memman::read_from_address;
-use crate::common::memman::set_address_bit;
-use crate::common::memman::write_address_bits;
-use crate::common::memman::write_to_address;
+use crate::common::bus::Bus;
+use crate::common::bus::Mmio;
+use crate::common::register::Value;
+use crate::common::snapshot::Snapshot;
+use registers::clock_control;
+use registers::counter_control;
+use registers::counter_value;
+use registers::event;
+use registers::event_control;
+use registers::interrupt_enable;
+use registers::interrupt_status;
+use registers::interval;
+use registers::match_1;
+use registers::match_2;
+use registers::match_3;
+use registers::TtcRegisters;
 
 // TODO: is this correct value?
 //const CHANGES_PER_USECOND: u32 = 325;
@@ -55,16 +65,29 @@ impl Parameters {
     pub fn useconds_per_interval(&self) -> Option<u32> {
         use crate::common::timing::FREQUENCY_PERIPHERALS;
 
-        let prescaler: u32 = self.prescaler.try_into().unwrap();
+        let prescaler = u32::from(self.prescaler);
         let frequency_scaler = 2u32.pow(prescaler + 1u32);
         let ticks_per_second = FREQUENCY_PERIPHERALS / frequency_scaler;
         let ticks_per_usecond = ticks_per_second / 1_000_000;
-        if ticks_per_usecond == 0 {
-            None
-        } else {
-            let ticks_per_interval: u32 = self.interval_value.try_into().unwrap();
-            let useconds_per_interval = ticks_per_interval / ticks_per_usecond;
-            Some(useconds_per_interval)
+        let ticks_per_interval = u32::from(self.interval_value);
+        ticks_per_interval.checked_div(ticks_per_usecond)
+    }
+}
+
+/// Triple timer counter error.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// Requested interval can not be reached with any prescaler and interval value.
+    IntervalOutOfRange(u32),
+}
+
+impl core::fmt::Display for Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::IntervalOutOfRange(useconds) => write!(
+                f,
+                "could not solve prescaler and interval value to reach {useconds} µseconds per interval"
+            ),
         }
     }
 }
@@ -320,291 +343,319 @@ pub struct InterruptStatus {
 
 impl InterruptStatus {
     pub fn new(value: u32) -> Self {
-        // TODO: check that value is 6 bits
+        let value = Value::<interrupt_status::Spec>::from_bits(value);
         Self {
-            interval_interrupt: value.read_bit(0),
-            match_1_interrupt: value.read_bit(1),
-            match_2_interrupt: value.read_bit(2),
-            match_3_interrupt: value.read_bit(3),
-            counter_overflow: value.read_bit(4),
-            event_timer_overflow_interrupt: value.read_bit(5),
+            interval_interrupt: value.is_set(interrupt_status::IV),
+            match_1_interrupt: value.is_set(interrupt_status::M1),
+            match_2_interrupt: value.is_set(interrupt_status::M2),
+            match_3_interrupt: value.is_set(interrupt_status::M3),
+            counter_overflow: value.is_set(interrupt_status::OV),
+            event_timer_overflow_interrupt: value.is_set(interrupt_status::EV),
         }
     }
 }
 
 /// Interface for TTC peripheral.
-pub struct TTCTimer {
-    /// Clock control register.
-    pub address_clock_control: *mut u32,
-
-    /// Counter control register.
-    pub address_counter_control: *mut u32,
-
-    /// Counter's value.
-    pub address_counter_value: *mut u32,
-
-    /// Interval's value.
-    pub address_interval_value: *mut u32,
-
-    /// Match 0's value.
-    pub address_match_value_0: *mut u32,
-
-    /// Match 1's value.
-    pub address_match_value_1: *mut u32,
-
-    /// Match 2's value.
-    pub address_match_value_2: *mut u32,
+pub struct TTCTimer<B = Mmio> {
+    /// Triple timer counter registers.
+    registers: TtcRegisters<B>,
 
-    /// Interrupt status register.
-    pub address_interrupt_status: *mut u32,
+    /// Which timer/clock of the triple timer counter.
+    index: u32,
 
-    /// Interrupt enable register.
-    pub address_interrupt_enable: *mut u32,
+    /// True if timer is waiting for interrupt.
+    pub sleeping: core::sync::atomic::AtomicBool,
+}
 
-    /// Event control register.
-    pub address_event_control_timer: *mut u32,
+impl<B> TTCTimer<B> {
+    /// Create interface for timer/clock of TTC peripheral using given register access.
+    ///
+    /// Registers of three timers/clocks are interleaved, `index` selects one of them.
+    #[must_use]
+    pub const fn with_bus(address_base: u32, index: u32, bus: B) -> Self {
+        Self {
+            registers: TtcRegisters::new(address_base, bus),
+            index,
+            sleeping: core::sync::atomic::AtomicBool::new(false),
+        }
+    }
 
-    /// Clock cycle count.
-    pub address_event: *mut u32,
+    /// Triple timer counter registers.
+    #[inline]
+    #[must_use]
+    pub fn registers(&self) -> &TtcRegisters<B> {
+        &self.registers
+    }
 
-    /// True if timer is waiting for interrupt.
-    pub sleeping: core::sync::atomic::AtomicBool,
+    /// Which timer/clock of the triple timer counter.
+    #[inline]
+    #[must_use]
+    pub const fn index(&self) -> u32 {
+        self.index
+    }
 }
 
-impl TTCTimer {
+impl<B: Bus> TTCTimer<B> {
     /// Enable or disable prescaler.
     pub fn toggle_prescaler(&self, enable: bool) {
-        let action = if enable {
-            set_address_bit
-        } else {
-            clear_address_bit
-        };
-        action(self.address_clock_control, 0);
+        self.registers
+            .clock_control(self.index)
+            .modify(|r| r.set(clock_control::PS_EN, enable));
     }
 
     #[must_use]
     pub fn prescaler_enabled(&self) -> bool {
-        read_address_bit(self.address_clock_control, 0)
+        self.registers
+            .clock_control(self.index)
+            .read()
+            .is_set(clock_control::PS_EN)
     }
 
-    // TODO: prescaler is 4 bits
     pub fn set_prescaler(&self, value: u8) {
-        write_address_bits(self.address_clock_control, 1..=4, value as u32)
+        self.registers
+            .clock_control(self.index)
+            .modify(|r| r.write(clock_control::PS_V, value as u32));
     }
 
-    // TODO: prescaler is 4 bits
     #[must_use]
     pub fn get_prescaler(&self) -> u8 {
-        read_address_bits(self.address_clock_control, 1..=4) as u8
+        self.registers
+            .clock_control(self.index)
+            .read()
+            .read(clock_control::PS_V) as u8
     }
 
     pub fn set_clock_source(&self, source: ClockSource) {
-        let action = if source.as_bool() {
-            set_address_bit
-        } else {
-            clear_address_bit
-        };
-        action(self.address_clock_control, 5);
+        self.registers
+            .clock_control(self.index)
+            .modify(|r| r.set(clock_control::C_SRC, source.as_bool()));
     }
 
     #[must_use]
     pub fn get_clock_source(&self) -> ClockSource {
-        let value = read_address_bit(self.address_clock_control, 5);
+        let value = self
+            .registers
+            .clock_control(self.index)
+            .read()
+            .is_set(clock_control::C_SRC);
         ClockSource::from_bool(value)
     }
 
     pub fn set_external_clock_edge(&self, edge: ExternalClockEdge) {
-        let action = if edge.as_bool() {
-            set_address_bit
-        } else {
-            clear_address_bit
-        };
-        action(self.address_clock_control, 6);
+        self.registers
+            .clock_control(self.index)
+            .modify(|r| r.set(clock_control::EX_E, edge.as_bool()));
     }
 
     #[must_use]
     pub fn get_external_clock_edge(&self) -> ExternalClockEdge {
-        let value = read_address_bit(self.address_clock_control, 6);
+        let value = self
+            .registers
+            .clock_control(self.index)
+            .read()
+            .is_set(clock_control::EX_E);
         ExternalClockEdge::from_bool(value)
     }
 
     /// Enable or disable counter.
     pub fn toggle_counter(&self, enable: bool) {
         // Counter is active low.
-        let action = if enable {
-            clear_address_bit
-        } else {
-            set_address_bit
-        };
-        action(self.address_counter_control, 0);
+        self.registers
+            .counter_control(self.index)
+            .modify(|r| r.set(counter_control::DIS, enable.not()));
     }
 
     #[must_use]
     pub fn counter_enabled(&self) -> bool {
         // Counter is active low.
-        read_address_bit(self.address_counter_control, 0).not()
+        self.registers
+            .counter_control(self.index)
+            .read()
+            .is_set(counter_control::DIS)
+            .not()
     }
 
     pub fn set_mode(&self, mode: TimerMode) {
-        let action = if mode.as_bool() {
-            set_address_bit
-        } else {
-            clear_address_bit
-        };
-        action(self.address_counter_control, 1);
+        self.registers
+            .counter_control(self.index)
+            .modify(|r| r.set(counter_control::INT, mode.as_bool()));
     }
 
     #[must_use]
     pub fn get_mode(&self) -> TimerMode {
-        let value = read_address_bit(self.address_counter_control, 1);
+        let value = self
+            .registers
+            .counter_control(self.index)
+            .read()
+            .is_set(counter_control::INT);
         TimerMode::from_bool(value)
     }
 
     pub fn set_direction(&self, direction: TimerDirection) {
-        let action = if direction.as_bool() {
-            set_address_bit
-        } else {
-            clear_address_bit
-        };
-        action(self.address_counter_control, 2);
+        self.registers
+            .counter_control(self.index)
+            .modify(|r| r.set(counter_control::DEC, direction.as_bool()));
     }
 
     #[must_use]
     pub fn get_direction(&self) -> TimerDirection {
-        let value = read_address_bit(self.address_counter_control, 1);
+        let value = self
+            .registers
+            .counter_control(self.index)
+            .read()
+            .is_set(counter_control::DEC);
         TimerDirection::from_bool(value)
     }
 
     pub fn toggle_match_mode(&self, enable: bool) {
-        let action = if enable {
-            set_address_bit
-        } else {
-            clear_address_bit
-        };
-        action(self.address_counter_control, 3);
+        self.registers
+            .counter_control(self.index)
+            .modify(|r| r.set(counter_control::MATCH, enable));
     }
 
     #[must_use]
     pub fn match_mode_enabled(&self) -> bool {
-        read_address_bit(self.address_counter_control, 3)
+        self.registers
+            .counter_control(self.index)
+            .read()
+            .is_set(counter_control::MATCH)
     }
 
     pub fn reset(&self) {
-        let address = self.address_counter_control;
-        let index = 4;
-        set_address_bit(address, index);
-        clear_address_bit(address, index);
+        let register = self.registers.counter_control(self.index);
+        register.modify(|r| r.set(counter_control::RST, true));
+        register.modify(|r| r.set(counter_control::RST, false));
     }
 
     pub fn toggle_output_waveform(&self, enable: bool) {
         // Output waveform is active low.
-        let action = if enable {
-            clear_address_bit
-        } else {
-            set_address_bit
-        };
-        action(self.address_counter_control, 5);
+        self.registers
+            .counter_control(self.index)
+            .modify(|r| r.set(counter_control::WAVE_EN, enable.not()));
     }
 
     #[must_use]
     pub fn output_waveform_enabled(&self) -> bool {
         // Output waveform is active low.
-        read_address_bit(self.address_counter_control, 5).not()
+        self.registers
+            .counter_control(self.index)
+            .read()
+            .is_set(counter_control::WAVE_EN)
+            .not()
     }
 
     pub fn set_waveform_polarity(&self, polarity: WaveformPolarity) {
-        let action = if polarity.as_bool() {
-            set_address_bit
-        } else {
-            clear_address_bit
-        };
-        action(self.address_counter_control, 6);
+        self.registers
+            .counter_control(self.index)
+            .modify(|r| r.set(counter_control::WAVE_POL, polarity.as_bool()));
     }
 
     #[must_use]
     pub fn get_waveform_polarity(&self) -> WaveformPolarity {
-        let value = read_address_bit(self.address_counter_control, 6);
+        let value = self
+            .registers
+            .counter_control(self.index)
+            .read()
+            .is_set(counter_control::WAVE_POL);
         WaveformPolarity::from_bool(value)
     }
 
     #[must_use]
     pub fn get_counter_value(&self) -> u16 {
-        read_from_address(self.address_counter_value) as u16
+        self.registers
+            .counter_value(self.index)
+            .read()
+            .read(counter_value::VALUE) as u16
     }
 
     pub fn set_interval_value(&self, value: u16) {
-        write_to_address(self.address_interval_value, value as u32);
+        self.registers
+            .interval(self.index)
+            .write(|r| r.write(interval::VALUE, value as u32));
     }
 
     #[must_use]
     pub fn get_interval_value(&self) -> u16 {
-        read_from_address(self.address_interval_value) as u16
+        self.registers
+            .interval(self.index)
+            .read()
+            .read(interval::VALUE) as u16
     }
 
     pub fn set_match_value(&self, index: MatchIndex, value: u16) {
-        let address = match index {
-            MatchIndex::One => self.address_match_value_0,
-            MatchIndex::Two => self.address_match_value_1,
-            MatchIndex::Three => self.address_match_value_2,
-        };
-        write_to_address(address, value as u32);
+        let value = value as u32;
+        match index {
+            MatchIndex::One => self
+                .registers
+                .match_1(self.index)
+                .write(|r| r.write(match_1::VALUE, value)),
+            MatchIndex::Two => self
+                .registers
+                .match_2(self.index)
+                .write(|r| r.write(match_2::VALUE, value)),
+            MatchIndex::Three => self
+                .registers
+                .match_3(self.index)
+                .write(|r| r.write(match_3::VALUE, value)),
+        }
     }
 
     #[must_use]
     pub fn get_match_value(&self, index: MatchIndex) -> u16 {
-        let address = match index {
-            MatchIndex::One => self.address_match_value_0,
-            MatchIndex::Two => self.address_match_value_1,
-            MatchIndex::Three => self.address_match_value_2,
+        let value = match index {
+            MatchIndex::One => self
+                .registers
+                .match_1(self.index)
+                .read()
+                .read(match_1::VALUE),
+            MatchIndex::Two => self
+                .registers
+                .match_2(self.index)
+                .read()
+                .read(match_2::VALUE),
+            MatchIndex::Three => self
+                .registers
+                .match_3(self.index)
+                .read()
+                .read(match_3::VALUE),
         };
-        read_from_address(address) as u16
+        value as u16
     }
 
     #[must_use]
     pub fn clear_interrupt(&self) -> InterruptStatus {
-        let value = read_from_address(self.address_interrupt_status);
-        InterruptStatus::new(value)
+        // Reading clears the register.
+        let value = self.registers.interrupt_status(self.index).read();
+        InterruptStatus::new(value.bits())
     }
 
     pub fn toggle_interval_interrupt(&self, enable: bool) {
-        let action = if enable {
-            set_address_bit
-        } else {
-            clear_address_bit
-        };
-        action(self.address_interrupt_enable, 0);
+        self.registers
+            .interrupt_enable(self.index)
+            .modify(|r| r.set(interrupt_enable::IV, enable));
     }
 
     pub fn toggle_match_interrupt(&self, match_index: MatchIndex, enable: bool) {
-        let bit_index = match match_index {
-            MatchIndex::One => 1,
-            MatchIndex::Two => 2,
-            MatchIndex::Three => 3,
+        let field = match match_index {
+            MatchIndex::One => interrupt_enable::M1,
+            MatchIndex::Two => interrupt_enable::M2,
+            MatchIndex::Three => interrupt_enable::M3,
         };
-        let action = if enable {
-            set_address_bit
-        } else {
-            clear_address_bit
-        };
-        action(self.address_interrupt_enable, bit_index);
+        self.registers
+            .interrupt_enable(self.index)
+            .modify(|r| r.set(field, enable));
     }
 
     pub fn toggle_counter_overflow_interrupt(&self, enable: bool) {
-        let action = if enable {
-            set_address_bit
-        } else {
-            clear_address_bit
-        };
-        action(self.address_interrupt_enable, 4);
+        self.registers
+            .interrupt_enable(self.index)
+            .modify(|r| r.set(interrupt_enable::OV, enable));
     }
 
     pub fn toggle_event_timer_overflow_interrupt(&self, enable: bool) {
-        let action = if enable {
-            set_address_bit
-        } else {
-            clear_address_bit
-        };
-        action(self.address_interrupt_enable, 5);
+        self.registers
+            .interrupt_enable(self.index)
+            .modify(|r| r.set(interrupt_enable::EV, enable));
     }
 
     pub fn toggle_all_interrupts(&self, enable: bool) {
@@ -617,47 +668,56 @@ impl TTCTimer {
     }
 
     pub fn toggle_event_timer(&self, enable: bool) {
-        let action = if enable {
-            set_address_bit
-        } else {
-            clear_address_bit
-        };
-        action(self.address_event_control_timer, 0);
+        self.registers
+            .event_control(self.index)
+            .modify(|r| r.set(event_control::E_EN, enable));
     }
 
     pub fn set_event_timer_polarity(&self, polarity: EventTimerPolarity) {
-        let action = if polarity.as_bool() {
-            set_address_bit
-        } else {
-            clear_address_bit
-        };
-        action(self.address_event_control_timer, 1);
+        self.registers
+            .event_control(self.index)
+            .modify(|r| r.set(event_control::E_LO, polarity.as_bool()));
     }
 
     // TODO: get
 
     pub fn set_event_timer_mode(&self, mode: EventTimerMode) {
-        let action = if mode.as_bool() {
-            set_address_bit
-        } else {
-            clear_address_bit
-        };
-        action(self.address_event_control_timer, 2);
+        self.registers
+            .event_control(self.index)
+            .modify(|r| r.set(event_control::E_OV, mode.as_bool()));
     }
 
     // TODO: get
 
     #[must_use]
     pub fn get_event_timer_count(&self) -> u16 {
-        read_from_address(self.address_event) as u16
+        self.registers.event(self.index).read().read(event::VALUE) as u16
     }
 
     /// Solve and set prescaler and interval value from requested µseconds.
-    pub fn set_interval_useconds(&self, useconds: u32) {
+    ///
+    /// # Errors
+    ///
+    /// - Solved interval differs from requested interval by more than 10 %.
+    pub fn set_interval_useconds(&self, useconds: u32) -> Result<(), Error> {
+        let parameters = Self::solve_interval(useconds)?;
+        self.set_prescaler(parameters.prescaler);
+        self.set_interval_value(parameters.interval_value);
+        Ok(())
+    }
+
+    /// Solve prescaler and interval values for given µseconds without touching the timer.
+    ///
+    /// # Errors
+    ///
+    /// - Solved interval differs from requested interval by more than 10 %.
+    fn solve_interval(useconds: u32) -> Result<Parameters, Error> {
         use crate::sprintln;
 
         let parameters = Parameters::solve(useconds);
-        let useconds_per_interval = parameters.useconds_per_interval().unwrap();
+        let useconds_per_interval = parameters
+            .useconds_per_interval()
+            .ok_or(Error::IntervalOutOfRange(useconds))?;
 
         sprintln!("Requested µseconds: {useconds}");
         sprintln!(" - Solved prescaler value: {}", parameters.prescaler);
@@ -666,28 +726,32 @@ impl TTCTimer {
 
         let lower_bound: u32 = (0.9 * (useconds as f32)) as u32;
         let upper_bound: u32 = (1.1 * (useconds as f32)) as u32;
-        if lower_bound <= useconds_per_interval {
-            if useconds_per_interval <= upper_bound {
-                self.set_prescaler(parameters.prescaler);
-                self.set_interval_value(parameters.interval_value);
-            } else {
-                panic!("Could not solve prescaler and interval value to reach {useconds} µseconds per interval. Upper bound: {upper_bound}.");
-            }
-        } else {
-            panic!("Could not solve prescaler and interval value to reach {useconds} µseconds per interval. Lower bound: {lower_bound}.");
+        if (lower_bound..=upper_bound)
+            .contains(&useconds_per_interval)
+            .not()
+        {
+            return Err(Error::IntervalOutOfRange(useconds));
         }
+        Ok(parameters)
     }
 
     /// Sleep given µseconds.
     ///
     /// Only works for short sleeps, under 100 000 µseconds.
-    pub fn usleep(&mut self, useconds: u32) {
+    ///
+    /// # Errors
+    ///
+    /// - Requested sleep can not be reached.
+    pub fn usleep(&mut self, useconds: u32) -> Result<(), Error> {
         // TODO: return error if timer is not enabled
         // TODO: return error if event mode is enabled
         // TODO: return error if direction is not up
         // TODO: return error if matches are enabled
 
-        // TODO: maybe store timer's context and restore after sleep?
+        // Interval is solved before the timer is touched,
+        // so an unreachable interval leaves the timer as it was.
+        let parameters = Self::solve_interval(useconds)?;
+        let saved = self.save();
 
         self.toggle_counter(false);
         self.toggle_event_timer(false);
@@ -695,7 +759,8 @@ impl TTCTimer {
         self.toggle_all_interrupts(false);
         self.set_clock_source(ClockSource::Internal);
         self.toggle_prescaler(true);
-        self.set_interval_useconds(useconds);
+        self.set_prescaler(parameters.prescaler);
+        self.set_interval_value(parameters.interval_value);
         self.set_mode(TimerMode::Interval);
         //self.set_direction(TimerDirection::Increment);
         self.set_direction(TimerDirection::Decrement);
@@ -714,117 +779,169 @@ impl TTCTimer {
             crate::common::instruction::nop();
         }
         self.toggle_counter(false);
+        self.restore(&saved);
+        Ok(())
     }
 }
 
-const ADDRESS_BASE_TTC0: u32 = 0xF800_1000;
+/// Saved TTC timer configuration.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TTCTimerState {
+    clock_control: u32,
+    counter_control: u32,
+    interval: u32,
+    match_1: u32,
+    match_2: u32,
+    match_3: u32,
+    interrupt_enable: u32,
+    event_control: u32,
+}
 
-/// Triple timer counter 0's timer/clock 0.
-///
-/// Can count events from MIO or EMIO.
-pub static mut TIMER_TTC0_0: TTCTimer = TTCTimer {
-    address_clock_control: (ADDRESS_BASE_TTC0 + 0x00) as *mut u32,
-    address_counter_control: (ADDRESS_BASE_TTC0 + 0x0C) as *mut u32,
-    address_counter_value: (ADDRESS_BASE_TTC0 + 0x18) as *mut u32,
-    address_interval_value: (ADDRESS_BASE_TTC0 + 0x24) as *mut u32,
-    address_match_value_0: (ADDRESS_BASE_TTC0 + 0x30) as *mut u32,
-    address_match_value_1: (ADDRESS_BASE_TTC0 + 0x3C) as *mut u32,
-    address_match_value_2: (ADDRESS_BASE_TTC0 + 0x48) as *mut u32,
-    address_interrupt_status: (ADDRESS_BASE_TTC0 + 0x54) as *mut u32,
-    address_interrupt_enable: (ADDRESS_BASE_TTC0 + 0x60) as *mut u32,
-    address_event_control_timer: (ADDRESS_BASE_TTC0 + 0x6C) as *mut u32,
-    address_event: (ADDRESS_BASE_TTC0 + 0x78) as *mut u32,
-    sleeping: core::sync::atomic::AtomicBool::new(false),
-};
-
-/// Triple timer counter 0's timer/clock 1.
-///
-/// Can count events from EMIO.
-pub static mut TIMER_TTC0_1: TTCTimer = TTCTimer {
-    address_clock_control: (ADDRESS_BASE_TTC0 + 0x04) as *mut u32,
-    address_counter_control: (ADDRESS_BASE_TTC0 + 0x10) as *mut u32,
-    address_counter_value: (ADDRESS_BASE_TTC0 + 0x1C) as *mut u32,
-    address_interval_value: (ADDRESS_BASE_TTC0 + 0x28) as *mut u32,
-    address_match_value_0: (ADDRESS_BASE_TTC0 + 0x34) as *mut u32,
-    address_match_value_1: (ADDRESS_BASE_TTC0 + 0x40) as *mut u32,
-    address_match_value_2: (ADDRESS_BASE_TTC0 + 0x4C) as *mut u32,
-    address_interrupt_status: (ADDRESS_BASE_TTC0 + 0x58) as *mut u32,
-    address_interrupt_enable: (ADDRESS_BASE_TTC0 + 0x64) as *mut u32,
-    address_event_control_timer: (ADDRESS_BASE_TTC0 + 0x70) as *mut u32,
-    address_event: (ADDRESS_BASE_TTC0 + 0x7C) as *mut u32,
-    sleeping: core::sync::atomic::AtomicBool::new(false),
-};
-
-/// Triple timer counter 0's timer/clock 2.
-///
-/// Can count events from EMIO.
-pub static mut TIMER_TTC0_2: TTCTimer = TTCTimer {
-    address_clock_control: (ADDRESS_BASE_TTC0 + 0x08) as *mut u32,
-    address_counter_control: (ADDRESS_BASE_TTC0 + 0x14) as *mut u32,
-    address_counter_value: (ADDRESS_BASE_TTC0 + 0x20) as *mut u32,
-    address_interval_value: (ADDRESS_BASE_TTC0 + 0x2C) as *mut u32,
-    address_match_value_0: (ADDRESS_BASE_TTC0 + 0x38) as *mut u32,
-    address_match_value_1: (ADDRESS_BASE_TTC0 + 0x44) as *mut u32,
-    address_match_value_2: (ADDRESS_BASE_TTC0 + 0x50) as *mut u32,
-    address_interrupt_status: (ADDRESS_BASE_TTC0 + 0x5C) as *mut u32,
-    address_interrupt_enable: (ADDRESS_BASE_TTC0 + 0x68) as *mut u32,
-    address_event_control_timer: (ADDRESS_BASE_TTC0 + 0x74) as *mut u32,
-    address_event: (ADDRESS_BASE_TTC0 + 0x80) as *mut u32,
-    sleeping: core::sync::atomic::AtomicBool::new(false),
-};
-
-const ADDRESS_BASE_TTC1: u32 = 0xF800_2000;
-
-/// Triple timer counter 1's timer/clock 0.
-///
-/// Can count events from MIO or EMIO.
-pub static mut TIMER_TTC1_0: TTCTimer = TTCTimer {
-    address_clock_control: (ADDRESS_BASE_TTC1 + 0x00) as *mut u32,
-    address_counter_control: (ADDRESS_BASE_TTC1 + 0x0C) as *mut u32,
-    address_counter_value: (ADDRESS_BASE_TTC1 + 0x18) as *mut u32,
-    address_interval_value: (ADDRESS_BASE_TTC1 + 0x24) as *mut u32,
-    address_match_value_0: (ADDRESS_BASE_TTC1 + 0x30) as *mut u32,
-    address_match_value_1: (ADDRESS_BASE_TTC1 + 0x3C) as *mut u32,
-    address_match_value_2: (ADDRESS_BASE_TTC1 + 0x48) as *mut u32,
-    address_interrupt_status: (ADDRESS_BASE_TTC1 + 0x54) as *mut u32,
-    address_interrupt_enable: (ADDRESS_BASE_TTC1 + 0x60) as *mut u32,
-    address_event_control_timer: (ADDRESS_BASE_TTC1 + 0x6C) as *mut u32,
-    address_event: (ADDRESS_BASE_TTC1 + 0x78) as *mut u32,
-    sleeping: core::sync::atomic::AtomicBool::new(false),
-};
-
-/// Triple timer counter 1's timer/clock 1.
-///
-/// Can count events from EMIO.
-pub static mut TIMER_TTC1_1: TTCTimer = TTCTimer {
-    address_clock_control: (ADDRESS_BASE_TTC1 + 0x04) as *mut u32,
-    address_counter_control: (ADDRESS_BASE_TTC1 + 0x10) as *mut u32,
-    address_counter_value: (ADDRESS_BASE_TTC1 + 0x1C) as *mut u32,
-    address_interval_value: (ADDRESS_BASE_TTC1 + 0x28) as *mut u32,
-    address_match_value_0: (ADDRESS_BASE_TTC1 + 0x34) as *mut u32,
-    address_match_value_1: (ADDRESS_BASE_TTC1 + 0x40) as *mut u32,
-    address_match_value_2: (ADDRESS_BASE_TTC1 + 0x4C) as *mut u32,
-    address_interrupt_status: (ADDRESS_BASE_TTC1 + 0x58) as *mut u32,
-    address_interrupt_enable: (ADDRESS_BASE_TTC1 + 0x64) as *mut u32,
-    address_event_control_timer: (ADDRESS_BASE_TTC1 + 0x70) as *mut u32,
-    address_event: (ADDRESS_BASE_TTC1 + 0x7C) as *mut u32,
-    sleeping: core::sync::atomic::AtomicBool::new(false),
-};
-
-/// Triple timer counter 1's timer/clock 2.
+impl<B: Bus> Snapshot for TTCTimer<B> {
+    type State = TTCTimerState;
+
+    fn save(&self) -> TTCTimerState {
+        let index = self.index;
+        TTCTimerState {
+            clock_control: self.registers.clock_control(index).read().bits(),
+            counter_control: self.registers.counter_control(index).read().bits(),
+            interval: self.registers.interval(index).read().bits(),
+            match_1: self.registers.match_1(index).read().bits(),
+            match_2: self.registers.match_2(index).read().bits(),
+            match_3: self.registers.match_3(index).read().bits(),
+            interrupt_enable: self.registers.interrupt_enable(index).read().bits(),
+            event_control: self.registers.event_control(index).read().bits(),
+        }
+    }
+
+    /// Restore TTC timer configuration.
+    ///
+    /// Counter value can not be written, so counter continues from its current value.
+    fn restore(&self, state: &TTCTimerState) {
+        let index = self.index;
+        self.toggle_counter(false);
+        self.registers
+            .clock_control(index)
+            .write_bits(state.clock_control);
+        self.registers.interval(index).write_bits(state.interval);
+        self.registers.match_1(index).write_bits(state.match_1);
+        self.registers.match_2(index).write_bits(state.match_2);
+        self.registers.match_3(index).write_bits(state.match_3);
+        self.registers
+            .interrupt_enable(index)
+            .write_bits(state.interrupt_enable);
+        self.registers
+            .event_control(index)
+            .write_bits(state.event_control);
+        // Counter control is written last, since it may enable the counter.
+        self.registers
+            .counter_control(index)
+            .write_bits(state.counter_control);
+    }
+}
+
+/// Triple timer counter.
 ///
-/// Can count events from EMIO.
-pub static mut TIMER_TTC1_2: TTCTimer = TTCTimer {
-    address_clock_control: (ADDRESS_BASE_TTC1 + 0x08) as *mut u32,
-    address_counter_control: (ADDRESS_BASE_TTC1 + 0x14) as *mut u32,
-    address_counter_value: (ADDRESS_BASE_TTC1 + 0x20) as *mut u32,
-    address_interval_value: (ADDRESS_BASE_TTC1 + 0x2C) as *mut u32,
-    address_match_value_0: (ADDRESS_BASE_TTC1 + 0x38) as *mut u32,
-    address_match_value_1: (ADDRESS_BASE_TTC1 + 0x44) as *mut u32,
-    address_match_value_2: (ADDRESS_BASE_TTC1 + 0x50) as *mut u32,
-    address_interrupt_status: (ADDRESS_BASE_TTC1 + 0x5C) as *mut u32,
-    address_interrupt_enable: (ADDRESS_BASE_TTC1 + 0x68) as *mut u32,
-    address_event_control_timer: (ADDRESS_BASE_TTC1 + 0x74) as *mut u32,
-    address_event: (ADDRESS_BASE_TTC1 + 0x80) as *mut u32,
-    sleeping: core::sync::atomic::AtomicBool::new(false),
-};
+/// Consists of three independent timers/clocks.
+/// Use [`split`](TripleTimerCounter::split) to get interface for each of them.
+pub struct TripleTimerCounter<B = Mmio> {
+    /// Triple timer counter registers.
+    registers: TtcRegisters<B>,
+}
+
+impl<B> TripleTimerCounter<B> {
+    /// Create interface for TTC peripheral at given base address using given register access.
+    #[must_use]
+    pub const fn with_bus(address_base: u32, bus: B) -> Self {
+        Self {
+            registers: TtcRegisters::new(address_base, bus),
+        }
+    }
+
+    /// Triple timer counter registers.
+    #[inline]
+    #[must_use]
+    pub fn registers(&self) -> &TtcRegisters<B> {
+        &self.registers
+    }
+}
+
+impl<B: Copy> TripleTimerCounter<B> {
+    /// Split into timers/clocks 0, 1 and 2.
+    ///
+    /// - Timer/clock 0 can count events from MIO or EMIO.
+    /// - Timers/clocks 1 and 2 can count events from EMIO.
+    #[inline]
+    #[must_use]
+    pub fn split(self) -> (TTCTimer<B>, TTCTimer<B>, TTCTimer<B>) {
+        let address_base = self.registers.base();
+        let bus = *self.registers.bus();
+        (
+            TTCTimer::with_bus(address_base, 0, bus),
+            TTCTimer::with_bus(address_base, 1, bus),
+            TTCTimer::with_bus(address_base, 2, bus),
+        )
+    }
+}
+
+/// Triple timer counter 0's base address.
+pub(crate) const ADDRESS_BASE_TTC0: u32 = 0xF800_1000;
+
+/// Triple timer counter 1's base address.
+pub(crate) const ADDRESS_BASE_TTC1: u32 = 0xF800_2000;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::bus::SimulatedBus;
+    use crate::console;
+    use crate::console::Console;
+
+    /// Write distinct values to every saved register of timer/clock 1.
+    fn preload_configuration(bus: &SimulatedBus<16, 32>) {
+        let registers: [usize; 8] = [
+            0xF800_1004,
+            0xF800_1010,
+            0xF800_1028,
+            0xF800_1034,
+            0xF800_1040,
+            0xF800_104C,
+            0xF800_1064,
+            0xF800_1070,
+        ];
+        for (value, address) in (1..).zip(registers) {
+            bus.preload(address as *mut u32, value);
+        }
+    }
+
+    #[test]
+    fn restore_writes_saved_configuration_back() {
+        let bus = SimulatedBus::<16, 32>::new();
+        preload_configuration(&bus);
+        let timer = TTCTimer::with_bus(ADDRESS_BASE_TTC0, 1, &bus);
+
+        let saved = timer.save();
+        timer.toggle_prescaler(true);
+        timer.set_interval_value(0x1234);
+        timer.toggle_all_interrupts(false);
+        timer.restore(&saved);
+        assert_eq!(timer.save(), saved);
+    }
+
+    #[test]
+    fn failing_sleep_does_not_touch_timer() {
+        // Solver prints to console, which is not available on the host.
+        console::set_console(Console::Disabled);
+        let bus = SimulatedBus::<16, 32>::new();
+        preload_configuration(&bus);
+        let mut timer = TTCTimer::with_bus(ADDRESS_BASE_TTC0, 1, &bus);
+
+        let saved = timer.save();
+        assert_eq!(
+            timer.usleep(1_000_000),
+            Err(Error::IntervalOutOfRange(1_000_000))
+        );
+        assert_eq!(bus.writes().count(), 0);
+        assert_eq!(timer.save(), saved);
+    }
+}
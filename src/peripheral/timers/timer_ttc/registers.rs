@@ -0,0 +1,118 @@
+//! Triple timer counter registers.
+//!
+//! Registers of three timers/clocks are interleaved, so each register is an array indexed by timer/clock.
+
+crate::register_block! {
+    /// Triple timer counter registers.
+    pub TtcRegisters {
+        /// Clock control registers.
+        0x00 [3; 0x04] => clock_control: ReadWrite, reset = 0x0000_0000 {
+            /// Enable prescaler.
+            PS_EN: 0, 1;
+            /// Prescaler value, clock is divided by `2 ^ (N + 1)`.
+            PS_V: 1, 4;
+            /// Use external clock.
+            C_SRC: 5, 1;
+            /// Count external clock's negative edges.
+            EX_E: 6, 1;
+        }
+
+        /// Counter control registers.
+        0x0C [3; 0x04] => counter_control: ReadWrite, reset = 0x0000_0021 {
+            /// Disable counter.
+            DIS: 0, 1;
+            /// Interval mode.
+            INT: 1, 1;
+            /// Count down.
+            DEC: 2, 1;
+            /// Enable match mode.
+            MATCH: 3, 1;
+            /// Reset counter, cleared automatically.
+            RST: 4, 1;
+            /// Disable output waveform.
+            WAVE_EN: 5, 1;
+            /// Output waveform polarity.
+            WAVE_POL: 6, 1;
+        }
+
+        /// Counter value registers.
+        0x18 [3; 0x04] => counter_value: ReadOnly, reset = 0x0000_0000 {
+            /// Counter's value.
+            VALUE: 0, 16;
+        }
+
+        /// Interval value registers.
+        0x24 [3; 0x04] => interval: ReadWrite, reset = 0x0000_0000 {
+            /// Interval's value.
+            VALUE: 0, 16;
+        }
+
+        /// Match 1 value registers.
+        0x30 [3; 0x04] => match_1: ReadWrite, reset = 0x0000_0000 {
+            /// Match's value.
+            VALUE: 0, 16;
+        }
+
+        /// Match 2 value registers.
+        0x3C [3; 0x04] => match_2: ReadWrite, reset = 0x0000_0000 {
+            /// Match's value.
+            VALUE: 0, 16;
+        }
+
+        /// Match 3 value registers.
+        0x48 [3; 0x04] => match_3: ReadWrite, reset = 0x0000_0000 {
+            /// Match's value.
+            VALUE: 0, 16;
+        }
+
+        /// Interrupt status registers.
+        ///
+        /// Reading clears the register.
+        0x54 [3; 0x04] => interrupt_status: ReadOnly, reset = 0x0000_0000 {
+            /// Interval interrupt.
+            IV: 0, 1;
+            /// Match 1 interrupt.
+            M1: 1, 1;
+            /// Match 2 interrupt.
+            M2: 2, 1;
+            /// Match 3 interrupt.
+            M3: 3, 1;
+            /// Counter overflow interrupt.
+            OV: 4, 1;
+            /// Event timer overflow interrupt.
+            EV: 5, 1;
+        }
+
+        /// Interrupt enable registers.
+        0x60 [3; 0x04] => interrupt_enable: ReadWrite, reset = 0x0000_0000 {
+            /// Interval interrupt.
+            IV: 0, 1;
+            /// Match 1 interrupt.
+            M1: 1, 1;
+            /// Match 2 interrupt.
+            M2: 2, 1;
+            /// Match 3 interrupt.
+            M3: 3, 1;
+            /// Counter overflow interrupt.
+            OV: 4, 1;
+            /// Event timer overflow interrupt.
+            EV: 5, 1;
+        }
+
+        /// Event control timer registers.
+        0x6C [3; 0x04] => event_control: ReadWrite, reset = 0x0000_0000 {
+            /// Enable event timer.
+            E_EN: 0, 1;
+            /// Count clock cycles when external clock is low.
+            E_LO: 1, 1;
+            /// Continue counting after overflow.
+            E_OV: 2, 1;
+        }
+
+        /// Event registers.
+        0x78 [3; 0x04] => event: ReadOnly, reset = 0x0000_0000 {
+            /// Clock cycle count.
+            VALUE: 0, 16;
+        }
+    }
+}
@@ -1,11 +1,13 @@
 //! CPU private watchdog timer.
 
-use crate::common::memman::clear_address_bit;
-use crate::common::memman::read_address_bit;
-use crate::common::memman::read_from_address;
-use crate::common::memman::set_address_bit;
-use crate::common::memman::write_address_bits;
-use crate::common::memman::write_to_address;
+pub mod registers;
+
+use crate::common::bus::Bus;
+use crate::common::bus::Mmio;
+use registers::control;
+use registers::interrupt_status;
+use registers::reset_status;
+use registers::PrivateWatchdogTimerRegisters;
 
 #[derive(Clone, Copy)]
 pub enum ReloadMode {
@@ -28,102 +30,112 @@ pub enum TimerMode {
     WatchdogMode,
 }
 
-pub struct PrivateWatchdogTimer {
-    address_load: *mut u32,
-    address_counter: *mut u32,
-    address_control: *mut u32,
-    address_interrupt_status: *mut u32,
-    address_reset_status: *mut u32,
-    address_disable_watchdog: *mut u32,
+pub struct PrivateWatchdogTimer<B = Mmio> {
+    /// Private watchdog timer registers.
+    registers: PrivateWatchdogTimerRegisters<B>,
+}
+
+impl<B> PrivateWatchdogTimer<B> {
+    /// Create interface for private watchdog timer using given register access.
+    #[must_use]
+    pub const fn with_bus(bus: B) -> Self {
+        Self {
+            registers: PrivateWatchdogTimerRegisters::new(ADDRESS_BASE, bus),
+        }
+    }
+
+    /// Private watchdog timer registers.
+    #[inline]
+    #[must_use]
+    pub fn registers(&self) -> &PrivateWatchdogTimerRegisters<B> {
+        &self.registers
+    }
 }
 
-impl PrivateWatchdogTimer {
+impl<B: Bus> PrivateWatchdogTimer<B> {
     pub fn get_load(&self) -> u32 {
-        read_from_address(self.address_load)
+        self.registers.load().read().bits()
     }
 
     pub fn set_load(&self, value: u32) {
-        write_to_address(self.address_load, value);
+        self.registers.load().write_bits(value);
     }
 
     pub fn get_count(&self) -> u32 {
-        read_from_address(self.address_counter)
+        self.registers.counter().read().bits()
     }
 
     pub fn set_count(&self, value: u32) {
-        write_to_address(self.address_counter, value);
+        self.registers.counter().write_bits(value);
     }
 
     /// Enable or disable watchdog timer.
     pub fn toggle(&self, enable: bool) {
-        let action = if enable {
-            set_address_bit
-        } else {
-            clear_address_bit
-        };
-        action(self.address_control, 0);
+        self.registers
+            .control()
+            .modify(|r| r.set(control::WATCHDOG_ENABLE, enable));
     }
 
     pub fn set_reload_mode(&self, mode: ReloadMode) {
-        let action = if mode.as_bool() {
-            set_address_bit
-        } else {
-            clear_address_bit
-        };
-        action(self.address_control, 1)
+        self.registers
+            .control()
+            .modify(|r| r.set(control::AUTO_RELOAD, mode.as_bool()));
     }
 
     pub fn toggle_interrupt(&self, enable: bool) {
-        let action = if enable {
-            set_address_bit
-        } else {
-            clear_address_bit
-        };
-        action(self.address_control, 2);
+        self.registers
+            .control()
+            .modify(|r| r.set(control::IT_ENABLE, enable));
     }
 
     pub fn set_timer_mode(&self, mode: TimerMode) {
         match mode {
             TimerMode::TimerMode => {
-                write_to_address(self.address_disable_watchdog, 0x1234_5678);
-                write_to_address(self.address_disable_watchdog, 0x8765_4321);
+                self.registers.disable().write_bits(0x1234_5678);
+                self.registers.disable().write_bits(0x8765_4321);
             }
             TimerMode::WatchdogMode => {
-                set_address_bit(self.address_control, 3);
+                self.registers
+                    .control()
+                    .modify(|r| r.set(control::WATCHDOG_MODE, true));
             }
         }
     }
 
     pub fn set_prescaler(&self, value: u8) {
-        write_address_bits(self.address_control, 8..=15, value as u32);
+        self.registers
+            .control()
+            .modify(|r| r.write(control::PRESCALER, value as u32));
     }
 
     /// True if counter has reached zero in timer mode.
     pub fn read_interrupt_status(&self) -> bool {
-        read_address_bit(self.address_interrupt_status, 0)
+        self.registers
+            .interrupt_status()
+            .read()
+            .is_set(interrupt_status::EVENT_FLAG)
     }
 
     pub fn clear_interrupt(&self) {
-        set_address_bit(self.address_interrupt_status, 0);
+        // Writing low has no effect.
+        self.registers
+            .interrupt_status()
+            .write_with_zero(|r| r.set(interrupt_status::EVENT_FLAG, true));
     }
 
     pub fn read_reset_status(&self) -> bool {
-        read_address_bit(self.address_reset_status, 0)
+        self.registers
+            .reset_status()
+            .read()
+            .is_set(reset_status::RESET_FLAG)
     }
 
     pub fn clear_reset(&self) {
-        set_address_bit(self.address_reset_status, 0);
+        // Writing low has no effect.
+        self.registers
+            .reset_status()
+            .write_with_zero(|r| r.set(reset_status::RESET_FLAG, true));
     }
 }
 
 const ADDRESS_BASE: u32 = 0xF8F0_0000;
-const ADDRESS_BASE_WATCHDOG: u32 = ADDRESS_BASE + 0x600;
-
-pub static mut PRIVATE_WATCHDOG_TIMER: PrivateWatchdogTimer = PrivateWatchdogTimer {
-    address_load: (ADDRESS_BASE_WATCHDOG + 0x20) as *mut u32,
-    address_counter: (ADDRESS_BASE_WATCHDOG + 0x24) as *mut u32,
-    address_control: (ADDRESS_BASE_WATCHDOG + 0x28) as *mut u32,
-    address_interrupt_status: (ADDRESS_BASE_WATCHDOG + 0x2C) as *mut u32,
-    address_reset_status: (ADDRESS_BASE_WATCHDOG + 0x30) as *mut u32,
-    address_disable_watchdog: (ADDRESS_BASE_WATCHDOG + 0x34) as *mut u32,
-};
@@ -0,0 +1,41 @@
+//! Private watchdog timer registers.
+
+crate::register_block! {
+    /// Private watchdog timer registers.
+    pub PrivateWatchdogTimerRegisters {
+        /// Watchdog load register.
+        0x620 => load: ReadWrite, reset = 0x0000_0000 {}
+
+        /// Watchdog counter register.
+        0x624 => counter: ReadWrite, reset = 0x0000_0000 {}
+
+        /// Watchdog control register.
+        0x628 => control: ReadWrite, reset = 0x0000_0000 {
+            /// Enable watchdog.
+            WATCHDOG_ENABLE: 0, 1;
+            /// Reload counter when it reaches zero.
+            AUTO_RELOAD: 1, 1;
+            /// Enable watchdog interrupt.
+            IT_ENABLE: 2, 1;
+            /// Watchdog mode, cleared only by disable sequence.
+            WATCHDOG_MODE: 3, 1;
+            /// Clock prescaler.
+            PRESCALER: 8, 8;
+        }
+
+        /// Watchdog interrupt status register.
+        0x62C => interrupt_status: ReadWrite, reset = 0x0000_0000 {
+            /// Counter has reached zero in timer mode, write high to clear.
+            EVENT_FLAG: 0, 1;
+        }
+
+        /// Watchdog reset status register.
+        0x630 => reset_status: ReadWrite, reset = 0x0000_0000 {
+            /// Counter has reached zero in watchdog mode, write high to clear.
+            RESET_FLAG: 0, 1;
+        }
+
+        /// Watchdog disable register.
+        0x634 => disable: WriteOnly, reset = 0x0000_0000 {}
+    }
+}
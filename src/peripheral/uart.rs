@@ -3,28 +3,196 @@
 //! # How to use?
 //!
 //! ```ignore
-//! UART0::configure();
-//! UART0::toggle(true);
-//! UART0::transmit_line("Hello, World!");
+//! let peripherals = Peripherals::take().unwrap();
+//! let uart = peripherals.uart0;
+//! uart.configure(&peripherals.slcr).unwrap();
+//! uart.set_baud_rate(&peripherals.slcr, 115_200).unwrap();
+//! uart.toggle(true);
+//! uart.transmit_line("Hello, World!");
 //! ```
 //!
-//! # TODO
+//! Formatted output is transmitted using the UART it is written to.
+//! To print to a console selected at runtime, see [`console`](crate::console).
+//!
+//! ```ignore
+//! use core::fmt::Write;
+//!
+//! let mut uart = peripherals.uart1;
+//! writeln!(uart, "Temperature: {temperature}").unwrap();
+//! ```
+//!
+//! Idle-line framed and LIN-style protocols use receiver timeout and breaks.
+//!
+//! ```ignore
+//! uart.set_receiver_timeout(40)?;
+//! let count = uart.receive_frame(&mut frame)?;
+//!
+//! uart.send_break(&peripherals.slcr, &mut delay, 13)?;
+//! if uart.try_receive() == Some(Received::Break) {}
+//! ```
+//!
+//! Hardware flow control pauses the sender before receiver FIFO overflows.
+//!
+//! ```ignore
+//! uart.set_flow_control(FlowControl::Hardware);
+//! uart.set_flow_control_delay(48)?;
+//! uart.toggle_interrupt(Interrupt::ModemIndicator, true);
+//! ```
+//!
+//! Production test images can verify UART with [`self_test`](Uart::self_test),
+//! which uses the built-in local loopback.
+//!
+//! To transmit and receive without blocking, wrap UART to [`BufferedUart`](buffered::BufferedUart),
+//! which moves bytes in UART interrupt handler.
+//!
+//! `Uart` implements [`embedded_io`] and [`embedded_hal_nb::serial`] traits,
+//! so it can be given to drivers written against them.
+//!
+//! ```ignore
+//! use embedded_io::Write;
+//!
+//! let mut uart = peripherals.uart1;
+//! uart.write_all(b"AT\r\n")?;
+//! uart.flush()?;
+//! ```
 //!
-//! - modem
-
 // TODO: separate to receiver and transmitter substructs
 
+pub mod baud_rate;
+pub mod buffered;
+pub mod registers;
+pub mod self_test;
+
 use crate::common::bitman::ReadBitwise;
-use crate::common::bitman::SetBitwise;
+use crate::common::bus::Bus;
+use crate::common::bus::Mmio;
 use crate::common::instruction::nop;
-use crate::common::memman::clear_address_bit;
-use crate::common::memman::read_address_bit;
-use crate::common::memman::read_from_address;
-use crate::common::memman::set_address_bit;
-use crate::common::memman::write_address_bits;
-use crate::common::memman::write_to_address;
+use crate::common::register::Value;
+use crate::common::snapshot::Snapshot;
+use crate::peripheral::slcr::Error as SlcrError;
+use crate::peripheral::slcr::Slcr;
+use baud_rate::BaudRate;
 use core::ops::BitAnd;
 use core::ops::Not;
+use embedded_hal::delay::DelayNs;
+use registers::baud_rate_divider;
+use registers::baud_rate_generator;
+use registers::channel_interrupt_status;
+use registers::channel_status;
+use registers::control;
+use registers::fifo;
+use registers::flow_control_delay;
+use registers::interrupt_disable;
+use registers::interrupt_enable;
+use registers::interrupt_mask;
+use registers::mode;
+use registers::modem_control;
+use registers::modem_status;
+use registers::receiver_fifo_trigger_level;
+use registers::receiver_timeout;
+use registers::transmitter_fifo_trigger_level;
+use registers::UartRegisters;
+
+/// UART error.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// System level control registers could not be configured.
+    Slcr(SlcrError),
+
+    /// FIFO trigger value is out of range.
+    InvalidTriggerValue(u32),
+
+    /// Register holds a reserved value.
+    ReservedValue(u32),
+
+    /// Baud rate can not be generated within tolerance.
+    UnreachableBaudRate(u32),
+
+    /// Receiver timeout in bit periods is out of range.
+    InvalidReceiverTimeout(u32),
+
+    /// Receiver timeout is disabled, so end of frame can not be detected.
+    ReceiverTimeoutDisabled,
+
+    /// Receiver FIFO was full when a byte was received, so the byte was lost.
+    Overrun,
+
+    /// Received byte did not end with a valid stop bit.
+    Framing,
+
+    /// Received byte's parity bit did not match.
+    Parity,
+
+    /// Byte received in loopback differs from transmitted byte, or no byte was received.
+    LoopbackMismatch {
+        /// Baud rate used.
+        baud: u32,
+
+        /// Transmitted byte.
+        sent: u8,
+
+        /// Received byte.
+        received: Option<u8>,
+    },
+}
+
+impl From<SlcrError> for Error {
+    fn from(value: SlcrError) -> Self {
+        Self::Slcr(value)
+    }
+}
+
+impl core::fmt::Display for Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Slcr(error) => write!(f, "{error}"),
+            Self::InvalidTriggerValue(value) => {
+                write!(f, "FIFO trigger value {value} is out of range 0..=63")
+            }
+            Self::ReservedValue(value) => write!(f, "register holds reserved value 0b{value:b}"),
+            Self::UnreachableBaudRate(baud) => {
+                write!(f, "baud rate {baud} can not be generated within tolerance")
+            }
+            Self::InvalidReceiverTimeout(value) => write!(
+                f,
+                "receiver timeout {value} is out of range 0..={RECEIVER_TIMEOUT_MAX} bit periods"
+            ),
+            Self::ReceiverTimeoutDisabled => write!(f, "receiver timeout is disabled"),
+            Self::Overrun => write!(f, "receiver FIFO overrun"),
+            Self::Framing => write!(f, "received byte has framing error"),
+            Self::Parity => write!(f, "received byte has parity error"),
+            Self::LoopbackMismatch {
+                baud,
+                sent,
+                received: Some(received),
+            } => write!(
+                f,
+                "loopback at baud rate {baud} sent 0x{sent:02X} but received 0x{received:02X}"
+            ),
+            Self::LoopbackMismatch {
+                baud,
+                sent,
+                received: None,
+            } => write!(
+                f,
+                "loopback at baud rate {baud} sent 0x{sent:02X} but received nothing"
+            ),
+        }
+    }
+}
+
+/// Longest receiver timeout in bit periods.
+pub const RECEIVER_TIMEOUT_MAX: u32 = 255 * 4;
+
+/// Byte or line condition read from receiver.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Received {
+    /// Received byte.
+    Byte(u8),
+
+    /// Line was held low for longer than one frame.
+    Break,
+}
 
 #[derive(Clone, Copy)]
 pub enum DeviceIndex {
@@ -113,13 +281,18 @@ impl CharacterLength {
         }
     }
 
-    pub fn from_u32(value: u32) -> Result<Self, ()> {
+    /// Transform from unsigned 32-bit integer.
+    ///
+    /// # Errors
+    ///
+    /// - Value is reserved.
+    pub fn from_u32(value: u32) -> Result<Self, Error> {
         let result = match value {
             0b00 => Self::Eight,
             0b10 => Self::Seven,
             0b11 => Self::Six,
-            _ => {
-                return Err(());
+            other => {
+                return Err(Error::ReservedValue(other));
             }
         };
         Ok(result)
@@ -165,15 +338,20 @@ impl ParityType {
         }
     }
 
-    pub fn from_u32(value: u32) -> Result<Self, ()> {
+    /// Transform from unsigned 32-bit integer.
+    ///
+    /// # Errors
+    ///
+    /// - Value is reserved.
+    pub fn from_u32(value: u32) -> Result<Self, Error> {
         let result = match value {
             0b000 => Self::Even,
             0b001 => Self::Odd,
             0b010 => Self::ForcedTo0,
             0b011 => Self::ForcedTo1,
             0b100 => Self::Disabled,
-            _ => {
-                return Err(());
+            other => {
+                return Err(Error::ReservedValue(other));
             }
         };
         Ok(result)
@@ -218,13 +396,18 @@ impl StopBits {
         }
     }
 
-    pub fn from_u32(value: u32) -> Result<Self, ()> {
+    /// Transform from unsigned 32-bit integer.
+    ///
+    /// # Errors
+    ///
+    /// - Value is reserved.
+    pub fn from_u32(value: u32) -> Result<Self, Error> {
         let result = match value {
             0b00 => Self::One,
             0b01 => Self::OneAndHalf,
             0b10 => Self::Two,
-            _ => {
-                return Err(());
+            other => {
+                return Err(Error::ReservedValue(other));
             }
         };
         Ok(result)
@@ -242,6 +425,80 @@ impl core::fmt::Display for StopBits {
     }
 }
 
+/// UART flow control mode.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum FlowControl {
+    /// Request to send is controlled by software, clear to send is ignored.
+    None,
+
+    /// Request to send is deasserted when receiver FIFO reaches flow control delay level,
+    /// transmitter waits while clear to send is deasserted.
+    Hardware,
+}
+
+impl FlowControl {
+    /// Transform to boolean.
+    #[inline]
+    #[must_use]
+    pub const fn as_bool(self) -> bool {
+        match self {
+            Self::None => false,
+            Self::Hardware => true,
+        }
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn from_bool(value: bool) -> Self {
+        if value {
+            Self::Hardware
+        } else {
+            Self::None
+        }
+    }
+}
+
+impl core::fmt::Display for FlowControl {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let name = match self {
+            Self::None => "no flow control",
+            Self::Hardware => "hardware flow control",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// Modem signals and their changes.
+///
+/// Changes are held until [cleared](Uart::clear_modem_status_changes).
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ModemStatus {
+    /// Clear to send is asserted.
+    pub clear_to_send: bool,
+
+    /// Data set ready is asserted.
+    pub data_set_ready: bool,
+
+    /// Ring indicator is asserted.
+    pub ring_indicator: bool,
+
+    /// Data carrier detect is asserted.
+    pub data_carrier_detect: bool,
+
+    /// Clear to send has changed.
+    pub clear_to_send_changed: bool,
+
+    /// Data set ready has changed.
+    pub data_set_ready_changed: bool,
+
+    /// Ring indicator has been deasserted.
+    pub ring_indicator_ended: bool,
+
+    /// Data carrier detect has changed.
+    pub data_carrier_detect_changed: bool,
+}
+
 /// UART channel mode.
 #[derive(Clone, Copy)]
 pub enum ChannelMode {
@@ -275,14 +532,19 @@ impl ChannelMode {
         }
     }
 
-    pub fn from_u32(value: u32) -> Result<Self, ()> {
+    /// Transform from unsigned 32-bit integer.
+    ///
+    /// # Errors
+    ///
+    /// - Value is reserved.
+    pub fn from_u32(value: u32) -> Result<Self, Error> {
         let result = match value {
             0b00 => Self::Normal,
             0b01 => Self::AutomaticEcho,
             0b10 => Self::LocalLoopback,
             0b11 => Self::RemoteLoopback,
-            _ => {
-                return Err(());
+            other => {
+                return Err(Error::ReservedValue(other));
             }
         };
         Ok(result)
@@ -382,7 +644,7 @@ pub enum Interrupt {
     /// Transmitter interrupt.
     Transmitter(TransmitterInterrupt),
 
-    /// TODO: what is this?
+    /// Modem status changed: clear to send, data set ready, ring indicator or data carrier detect.
     ModemIndicator,
 }
 
@@ -399,6 +661,62 @@ impl Interrupt {
     }
 }
 
+/// UART channel status.
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ChannelStatus {
+    /// Receiver FIFO has reached trigger level.
+    pub receiver_fifo_trigger: bool,
+
+    /// Receiver FIFO is empty.
+    pub receiver_fifo_empty: bool,
+
+    /// Receiver FIFO is full.
+    pub receiver_fifo_full: bool,
+
+    /// Transmitter FIFO is empty.
+    pub transmitter_fifo_empty: bool,
+
+    /// Transmitter FIFO is full.
+    pub transmitter_fifo_full: bool,
+
+    /// Receiver is active.
+    pub receiver_active: bool,
+
+    /// Transmitter is active.
+    pub transmitter_active: bool,
+
+    /// Receiver flow delay trigger level has been reached.
+    pub receiver_flow_delay_trigger: bool,
+
+    /// Transmitter FIFO has reached trigger level.
+    pub transmitter_fifo_trigger: bool,
+
+    /// Transmitter FIFO is nearly full.
+    pub transmitter_fifo_nearly_full: bool,
+}
+
+impl ChannelStatus {
+    /// Create channel status from register value.
+    #[inline]
+    #[must_use]
+    pub fn new(value: u32) -> Self {
+        let value = Value::<channel_status::Spec>::from_bits(value);
+        Self {
+            receiver_fifo_trigger: value.is_set(channel_status::RTRIG),
+            receiver_fifo_empty: value.is_set(channel_status::REMPTY),
+            receiver_fifo_full: value.is_set(channel_status::RFUL),
+            transmitter_fifo_empty: value.is_set(channel_status::TEMPTY),
+            transmitter_fifo_full: value.is_set(channel_status::TFUL),
+            receiver_active: value.is_set(channel_status::RACTIVE),
+            transmitter_active: value.is_set(channel_status::TACTIVE),
+            receiver_flow_delay_trigger: value.is_set(channel_status::FDELT),
+            transmitter_fifo_trigger: value.is_set(channel_status::TTRIG),
+            transmitter_fifo_nearly_full: value.is_set(channel_status::TNFUL),
+        }
+    }
+}
+
 /// UART interrupt's causes.
 #[allow(clippy::struct_excessive_bools)]
 #[derive(Clone, Copy, Debug)]
@@ -430,7 +748,7 @@ pub struct InterruptCauses {
     /// Receiver timeout counter reached zero.
     pub receiver_timeout: bool,
 
-    /// TODO: what is this?
+    /// Modem status changed: clear to send, data set ready, ring indicator or data carrier detect.
     pub modem_indicator: bool,
 
     /// Transmitter FIFO level reached given trigger level.
@@ -467,245 +785,239 @@ impl InterruptCauses {
 }
 
 /// Interface for UART peripheral.
-pub struct Uart {
+pub struct Uart<B = Mmio> {
     /// Peripheral index.
     index: DeviceIndex,
 
-    /// UART control register.
-    address_control: *mut u32,
-
-    /// UART mode register.
-    address_mode: *mut u32,
-
-    /// Interrupt enable register.
-    address_interrupt_enable: *mut u32,
-
-    /// Interrupt disable register.
-    address_interrupt_disable: *mut u32,
-
-    /// Interrupt mask register.
-    address_interrupt_mask: *mut u32,
-
-    /// Channel interrupt status register.
-    address_channel_interrupt_status: *mut u32,
-
-    /// Baud rate generator register.
-    address_baud_rate_generator: *mut u32,
-
-    /// Receiver timeout register.
-    address_receiver_timeout: *mut u32,
-
-    /// Receiver FIFO trigger level register.
-    address_receiver_fifo_trigger_level: *mut u32,
-
-    /// Modem control register.
-    address_modem_control: *mut u32,
-
-    /// Modem status register.
-    address_modem_status: *mut u32,
-
-    /// Channel status register.
-    address_channel_status: *mut u32,
-
-    /// Transmit and receive FIFO.
-    address_transmit_and_receive_fifo: *mut u32,
+    /// UART registers.
+    registers: UartRegisters<B>,
+}
 
-    /// Baud rate divider register.
-    address_baud_rate_divider: *mut u32,
+impl<B> Uart<B> {
+    /// Create interface for given UART peripheral using given register access.
+    #[must_use]
+    pub const fn with_bus(index: DeviceIndex, bus: B) -> Self {
+        let base = match index {
+            DeviceIndex::Uart0 => ADDRESS_UART0_BASE,
+            DeviceIndex::Uart1 => ADDRESS_UART1_BASE,
+        };
+        Self {
+            index,
+            registers: UartRegisters::new(base, bus),
+        }
+    }
 
-    /// Flow control delay register.
-    address_flow_control_delay: *mut u32,
+    /// Peripheral index.
+    #[inline]
+    #[must_use]
+    pub const fn index(&self) -> DeviceIndex {
+        self.index
+    }
 
-    /// Transmitter FIFO trigger level register.
-    address_transmitter_fifo_trigger_level: *mut u32,
+    /// UART registers.
+    #[inline]
+    #[must_use]
+    pub fn registers(&self) -> &UartRegisters<B> {
+        &self.registers
+    }
 }
 
-impl Uart {
+impl<B: Bus> Uart<B> {
     /// Receiver logic is reset and all pending receiver data is discarded.
     #[inline]
     pub fn reset_receiver(&self) {
-        set_address_bit(self.address_control, 0);
         // Bit is cleared automatically.
+        self.registers
+            .control()
+            .modify(|r| r.set(control::RXRST, true));
     }
 
     /// Transmitter logic is reset and all pending transmitter data is discarded.
     #[inline]
     pub fn reset_transmitter(&self) {
-        set_address_bit(self.address_control, 1);
         // Bit is cleared automatically.
+        self.registers
+            .control()
+            .modify(|r| r.set(control::TXRST, true));
     }
 
     /// Enable or disable receiving.
+    ///
+    /// Receiver is disabled with its disable bit, which overrides the enable bit.
     #[inline]
     pub fn toggle_receiving(&self, enable: bool) {
-        let action = if enable {
-            set_address_bit
-        } else {
-            clear_address_bit
-        };
-        action(self.address_control, 2);
+        self.registers
+            .control()
+            .modify(|r| r.set(control::RXEN, enable).set(control::RXDIS, !enable));
     }
 
-    // TODO: receiver disabled register
+    /// True if receiver is enabled.
+    #[inline]
+    #[must_use]
+    pub fn is_receiving(&self) -> bool {
+        let control = self.registers.control().read();
+        control.is_set(control::RXEN) && !control.is_set(control::RXDIS)
+    }
 
     /// Enable or disable transmitting.
+    ///
+    /// Transmitter is disabled with its disable bit, which overrides the enable bit.
     #[inline]
     pub fn toggle_transmitting(&self, enable: bool) {
-        let action = if enable {
-            set_address_bit
-        } else {
-            clear_address_bit
-        };
-        action(self.address_control, 4);
+        self.registers
+            .control()
+            .modify(|r| r.set(control::TXEN, enable).set(control::TXDIS, !enable));
     }
 
-    // TODO: transmitter disable register
+    /// True if transmitter is enabled.
+    #[inline]
+    #[must_use]
+    pub fn is_transmitting(&self) -> bool {
+        let control = self.registers.control().read();
+        control.is_set(control::TXEN) && !control.is_set(control::TXDIS)
+    }
 
-    // TODO: restart receiver timeout counter
+    /// Restart receiver timeout counter.
+    #[inline]
+    pub fn restart_receiver_timeout(&self) {
+        // Bit is cleared automatically.
+        self.registers
+            .control()
+            .modify(|r| r.set(control::RSTTO, true));
+    }
 
-    // TODO: start transmitter break
+    /// Start transmitting break.
+    ///
+    /// Transmitter holds the line low after transmitter FIFO is empty,
+    /// until break is [stopped](Self::stop_break).
+    #[inline]
+    pub fn start_break(&self) {
+        self.registers
+            .control()
+            .modify(|r| r.set(control::STPBRK, false).set(control::STTBRK, true));
+    }
 
-    // TODO: stop transmitter break
+    /// Stop transmitting break.
+    #[inline]
+    pub fn stop_break(&self) {
+        self.registers
+            .control()
+            .modify(|r| r.set(control::STTBRK, false).set(control::STPBRK, true));
+    }
+
+    /// Transmit break lasting given number of bit periods.
+    ///
+    /// Waits until transmitter FIFO is empty before starting break.
+    /// This function blocks.
+    ///
+    /// # Errors
+    ///
+    /// - Baud rate can not be solved, see [`Self::get_baud_rate`].
+    pub fn send_break<S: Bus, D: DelayNs>(
+        &self,
+        slcr: &Slcr<S>,
+        delay: &mut D,
+        bit_periods: u32,
+    ) -> Result<(), Error> {
+        let baud = self.get_baud_rate(slcr)?.max(1);
+        let nanoseconds = u64::from(bit_periods) * 1_000_000_000 / u64::from(baud);
+        let nanoseconds = u32::try_from(nanoseconds).unwrap_or(u32::MAX);
+        while self.is_transmitter_fifo_empty().not() || self.is_transmitter_active() {}
+        self.start_break();
+        delay.delay_ns(nanoseconds);
+        self.stop_break();
+        Ok(())
+    }
 
     /// Get parity bit configuration.
-    pub fn get_parity(&self) -> Result<ParityType, ()> {
-        let address = self.address_mode;
-        let value = read_from_address(address);
-        let mut result = 0u32;
-        for (index, bit_index) in (3..=5u32).into_iter().enumerate() {
-            if value.read_bit(bit_index) {
-                result.set_bit(index as u32);
-            }
-        }
-        ParityType::from_u32(result)
+    pub fn get_parity(&self) -> Result<ParityType, Error> {
+        let value = self.registers.mode().read().read(mode::PAR);
+        ParityType::from_u32(value)
     }
 
     /// Set parity bit configuration.
     #[inline]
     pub fn set_parity(&self, value: ParityType) {
-        for (index, bit_index) in (3..=5).into_iter().enumerate() {
-            let action = if value.as_u32().read_bit(index as u32) {
-                set_address_bit
-            } else {
-                clear_address_bit
-            };
-            action(self.address_mode, bit_index);
-        }
+        self.registers
+            .mode()
+            .modify(|r| r.write(mode::PAR, value.as_u32()));
     }
 
     /// Get clock source configuration.
     pub fn get_clock_source(&self) -> ClockSource {
-        let address = self.address_mode;
-        let value = read_from_address(address);
-        let result = value.read_bit(0);
-        ClockSource::from_bool(result)
+        let value = self.registers.mode().read().is_set(mode::CLKS);
+        ClockSource::from_bool(value)
     }
 
     /// Set clock source configuration.
     #[inline]
     pub fn set_clock_source(&self, value: ClockSource) {
-        let action = if value.as_bool() {
-            set_address_bit
-        } else {
-            clear_address_bit
-        };
-        action(self.address_mode, 0);
+        self.registers
+            .mode()
+            .modify(|r| r.set(mode::CLKS, value.as_bool()));
     }
 
     /// Get character length configuration.
-    pub fn get_character_length(&self) -> Result<CharacterLength, ()> {
-        let address = self.address_mode;
-        let value = read_from_address(address);
-        let mut result = 0u32;
-        for (index, bit_index) in (1..=2u32).into_iter().enumerate() {
-            if value.read_bit(bit_index) {
-                result.set_bit(index as u32);
-            }
-        }
-        CharacterLength::from_u32(result)
+    pub fn get_character_length(&self) -> Result<CharacterLength, Error> {
+        let value = self.registers.mode().read().read(mode::CHRL);
+        CharacterLength::from_u32(value)
     }
 
     /// Set character length configuration.
     #[inline]
     pub fn set_character_length(&self, value: CharacterLength) {
-        for (index, bit_index) in (1..=2).into_iter().enumerate() {
-            let action = if value.as_u32().read_bit(index as u32) {
-                set_address_bit
-            } else {
-                clear_address_bit
-            };
-            action(self.address_mode, bit_index);
-        }
+        self.registers
+            .mode()
+            .modify(|r| r.write(mode::CHRL, value.as_u32()));
     }
 
     /// Get stop bits configuration.
-    pub fn get_stop_bits(&self) -> Result<StopBits, ()> {
-        let address = self.address_mode;
-        let value = read_from_address(address);
-        let mut result = 0u32;
-        for (index, bit_index) in (6..=7u32).into_iter().enumerate() {
-            if value.read_bit(bit_index) {
-                result.set_bit(index as u32);
-            }
-        }
-        StopBits::from_u32(result)
+    pub fn get_stop_bits(&self) -> Result<StopBits, Error> {
+        let value = self.registers.mode().read().read(mode::NBSTOP);
+        StopBits::from_u32(value)
     }
 
     /// Set stop bits configuration.
     #[inline]
     pub fn set_stop_bits(&self, value: StopBits) {
-        for (index, bit_index) in (6..=7).into_iter().enumerate() {
-            let action = if value.as_u32().read_bit(index as u32) {
-                set_address_bit
-            } else {
-                clear_address_bit
-            };
-            action(self.address_mode, bit_index);
-        }
+        self.registers
+            .mode()
+            .modify(|r| r.write(mode::NBSTOP, value.as_u32()));
     }
 
     /// Get channel mode configuration.
-    pub fn get_channel_mode(&self) -> Result<ChannelMode, ()> {
-        let address = self.address_mode;
-        let value = read_from_address(address);
-        let mut result = 0u32;
-        for (index, bit_index) in (8..=9u32).into_iter().enumerate() {
-            if value.read_bit(bit_index) {
-                result.set_bit(index as u32);
-            }
-        }
-        ChannelMode::from_u32(result)
+    pub fn get_channel_mode(&self) -> Result<ChannelMode, Error> {
+        let value = self.registers.mode().read().read(mode::CHMODE);
+        ChannelMode::from_u32(value)
     }
 
     /// Set channel mode configuration.
     #[inline]
     pub fn set_channel_mode(&self, value: ChannelMode) {
-        for (index, bit_index) in (8..=9).into_iter().enumerate() {
-            let action = if value.as_u32().read_bit(index as u32) {
-                set_address_bit
-            } else {
-                clear_address_bit
-            };
-            action(self.address_mode, bit_index);
-        }
+        self.registers
+            .mode()
+            .modify(|r| r.write(mode::CHMODE, value.as_u32()));
     }
 
     /// True if given interrupt is enabled.
     pub fn is_interrupt_enabled(&self, interrupt: Interrupt) -> bool {
-        let index = interrupt.as_index();
-        read_address_bit(self.address_interrupt_mask, index)
+        let field = interrupt_mask::interrupt(interrupt.as_index());
+        self.registers.interrupt_mask().read().is_set(field)
     }
 
     /// Helper for enabling and disabling interrupts.
     #[inline]
     pub fn toggle_interrupt(&self, interrupt: Interrupt, enable: bool) {
-        let address = if enable {
-            self.address_interrupt_enable
-        } else {
-            self.address_interrupt_disable
-        };
         let index = interrupt.as_index();
-        set_address_bit(address, index);
+        if enable {
+            self.registers
+                .interrupt_enable()
+                .write_with_zero(|r| r.set(interrupt_enable::interrupt(index), true));
+        } else {
+            self.registers
+                .interrupt_disable()
+                .write_with_zero(|r| r.set(interrupt_disable::interrupt(index), true));
+        }
     }
 
     /// Read interrupt causes.
@@ -714,7 +1026,7 @@ impl Uart {
     #[inline]
     #[must_use]
     pub fn read_unmasked_interrupt_causes(&self) -> InterruptCauses {
-        let unmasked = read_from_address(self.address_channel_interrupt_status);
+        let unmasked = self.registers.channel_interrupt_status().read().bits();
         InterruptCauses::new(unmasked)
     }
 
@@ -724,8 +1036,8 @@ impl Uart {
     #[inline]
     #[must_use]
     pub fn read_interrupt_causes(&self) -> InterruptCauses {
-        let unmasked = read_from_address(self.address_channel_interrupt_status);
-        let mask = read_from_address(self.address_interrupt_mask);
+        let unmasked = self.registers.channel_interrupt_status().read().bits();
+        let mask = self.registers.interrupt_mask().read().bits();
         let value = unmasked.bitand(mask);
         InterruptCauses::new(value)
     }
@@ -733,84 +1045,290 @@ impl Uart {
     /// Clear given interrupt.
     #[inline]
     pub fn clear_interrupt(&self, interrupt: Interrupt) {
-        let index = interrupt.as_index();
-        set_address_bit(self.address_channel_interrupt_status, index);
+        let field = channel_interrupt_status::interrupt(interrupt.as_index());
+        self.registers
+            .channel_interrupt_status()
+            .write_with_zero(|r| r.set(field, true));
     }
 
     /// Clear all interrupts.
     pub fn clear_all_interrupts(&self) {
-        write_to_address(self.address_channel_interrupt_status, 0xFFFF_FFFF);
+        self.registers
+            .channel_interrupt_status()
+            .write_bits(0xFFFF_FFFF);
     }
 
     // TODO: order registers
 
-    // TODO: baud rate register
+    /// Get baud rate generator's clock divisor and baud rate divider.
+    #[must_use]
+    pub fn get_baud_rate_dividers(&self) -> (u16, u8) {
+        let cd = self
+            .registers
+            .baud_rate_generator()
+            .read()
+            .read(baud_rate_generator::CD);
+        let bdiv = self
+            .registers
+            .baud_rate_divider()
+            .read()
+            .read(baud_rate_divider::BDIV);
+        (cd as u16, bdiv as u8)
+    }
+
+    /// Get baud rate produced by current dividers.
+    ///
+    /// # Errors
+    ///
+    /// - UART reference clock frequency can not be solved.
+    pub fn get_baud_rate<S: Bus>(&self, slcr: &Slcr<S>) -> Result<u32, Error> {
+        let (cd, bdiv) = self.get_baud_rate_dividers();
+        let reference_clock = slcr.uart_reference_clock_frequency()?;
+        Ok(baud_rate::rate(
+            reference_clock,
+            self.get_clock_source(),
+            cd,
+            bdiv,
+        ))
+    }
+
+    /// Set baud rate.
+    ///
+    /// Dividers are solved from the UART reference clock
+    /// and the [clock source](Self::get_clock_source).
+    /// Receiver and transmitter are disabled while dividers are written and reset,
+    /// so pending data is discarded. Afterwards they are enabled if they were enabled before.
+    ///
+    /// Returns achieved baud rate.
+    ///
+    /// # Errors
+    ///
+    /// - UART reference clock frequency can not be solved.
+    /// - Baud rate can not be generated within [tolerance](baud_rate::TOLERANCE_PERCENT).
+    pub fn set_baud_rate<S: Bus>(&self, slcr: &Slcr<S>, baud: u32) -> Result<BaudRate, Error> {
+        let reference_clock = slcr.uart_reference_clock_frequency()?;
+        let solution = baud_rate::solve(reference_clock, self.get_clock_source(), baud)?;
+
+        let receiving = self.is_receiving();
+        let transmitting = self.is_transmitting();
+        self.toggle(false);
+        self.registers
+            .baud_rate_generator()
+            .write(|r| r.write(baud_rate_generator::CD, solution.cd as u32));
+        self.registers
+            .baud_rate_divider()
+            .write(|r| r.write(baud_rate_divider::BDIV, solution.bdiv as u32));
+        self.reset_receiver();
+        self.reset_transmitter();
+        self.toggle_receiving(receiving);
+        self.toggle_transmitting(transmitting);
+        Ok(solution)
+    }
+
+    /// Get receiver timeout in bit periods.
+    ///
+    /// Zero means timeout is disabled.
+    #[must_use]
+    pub fn get_receiver_timeout(&self) -> u32 {
+        let value = self
+            .registers
+            .receiver_timeout()
+            .read()
+            .read(receiver_timeout::RTO);
+        value * 4
+    }
+
+    /// Set how many bit periods the line must be idle before receiver times out.
+    ///
+    /// Timeout has resolution of four bit periods, value is rounded up.
+    /// Zero disables timeout.
+    ///
+    /// # Errors
+    ///
+    /// - Value is over [`RECEIVER_TIMEOUT_MAX`].
+    pub fn set_receiver_timeout(&self, bit_periods: u32) -> Result<(), Error> {
+        if RECEIVER_TIMEOUT_MAX < bit_periods {
+            return Err(Error::InvalidReceiverTimeout(bit_periods));
+        }
+        let value = bit_periods.div_ceil(4);
+        self.registers
+            .receiver_timeout()
+            .write(|r| r.write(receiver_timeout::RTO, value));
+        Ok(())
+    }
 
-    // TODO: timeout
+    /// True if receiver has timed out since timeout interrupt was cleared.
+    #[must_use]
+    pub fn is_receiver_timed_out(&self) -> bool {
+        let field = channel_interrupt_status::interrupt(ReceiverInterrupt::Timeout.as_index());
+        self.registers
+            .channel_interrupt_status()
+            .read()
+            .is_set(field)
+    }
 
     /// Get at what transmitter FIFO buffer value an interrupt is generated.
     pub fn get_transmitter_fifo_trigger_value(&self) -> u32 {
-        let address = self.address_transmitter_fifo_trigger_level;
-        let mut result = 0;
-        for index in 0..=5 {
-            if read_address_bit(address, index) {
-                result.set_bit(index);
-            }
-        }
-        result
+        let register = self.registers.transmitter_fifo_trigger_level();
+        register.read().read(transmitter_fifo_trigger_level::TTRIG)
     }
 
     /// Set at what transmitter FIFO buffer value an interrupt is generated.
+    ///
+    /// # Errors
+    ///
+    /// - Value is over 63.
     #[inline]
-    pub fn set_transmitter_fifo_trigger_value(&self, value: u32) -> Result<(), ()> {
+    pub fn set_transmitter_fifo_trigger_value(&self, value: u32) -> Result<(), Error> {
         if (0..=63).contains(&value).not() {
-            return Err(());
+            return Err(Error::InvalidTriggerValue(value));
         }
-        // TODO: fix memacc error with this
-        for index in 0..=5 {
-            let action = if value.read_bit(index) {
-                set_address_bit
-            } else {
-                clear_address_bit
-            };
-            action(self.address_transmitter_fifo_trigger_level, index);
+        self.registers
+            .transmitter_fifo_trigger_level()
+            .modify(|r| r.write(transmitter_fifo_trigger_level::TTRIG, value));
+        Ok(())
+    }
+
+    /// Get flow control mode.
+    #[must_use]
+    pub fn get_flow_control(&self) -> FlowControl {
+        let value = self
+            .registers
+            .modem_control()
+            .read()
+            .is_set(modem_control::FCM);
+        FlowControl::from_bool(value)
+    }
+
+    /// Set flow control mode.
+    ///
+    /// Modem signals are routed through EMIO, so they must be connected in programmable logic.
+    #[inline]
+    pub fn set_flow_control(&self, value: FlowControl) {
+        self.registers
+            .modem_control()
+            .modify(|r| r.set(modem_control::FCM, value.as_bool()));
+    }
+
+    /// Get receiver FIFO level at which hardware flow control deasserts request to send.
+    pub fn get_flow_control_delay(&self) -> u32 {
+        let register = self.registers.flow_control_delay();
+        register.read().read(flow_control_delay::FDEL)
+    }
+
+    /// Set receiver FIFO level at which hardware flow control deasserts request to send.
+    ///
+    /// Values below 4 disable the trigger.
+    ///
+    /// # Errors
+    ///
+    /// - Value is over 63.
+    pub fn set_flow_control_delay(&self, value: u32) -> Result<(), Error> {
+        if (0..=63).contains(&value).not() {
+            return Err(Error::InvalidTriggerValue(value));
         }
+        self.registers
+            .flow_control_delay()
+            .modify(|r| r.write(flow_control_delay::FDEL, value));
         Ok(())
     }
 
-    // TODO: modem registers
+    /// Assert or deassert data terminal ready.
+    #[inline]
+    pub fn set_data_terminal_ready(&self, assert: bool) {
+        self.registers
+            .modem_control()
+            .modify(|r| r.set(modem_control::DTR, assert));
+    }
+
+    /// Assert or deassert request to send.
+    ///
+    /// Has no effect while [hardware flow control](FlowControl::Hardware) is used.
+    #[inline]
+    pub fn set_request_to_send(&self, assert: bool) {
+        self.registers
+            .modem_control()
+            .modify(|r| r.set(modem_control::RTS, assert));
+    }
+
+    /// Read modem signals and their changes.
+    #[must_use]
+    pub fn modem_status(&self) -> ModemStatus {
+        let value = self.registers.modem_status().read();
+        ModemStatus {
+            clear_to_send: value.is_set(modem_status::CTS),
+            data_set_ready: value.is_set(modem_status::DSR),
+            ring_indicator: value.is_set(modem_status::RI),
+            data_carrier_detect: value.is_set(modem_status::DCD),
+            clear_to_send_changed: value.is_set(modem_status::DCTS),
+            data_set_ready_changed: value.is_set(modem_status::DDSR),
+            ring_indicator_ended: value.is_set(modem_status::TERI),
+            data_carrier_detect_changed: value.is_set(modem_status::DDCD),
+        }
+    }
+
+    /// Clear modem signal changes.
+    ///
+    /// Also clear [`Interrupt::ModemIndicator`] to acknowledge the interrupt.
+    #[inline]
+    pub fn clear_modem_status_changes(&self) {
+        self.registers.modem_status().write_with_zero(|r| {
+            r.set(modem_status::DCTS, true)
+                .set(modem_status::DDSR, true)
+                .set(modem_status::TERI, true)
+                .set(modem_status::DDCD, true)
+        });
+    }
 
     /// Get at what receiver FIFO buffer value an interrupt is generated.
     pub fn get_receiver_fifo_trigger_value(&self) -> u32 {
-        let address = self.address_receiver_fifo_trigger_level;
-        let mut result = 0;
-        for index in 0..=5 {
-            if read_address_bit(address, index) {
-                result.set_bit(index);
-            }
-        }
-        result
+        let register = self.registers.receiver_fifo_trigger_level();
+        register.read().read(receiver_fifo_trigger_level::RTRIG)
     }
 
     /// Set at what receiver FIFO buffer value an interrupt is generated.
+    ///
+    /// # Errors
+    ///
+    /// - Value is over 63.
     #[inline]
-    pub fn set_receiver_fifo_trigger_value(&self, value: u32) -> Result<(), ()> {
+    pub fn set_receiver_fifo_trigger_value(&self, value: u32) -> Result<(), Error> {
         if (0..=63).contains(&value).not() {
-            return Err(());
-        }
-        // TODO: fix memacc error with this
-        for index in 0..=5 {
-            let action = if value.read_bit(index) {
-                set_address_bit
-            } else {
-                clear_address_bit
-            };
-            action(self.address_receiver_fifo_trigger_level, index);
+            return Err(Error::InvalidTriggerValue(value));
         }
+        self.registers
+            .receiver_fifo_trigger_level()
+            .modify(|r| r.write(receiver_fifo_trigger_level::RTRIG, value));
         Ok(())
     }
 
-    // TODO: channel status register
+    /// Read channel status.
+    #[inline]
+    #[must_use]
+    pub fn channel_status(&self) -> ChannelStatus {
+        let value = self.registers.channel_status().read().bits();
+        ChannelStatus::new(value)
+    }
+
+    /// Get receive error detected since error was last reported.
+    ///
+    /// Error is not cleared.
+    #[must_use]
+    pub fn pending_receive_error(&self) -> Option<Error> {
+        let value = self.registers.channel_interrupt_status().read();
+        let is_set = |interrupt: ReceiverInterrupt| {
+            value.is_set(channel_interrupt_status::interrupt(interrupt.as_index()))
+        };
+        if is_set(ReceiverInterrupt::FifoOverflow) {
+            Some(Error::Overrun)
+        } else if is_set(ReceiverInterrupt::FramingError) {
+            Some(Error::Framing)
+        } else if is_set(ReceiverInterrupt::ParityError) {
+            Some(Error::Parity)
+        } else {
+            None
+        }
+    }
 
     /*
     // TODO: reset registers
@@ -823,86 +1341,120 @@ impl Uart {
     fn reset(&self) {
         self.reset_receiver();
         self.reset_transmitter();
-        write_to_address(self.address_control, 0x128);
-        write_to_address(self.address_mode, 0);
-        write_to_address(self.address_interrupt_disable, 0xFFFF_FFFF);
-        write_to_address(self.address_channel_interrupt_status, 0xFFFF_FFFF);
+        self.registers.control().reset();
+        self.registers.mode().reset();
+        self.registers.interrupt_disable().write_bits(0xFFFF_FFFF);
+        self.registers.channel_interrupt_status().write_bits(0xFFFF_FFFF);
         // TODO
-        write_to_address(self.address_receiver_fifo_trigger_level, 0x20);
+        self.registers.receiver_fifo_trigger_level().reset();
         // TODO
-        write_to_address(self.address_transmitter_fifo_trigger_level, 0x20);
+        self.registers.transmitter_fifo_trigger_level().reset();
     }
     */
 
     /// Enable or disable receiver and transmitter.
     #[inline]
     pub fn toggle(&self, enable: bool) {
-        self.toggle_receiving(enable);
-        self.toggle_transmitting(enable);
+        self.registers.control().modify(|r| {
+            r.set(control::RXEN, enable)
+                .set(control::RXDIS, !enable)
+                .set(control::TXEN, enable)
+                .set(control::TXDIS, !enable)
+        });
     }
 
     /// True if transmitter FIFO is nearly full.
     #[must_use]
     pub fn is_transmitter_fifo_nearly_full(&self) -> bool {
-        read_address_bit(self.address_channel_status, 14)
+        self.registers
+            .channel_status()
+            .read()
+            .is_set(channel_status::TNFUL)
     }
 
     /// True if transmitter FIFO trigger level has been reached.
     #[must_use]
     pub fn is_transmitter_fifo_trigger_reached(&self) -> bool {
-        read_address_bit(self.address_channel_status, 13)
+        self.registers
+            .channel_status()
+            .read()
+            .is_set(channel_status::TTRIG)
     }
 
     /// True if receiver flow delay trigger level has been reached.
     #[must_use]
     pub fn is_receiver_flow_delay_trigger_reached(&self) -> bool {
-        read_address_bit(self.address_channel_status, 12)
+        self.registers
+            .channel_status()
+            .read()
+            .is_set(channel_status::FDELT)
     }
 
     /// True if transmitter is currently active.
     #[must_use]
     pub fn is_transmitter_active(&self) -> bool {
-        read_address_bit(self.address_channel_status, 11)
+        self.registers
+            .channel_status()
+            .read()
+            .is_set(channel_status::TACTIVE)
     }
 
     /// True if receiver is currently active.
     #[must_use]
     pub fn is_receiver_active(&self) -> bool {
-        read_address_bit(self.address_channel_status, 10)
+        self.registers
+            .channel_status()
+            .read()
+            .is_set(channel_status::RACTIVE)
     }
 
     /// True if transmitter FIFO is full.
     #[inline]
     #[must_use]
     pub fn is_transmitter_fifo_full(&self) -> bool {
-        read_address_bit(self.address_channel_status, 4)
+        self.registers
+            .channel_status()
+            .read()
+            .is_set(channel_status::TFUL)
     }
 
     /// True if transmitter FIFO is empty.
     #[inline]
     #[must_use]
     pub fn is_transmitter_fifo_empty(&self) -> bool {
-        read_address_bit(self.address_channel_status, 3)
+        self.registers
+            .channel_status()
+            .read()
+            .is_set(channel_status::TEMPTY)
     }
 
     /// True if receiver FIFO is full.
     #[inline]
     #[must_use]
     pub fn is_receiver_fifo_full(&self) -> bool {
-        read_address_bit(self.address_channel_status, 2)
+        self.registers
+            .channel_status()
+            .read()
+            .is_set(channel_status::RFUL)
     }
 
     /// True if receiver FIFO is empty.
     #[inline]
     #[must_use]
     pub fn is_receiver_fifo_empty(&self) -> bool {
-        read_address_bit(self.address_channel_status, 1)
+        self.registers
+            .channel_status()
+            .read()
+            .is_set(channel_status::REMPTY)
     }
 
     /// True if receiver FIFO has reached trigger level.
     #[must_use]
     pub fn is_receiver_fifo_trigger_reached(&self) -> bool {
-        read_address_bit(self.address_channel_status, 0)
+        self.registers
+            .channel_status()
+            .read()
+            .is_set(channel_status::RTRIG)
     }
 
     /// Configure UART with default configuration.
@@ -918,30 +1470,23 @@ impl Uart {
     ///
     /// - System level control registers are locked and they can not be unlocked.
     #[inline]
-    #[must_use]
-    pub fn configure(&self) -> Result<(), ()> {
+    pub fn configure<S: Bus>(&self, slcr: &Slcr<S>) -> Result<(), Error> {
         use crate::peripheral::slcr::AmbaClockControl;
-        use crate::peripheral::slcr::SLCR;
 
         // Check that system level control registers are unlocked.
-        if unsafe { SLCR.is_system_level_configuration_registers_locked() } {
-            unsafe { SLCR.toggle_system_level_configuration_registers(false) };
-        }
-        if unsafe { SLCR.is_system_level_configuration_registers_locked() } {
-            return Err(());
-        }
+        slcr.unlock_system_level_configuration_registers()?;
         // Enable AMBA and reference clocks.
         let target = match self.index {
             DeviceIndex::Uart0 => AmbaClockControl::Uart0,
             DeviceIndex::Uart1 => AmbaClockControl::Uart1,
         };
-        unsafe { SLCR.toggle_amba_clocks(target, true) };
+        slcr.toggle_amba_clocks(target, true);
         match self.index {
             DeviceIndex::Uart0 => {
-                unsafe { SLCR.toggle_uart_0_reference_clock(true) };
+                slcr.toggle_uart_0_reference_clock(true);
             }
             DeviceIndex::Uart1 => {
-                unsafe { SLCR.toggle_uart_1_reference_clock(true) };
+                slcr.toggle_uart_1_reference_clock(true);
             }
         }
 
@@ -958,12 +1503,6 @@ impl Uart {
         Ok(())
     }
 
-    // TODO:
-    // host can do useful work when transmitting multiple bytes
-    // 1. send byte 0..N
-    // 2. FIFO is full, continue other work
-    // 3. FIFO empty interrupt -> send bytes N..M
-
     /// Transmit one byte.
     ///
     /// This function blocks.
@@ -971,7 +1510,9 @@ impl Uart {
     pub fn transmit_byte(&self, byte: u8) {
         // Wait until transmit buffer has space for more bytes.
         while self.is_transmitter_fifo_full() {}
-        write_to_address(self.address_transmit_and_receive_fifo, byte as u32);
+        self.registers
+            .fifo()
+            .write(|r| r.write(fifo::DATA, byte as u32));
     }
 
     /// Transmit string.
@@ -999,7 +1540,7 @@ impl Uart {
     #[must_use]
     pub fn receive_byte(&self) -> u8 {
         while self.is_receiver_fifo_empty() {}
-        let value = read_from_address(self.address_transmit_and_receive_fifo);
+        let value = self.registers.fifo().read().read(fifo::DATA);
         value as u8
     }
 
@@ -1016,33 +1557,122 @@ impl Uart {
         if self.is_receiver_fifo_empty() {
             None
         } else {
-            let value = read_from_address(self.address_transmit_and_receive_fifo);
+            let value = self.registers.fifo().read().read(fifo::DATA);
             let byte = value as u8;
             Some(byte)
         }
     }
 
+    /// Try to receive one byte and report receive errors.
+    ///
+    /// Controller does not store errors per byte in receiver FIFO,
+    /// so error is reported before the first byte read after error was detected, and cleared.
+    ///
+    /// - Overflow is not tied to any byte, so receiver FIFO is not read
+    ///   and the next call returns the byte.
+    /// - Byte read with framing or parity error is discarded.
+    ///
+    /// # Errors
+    ///
+    /// - Receiver FIFO has overflowed.
+    /// - Byte has framing error.
+    /// - Byte has parity error.
+    pub fn try_receive_checked(&self) -> Option<Result<u8, Error>> {
+        if self.is_receiver_fifo_empty() {
+            return None;
+        }
+        let error = self.pending_receive_error();
+        if error == Some(Error::Overrun) {
+            self.clear_interrupt(Interrupt::Receiver(ReceiverInterrupt::FifoOverflow));
+            return Some(Err(Error::Overrun));
+        }
+        let byte = self.registers.fifo().read().read(fifo::DATA) as u8;
+        match error {
+            Some(error) => {
+                self.clear_interrupt(Interrupt::Receiver(ReceiverInterrupt::FramingError));
+                self.clear_interrupt(Interrupt::Receiver(ReceiverInterrupt::ParityError));
+                Some(Err(error))
+            }
+            None => Some(Ok(byte)),
+        }
+    }
+
+    /// Try to receive one byte or break.
+    ///
+    /// Break is received as a zero byte with framing error.
+    /// Framing error is cleared whenever it is set, so it is not reported with a later byte.
+    #[must_use]
+    pub fn try_receive(&self) -> Option<Received> {
+        if self.is_receiver_fifo_empty() {
+            return None;
+        }
+        let framing = Interrupt::Receiver(ReceiverInterrupt::FramingError);
+        let field = channel_interrupt_status::interrupt(framing.as_index());
+        let framing_error = self
+            .registers
+            .channel_interrupt_status()
+            .read()
+            .is_set(field);
+        let byte = self.registers.fifo().read().read(fifo::DATA) as u8;
+        if framing_error {
+            self.clear_interrupt(framing);
+        }
+        if framing_error && byte == 0 {
+            Some(Received::Break)
+        } else {
+            Some(Received::Byte(byte))
+        }
+    }
+
+    /// Receive bytes until the line has been idle for
+    /// [receiver timeout](Self::set_receiver_timeout).
+    ///
+    /// Blocks until first byte is received.
+    /// Bytes which do not fit to given buffer are discarded.
+    ///
+    /// Returns how many bytes were stored.
+    ///
+    /// # Errors
+    ///
+    /// - Receiver timeout is disabled, so the frame would never end.
+    pub fn receive_frame(&self, buffer: &mut [u8]) -> Result<usize, Error> {
+        if self.get_receiver_timeout() == 0 {
+            return Err(Error::ReceiverTimeoutDisabled);
+        }
+        let timeout = Interrupt::Receiver(ReceiverInterrupt::Timeout);
+        while self.is_receiver_fifo_empty() {}
+        self.clear_interrupt(timeout);
+        self.restart_receiver_timeout();
+        let mut count = 0;
+        loop {
+            if let Some(byte) = self.try_receive_byte() {
+                if let Some(slot) = buffer.get_mut(count) {
+                    *slot = byte;
+                    count += 1;
+                }
+            } else if self.is_receiver_timed_out() {
+                break;
+            }
+        }
+        self.clear_interrupt(timeout);
+        Ok(count)
+    }
+
     /*
     /// Clear all UART interrupt causes.
     #[inline]
     pub fn clear_all_interrupt_causes(&self) {
-        write_to_address(self.address_channel_interrupt_status, 0xFFFF_FFFF);
+        self.registers.channel_interrupt_status().write_bits(0xFFFF_FFFF);
     }
     */
-
-    // TODO: set baud rate
-    /*pub fn set_baud_rate(&self) {
-        self.toggle(false);
-        self.reset();
-    }*/
 }
 
-impl core::fmt::Display for Uart {
+impl<B: Bus> core::fmt::Display for Uart<B> {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         let rx_trigger = self.get_receiver_fifo_trigger_value();
         let tx_trigger = self.get_transmitter_fifo_trigger_value();
 
-        let interrupts = unsafe { self.address_interrupt_mask.read_volatile() };
+        let interrupts = self.registers.interrupt_mask().read().bits();
         write!(
             f,
             "{}, receiver: trigger {rx_trigger}, transmitter: trigger {tx_trigger}, interrupts: 0b{interrupts:0>32b}",
@@ -1051,87 +1681,370 @@ impl core::fmt::Display for Uart {
     }
 }
 
-impl core::fmt::Write for Uart {
+/// Saved UART configuration.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct UartState {
+    mode: u32,
+    interrupt_mask: u32,
+    baud_rate_generator: u32,
+    baud_rate_divider: u32,
+    receiver_timeout: u32,
+    receiver_fifo_trigger_level: u32,
+    transmitter_fifo_trigger_level: u32,
+    modem_control: u32,
+    flow_control_delay: u32,
+    receiving: bool,
+    transmitting: bool,
+}
+
+impl<B: Bus> Snapshot for Uart<B> {
+    type State = UartState;
+
+    fn save(&self) -> UartState {
+        UartState {
+            mode: self.registers.mode().read().bits(),
+            interrupt_mask: self.registers.interrupt_mask().read().bits(),
+            baud_rate_generator: self.registers.baud_rate_generator().read().bits(),
+            baud_rate_divider: self.registers.baud_rate_divider().read().bits(),
+            receiver_timeout: self.registers.receiver_timeout().read().bits(),
+            receiver_fifo_trigger_level: self.registers.receiver_fifo_trigger_level().read().bits(),
+            transmitter_fifo_trigger_level: self
+                .registers
+                .transmitter_fifo_trigger_level()
+                .read()
+                .bits(),
+            modem_control: self.registers.modem_control().read().bits(),
+            flow_control_delay: self.registers.flow_control_delay().read().bits(),
+            receiving: self.is_receiving(),
+            transmitting: self.is_transmitting(),
+        }
+    }
+
+    /// Restore UART configuration.
+    ///
+    /// Receiver and transmitter are reset, so pending data is discarded.
+    fn restore(&self, state: &UartState) {
+        self.toggle(false);
+        self.registers.interrupt_disable().write_bits(0xFFFF_FFFF);
+        self.registers.mode().write_bits(state.mode);
+        self.registers
+            .baud_rate_generator()
+            .write_bits(state.baud_rate_generator);
+        self.registers
+            .baud_rate_divider()
+            .write_bits(state.baud_rate_divider);
+        self.reset_receiver();
+        self.reset_transmitter();
+        self.registers
+            .receiver_timeout()
+            .write_bits(state.receiver_timeout);
+        self.registers
+            .receiver_fifo_trigger_level()
+            .write_bits(state.receiver_fifo_trigger_level);
+        self.registers
+            .transmitter_fifo_trigger_level()
+            .write_bits(state.transmitter_fifo_trigger_level);
+        self.registers
+            .modem_control()
+            .write_bits(state.modem_control);
+        self.registers
+            .flow_control_delay()
+            .write_bits(state.flow_control_delay);
+        self.clear_all_interrupts();
+        self.registers
+            .interrupt_enable()
+            .write_bits(state.interrupt_mask);
+        self.toggle_receiving(state.receiving);
+        self.toggle_transmitting(state.transmitting);
+    }
+}
+
+impl<B: Bus> core::fmt::Write for Uart<B> {
     fn write_str(&mut self, s: &str) -> core::fmt::Result {
-        unsafe { UART0.transmit_string(s) }
+        self.transmit_string(s);
         Ok(())
     }
 }
 
-/// UART 0 base address.
-const ADDRESS_UART0_BASE: u32 = 0xE000_0000;
-/// UART 1 base address.
-const ADDRESS_UART1_BASE: u32 = 0xE000_1000;
+impl embedded_io::Error for Error {
+    fn kind(&self) -> embedded_io::ErrorKind {
+        match self {
+            Self::Overrun | Self::Framing | Self::Parity => embedded_io::ErrorKind::InvalidData,
+            _ => embedded_io::ErrorKind::Other,
+        }
+    }
+}
 
-/// UART 0 peripheral.
-pub static mut UART0: Uart = Uart {
-    index: DeviceIndex::Uart0,
-    address_control: (ADDRESS_UART0_BASE + 0x00) as *mut u32,
-    address_mode: (ADDRESS_UART0_BASE + 0x04) as *mut u32,
-    address_interrupt_enable: (ADDRESS_UART0_BASE + 0x08) as *mut u32,
-    address_interrupt_disable: (ADDRESS_UART0_BASE + 0x0C) as *mut u32,
-    address_interrupt_mask: (ADDRESS_UART0_BASE + 0x10) as *mut u32,
-    address_channel_interrupt_status: (ADDRESS_UART0_BASE + 0x14) as *mut u32,
-    address_baud_rate_generator: (ADDRESS_UART0_BASE + 0x18) as *mut u32,
-    address_receiver_timeout: (ADDRESS_UART0_BASE + 0x1C) as *mut u32,
-    address_receiver_fifo_trigger_level: (ADDRESS_UART0_BASE + 0x20) as *mut u32,
-    address_modem_control: (ADDRESS_UART0_BASE + 0x24) as *mut u32,
-    address_modem_status: (ADDRESS_UART0_BASE + 0x28) as *mut u32,
-    address_channel_status: (ADDRESS_UART0_BASE + 0x2C) as *mut u32,
-    address_transmit_and_receive_fifo: (ADDRESS_UART0_BASE + 0x30) as *mut u32,
-    address_baud_rate_divider: (ADDRESS_UART0_BASE + 0x34) as *mut u32,
-    address_flow_control_delay: (ADDRESS_UART0_BASE + 0x38) as *mut u32,
-    address_transmitter_fifo_trigger_level: (ADDRESS_UART0_BASE + 0x44) as *mut u32,
-};
-
-/// UART 1 peripheral.
-pub static mut UART1: Uart = Uart {
-    index: DeviceIndex::Uart1,
-    address_control: (ADDRESS_UART1_BASE + 0x00) as *mut u32,
-    address_mode: (ADDRESS_UART1_BASE + 0x04) as *mut u32,
-    address_interrupt_enable: (ADDRESS_UART1_BASE + 0x08) as *mut u32,
-    address_interrupt_disable: (ADDRESS_UART1_BASE + 0x0C) as *mut u32,
-    address_interrupt_mask: (ADDRESS_UART1_BASE + 0x10) as *mut u32,
-    address_channel_interrupt_status: (ADDRESS_UART1_BASE + 0x14) as *mut u32,
-    address_baud_rate_generator: (ADDRESS_UART1_BASE + 0x18) as *mut u32,
-    address_receiver_timeout: (ADDRESS_UART1_BASE + 0x1C) as *mut u32,
-    address_receiver_fifo_trigger_level: (ADDRESS_UART1_BASE + 0x20) as *mut u32,
-    address_modem_control: (ADDRESS_UART1_BASE + 0x24) as *mut u32,
-    address_modem_status: (ADDRESS_UART1_BASE + 0x28) as *mut u32,
-    address_channel_status: (ADDRESS_UART1_BASE + 0x2C) as *mut u32,
-    address_transmit_and_receive_fifo: (ADDRESS_UART1_BASE + 0x30) as *mut u32,
-    address_baud_rate_divider: (ADDRESS_UART1_BASE + 0x34) as *mut u32,
-    address_flow_control_delay: (ADDRESS_UART1_BASE + 0x38) as *mut u32,
-    address_transmitter_fifo_trigger_level: (ADDRESS_UART1_BASE + 0x44) as *mut u32,
-};
-
-/// Print formatted string using [`UART0`](crate::peripheral::uart::UART0).
-#[macro_export]
-macro_rules! sprint {
-    ($s:expr) => {
-        #[allow(unused_imports)]
-        use core::fmt::*;
-        unsafe {
-            write!($crate::peripheral::uart::UART0, $s).unwrap();
+impl embedded_hal_nb::serial::Error for Error {
+    fn kind(&self) -> embedded_hal_nb::serial::ErrorKind {
+        match self {
+            Self::Overrun => embedded_hal_nb::serial::ErrorKind::Overrun,
+            Self::Framing => embedded_hal_nb::serial::ErrorKind::FrameFormat,
+            Self::Parity => embedded_hal_nb::serial::ErrorKind::Parity,
+            _ => embedded_hal_nb::serial::ErrorKind::Other,
         }
-    };
-    ($($tt:tt)*) => {
-        #[allow(unused_imports)]
-        use core::fmt::*;
-        unsafe {
-            write!($crate::peripheral::uart::UART0, $($tt)*).unwrap();
+    }
+}
+
+impl<B> embedded_io::ErrorType for Uart<B> {
+    type Error = Error;
+}
+
+impl<B: Bus> embedded_io::Read for Uart<B> {
+    /// Receive bytes.
+    ///
+    /// Blocks until at least one byte is received,
+    /// then reads bytes from receiver FIFO until it is empty or buffer is full.
+    ///
+    /// Reading stops before a byte with receive error, which is reported by the next call.
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        let Some((first, rest)) = buf.split_first_mut() else {
+            return Ok(0);
+        };
+        *first = loop {
+            if let Some(result) = self.try_receive_checked() {
+                break result?;
+            }
+        };
+        let mut count = 1;
+        for byte in rest {
+            if self.pending_receive_error().is_some() {
+                break;
+            }
+            match self.try_receive_byte() {
+                Some(value) => *byte = value,
+                None => break,
+            }
+            count += 1;
+        }
+        Ok(count)
+    }
+}
+
+impl<B: Bus> embedded_io::ReadReady for Uart<B> {
+    fn read_ready(&mut self) -> Result<bool, Self::Error> {
+        Ok(self.is_receiver_fifo_empty().not())
+    }
+}
+
+impl<B: Bus> embedded_io::Write for Uart<B> {
+    /// Transmit bytes.
+    ///
+    /// Blocks until at least one byte is transmitted,
+    /// then writes bytes to transmitter FIFO until it is full or buffer is empty.
+    fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+        let Some((first, rest)) = buf.split_first() else {
+            return Ok(0);
+        };
+        self.transmit_byte(*first);
+        let mut count = 1;
+        for byte in rest {
+            if self.is_transmitter_fifo_full() {
+                break;
+            }
+            self.transmit_byte(*byte);
+            count += 1;
         }
-    };
+        Ok(count)
+    }
+
+    /// Wait until all bytes have been transmitted.
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        while self.is_transmitter_fifo_empty().not() || self.is_transmitter_active() {}
+        Ok(())
+    }
 }
 
-/// Print formatted line using [`UART0`](crate::peripheral::uart::UART0).
-#[macro_export]
-macro_rules! sprintln {
-    () => {
-        $crate::sprint!("\r\n");
-    };
-    ($($tt:tt)*) => {
-        $crate::sprint!($($tt)*);
-        $crate::sprint!("\r\n");
-    };
+impl<B: Bus> embedded_io::WriteReady for Uart<B> {
+    fn write_ready(&mut self) -> Result<bool, Self::Error> {
+        Ok(self.is_transmitter_fifo_full().not())
+    }
+}
+
+impl<B> embedded_hal_nb::serial::ErrorType for Uart<B> {
+    type Error = Error;
+}
+
+impl<B: Bus> embedded_hal_nb::serial::Read<u8> for Uart<B> {
+    fn read(&mut self) -> embedded_hal_nb::nb::Result<u8, Self::Error> {
+        match self.try_receive_checked() {
+            Some(result) => result.map_err(embedded_hal_nb::nb::Error::Other),
+            None => Err(embedded_hal_nb::nb::Error::WouldBlock),
+        }
+    }
+}
+
+impl<B: Bus> embedded_hal_nb::serial::Write<u8> for Uart<B> {
+    fn write(&mut self, word: u8) -> embedded_hal_nb::nb::Result<(), Self::Error> {
+        if self.is_transmitter_fifo_full() {
+            return Err(embedded_hal_nb::nb::Error::WouldBlock);
+        }
+        self.transmit_byte(word);
+        Ok(())
+    }
+
+    fn flush(&mut self) -> embedded_hal_nb::nb::Result<(), Self::Error> {
+        if self.is_transmitter_fifo_empty().not() || self.is_transmitter_active() {
+            return Err(embedded_hal_nb::nb::Error::WouldBlock);
+        }
+        Ok(())
+    }
+}
+
+/// UART 0 base address.
+const ADDRESS_UART0_BASE: u32 = 0xE000_0000;
+/// UART 1 base address.
+const ADDRESS_UART1_BASE: u32 = 0xE000_1000;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::bus::SimulatedBus;
+
+    #[test]
+    fn configure_writes_expected_sequence() {
+        let bus = SimulatedBus::<32, 64>::new();
+        let slcr = Slcr::with_bus(&bus);
+        let uart = Uart::with_bus(DeviceIndex::Uart0, &bus);
+        uart.configure(&slcr).unwrap();
+
+        let expected = [
+            // UART 0 AMBA clock.
+            (0xF800_012C, 0x0010_0000),
+            // UART 0 reference clock.
+            (0xF800_0154, 0x0000_0001),
+            // Disable receiver and transmitter.
+            (0xE000_0000, 0x0000_0028),
+            // Reset receiver and transmitter.
+            (0xE000_0000, 0x0000_0029),
+            (0xE000_0000, 0x0000_002B),
+            // Clear interrupts.
+            (0xE000_0014, 0xFFFF_FFFF),
+            // Clock source, character length, parity, stop bits and channel mode.
+            (0xE000_0004, 0x0000_0000),
+            (0xE000_0004, 0x0000_0000),
+            (0xE000_0004, 0x0000_0020),
+            (0xE000_0004, 0x0000_0020),
+            (0xE000_0004, 0x0000_0020),
+        ];
+        let writes = bus
+            .writes()
+            .map(|(address, value)| (address as usize, value));
+        assert!(writes.eq(expected));
+    }
+
+    #[test]
+    fn set_baud_rate_disables_controller_while_writing_dividers() {
+        let bus = SimulatedBus::<32, 64>::new();
+        let slcr = Slcr::with_bus(&bus);
+        let uart = Uart::with_bus(DeviceIndex::Uart0, &bus);
+        // IO PLL runs at 1 GHz and UART reference clock is divided by 10.
+        bus.preload(0xF800_0108 as *mut u32, 20 << 12);
+        bus.preload(0xF800_0154 as *mut u32, 10 << 8);
+        // Receiver and transmitter are enabled.
+        bus.preload(0xE000_0000 as *mut u32, 0x0000_0014);
+
+        let solution = uart.set_baud_rate(&slcr, 115_200).unwrap();
+        assert_eq!((solution.cd, solution.bdiv), (124, 6));
+
+        let expected = [
+            // Disable receiver and transmitter.
+            (0xE000_0000, 0x0000_0028),
+            // Dividers.
+            (0xE000_0018, 124),
+            (0xE000_0034, 6),
+            // Reset receiver and transmitter.
+            (0xE000_0000, 0x0000_0029),
+            (0xE000_0000, 0x0000_002B),
+            // Enable receiver and transmitter again.
+            (0xE000_0000, 0x0000_0027),
+            (0xE000_0000, 0x0000_0017),
+        ];
+        let writes = bus
+            .writes()
+            .map(|(address, value)| (address as usize, value));
+        assert!(writes.eq(expected));
+        assert!(uart.is_receiving());
+        assert!(uart.is_transmitting());
+    }
+
+    #[test]
+    fn configure_fails_when_slcr_stays_locked() {
+        let bus = SimulatedBus::<32, 64>::new();
+        let slcr = Slcr::with_bus(&bus);
+        let uart = Uart::with_bus(DeviceIndex::Uart1, &bus);
+        // Write protection status reads locked regardless of unlock key.
+        bus.preload(0xF800_000C as *mut u32, 1);
+        assert_eq!(uart.configure(&slcr), Err(Error::Slcr(SlcrError::Locked)));
+        assert!(bus
+            .writes()
+            .all(|(address, _)| address != 0xE000_1000 as *mut u32));
+    }
+
+    #[test]
+    fn overrun_does_not_consume_received_byte() {
+        let bus = SimulatedBus::<32, 64>::new();
+        let uart = Uart::with_bus(DeviceIndex::Uart0, &bus);
+        let status = 0xE000_0014 as *mut u32;
+        let overflow = 1 << ReceiverInterrupt::FifoOverflow.as_index();
+        bus.preload(status, overflow);
+        bus.preload(0xE000_0030 as *mut u32, u32::from(b'a'));
+
+        assert_eq!(uart.try_receive_checked(), Some(Err(Error::Overrun)));
+        assert!(bus.writes().eq([(status, overflow)]));
+
+        // Hardware clears the written status bit.
+        bus.preload(status, 0);
+        assert_eq!(uart.try_receive_checked(), Some(Ok(b'a')));
+    }
+
+    #[test]
+    fn framing_error_is_cleared_with_non_zero_byte() {
+        let bus = SimulatedBus::<32, 64>::new();
+        let uart = Uart::with_bus(DeviceIndex::Uart0, &bus);
+        let status = 0xE000_0014 as *mut u32;
+        let fifo = 0xE000_0030 as *mut u32;
+        let framing = 1 << ReceiverInterrupt::FramingError.as_index();
+        bus.preload(status, framing);
+        bus.preload(fifo, u32::from(b'a'));
+
+        assert_eq!(uart.try_receive(), Some(Received::Byte(b'a')));
+        assert!(bus.writes().eq([(status, framing)]));
+
+        // Hardware clears the written status bit, so the next zero byte is not a break.
+        bus.preload(status, 0);
+        bus.preload(fifo, 0);
+        assert_eq!(uart.try_receive(), Some(Received::Byte(0)));
+
+        bus.preload(status, framing);
+        assert_eq!(uart.try_receive(), Some(Received::Break));
+    }
+
+    #[test]
+    fn receive_frame_rejects_disabled_timeout() {
+        let bus = SimulatedBus::<32, 64>::new();
+        let uart = Uart::with_bus(DeviceIndex::Uart0, &bus);
+        let mut frame = [0; 4];
+        assert_eq!(
+            uart.receive_frame(&mut frame),
+            Err(Error::ReceiverTimeoutDisabled)
+        );
+        assert_eq!(bus.writes().count(), 0);
+    }
+
+    #[test]
+    fn transmit_string_writes_fifo() {
+        let bus = SimulatedBus::<32, 64>::new();
+        let uart = Uart::with_bus(DeviceIndex::Uart1, &bus);
+        uart.transmit_string("hi");
+
+        let expected = [
+            (0xE000_1030, u32::from(b'h')),
+            (0xE000_1030, u32::from(b'i')),
+        ];
+        let writes = bus
+            .writes()
+            .map(|(address, value)| (address as usize, value));
+        assert!(writes.eq(expected));
+    }
 }
@@ -0,0 +1,175 @@
+//! UART baud rate solver.
+//!
+//! Baud rate is generated from the selected clock with two dividers:
+//!
+//! `baud = clock / (CD * (BDIV + 1))`
+//!
+//! where `clock` is the UART reference clock, optionally prescaled with 8.
+//!
+//! Solver does not access registers, so it can be run on the host.
+//!
+//! # How to use?
+//!
+//! ```ignore
+//! let solution = baud_rate::solve(100_000_000, ClockSource::UartRefClk, 115_200)?;
+//! assert_eq!(solution.actual, 115_207);
+//! ```
+
+use super::ClockSource;
+use super::Error;
+use core::ops::RangeInclusive;
+
+/// Largest accepted difference between requested and achieved baud rate, in percents.
+pub const TOLERANCE_PERCENT: f32 = 3.0;
+
+/// Accepted values for baud rate generator's clock divisor.
+///
+/// Value 0 disables the generator and value 1 bypasses it.
+const CD_RANGE: RangeInclusive<u32> = 2..=65535;
+
+/// Accepted values for baud rate divider.
+const BDIV_RANGE: RangeInclusive<u32> = 4..=255;
+
+/// Divider values which produce a baud rate.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BaudRate {
+    /// Baud rate generator's clock divisor.
+    pub cd: u16,
+
+    /// Baud rate divider.
+    pub bdiv: u8,
+
+    /// Baud rate achieved with the dividers.
+    pub actual: u32,
+
+    /// Difference between requested and achieved baud rate, in percents.
+    pub error_percent: f32,
+}
+
+/// Baud rate produced by given dividers.
+///
+/// Returns zero if baud rate generator is disabled.
+#[must_use]
+pub fn rate(reference_clock: u32, source: ClockSource, cd: u16, bdiv: u8) -> u32 {
+    let clock = match source {
+        ClockSource::UartRefClk => reference_clock,
+        ClockSource::UartRefClkDiv8 => reference_clock / 8,
+    };
+    if cd == 0 {
+        return 0;
+    }
+    clock / (u32::from(cd) * (u32::from(bdiv) + 1))
+}
+
+/// Find dividers which produce baud rate closest to requested baud rate.
+///
+/// # Errors
+///
+/// - Requested baud rate is zero.
+/// - No dividers produce baud rate within [tolerance](TOLERANCE_PERCENT).
+pub fn solve(reference_clock: u32, source: ClockSource, baud: u32) -> Result<BaudRate, Error> {
+    if baud == 0 {
+        return Err(Error::UnreachableBaudRate(baud));
+    }
+    let clock = match source {
+        ClockSource::UartRefClk => reference_clock,
+        ClockSource::UartRefClkDiv8 => reference_clock / 8,
+    };
+    let clock = u64::from(clock);
+    let baud = u64::from(baud);
+
+    let mut best: Option<(u32, u32, u64, u64)> = None;
+    for bdiv in BDIV_RANGE {
+        let divider = baud * u64::from(bdiv + 1);
+        // Round to nearest divisor.
+        let cd = (clock + divider / 2) / divider;
+        let Ok(cd) = u32::try_from(cd) else {
+            continue;
+        };
+        if !CD_RANGE.contains(&cd) {
+            continue;
+        }
+        let actual = clock / (u64::from(cd) * u64::from(bdiv + 1));
+        let difference = actual.abs_diff(baud);
+        if best.is_none_or(|(_, _, _, best)| difference < best) {
+            best = Some((cd, bdiv, actual, difference));
+        }
+    }
+
+    let Some((cd, bdiv, actual, difference)) = best else {
+        return Err(Error::UnreachableBaudRate(baud as u32));
+    };
+    let error_percent = difference as f32 * 100.0 / baud as f32;
+    if TOLERANCE_PERCENT < error_percent {
+        return Err(Error::UnreachableBaudRate(baud as u32));
+    }
+    Ok(BaudRate {
+        cd: cd as u16,
+        bdiv: bdiv as u8,
+        actual: actual as u32,
+        error_percent,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rate_divides_selected_clock() {
+        assert_eq!(rate(100_000_000, ClockSource::UartRefClk, 124, 6), 115_207);
+        assert_eq!(
+            rate(100_000_000, ClockSource::UartRefClkDiv8, 124, 6),
+            14_400
+        );
+        assert_eq!(rate(100_000_000, ClockSource::UartRefClk, 0, 6), 0);
+    }
+
+    #[test]
+    fn solve_115200_at_100_mhz() {
+        let solution = solve(100_000_000, ClockSource::UartRefClk, 115_200).unwrap();
+        assert_eq!(solution.actual, 115_207);
+        assert_eq!(
+            rate(
+                100_000_000,
+                ClockSource::UartRefClk,
+                solution.cd,
+                solution.bdiv
+            ),
+            solution.actual
+        );
+        assert!(solution.error_percent < 0.01);
+    }
+
+    #[test]
+    fn solve_rejects_unreachable_rates() {
+        assert_eq!(
+            solve(100_000_000, ClockSource::UartRefClk, 0),
+            Err(Error::UnreachableBaudRate(0))
+        );
+        // Fastest rate is clock / (2 * 5).
+        assert_eq!(
+            solve(100_000_000, ClockSource::UartRefClk, 20_000_000),
+            Err(Error::UnreachableBaudRate(20_000_000))
+        );
+        // Slowest rate is clock / (65535 * 256).
+        assert_eq!(
+            solve(100_000_000, ClockSource::UartRefClk, 1),
+            Err(Error::UnreachableBaudRate(1))
+        );
+    }
+
+    #[test]
+    fn solve_respects_bdiv_limits() {
+        let solution = solve(100_000_000, ClockSource::UartRefClk, 10_000_000).unwrap();
+        assert_eq!((solution.cd, solution.bdiv), (2, 4));
+        // Divider 3 would give an exact rate, but 4 is the smallest accepted.
+        assert_eq!(
+            solve(100_000_000, ClockSource::UartRefClk, 12_500_000),
+            Err(Error::UnreachableBaudRate(12_500_000))
+        );
+        // Only divider 255 keeps clock divisor within its range.
+        let solution = solve(1_675_000_000, ClockSource::UartRefClk, 100).unwrap();
+        assert_eq!(solution.bdiv, 255);
+    }
+}
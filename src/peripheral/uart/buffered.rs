@@ -0,0 +1,603 @@
+//! Interrupt driven UART.
+//!
+//! [`BufferedUart`] moves bytes between UART FIFOs and software ring buffers
+//! in its interrupt handler, so reading and writing never wait for the line.
+//!
+//! - Received bytes are moved from receiver FIFO when it reaches trigger level
+//!   or receiver times out.
+//! - Written bytes are moved to transmitter FIFO when it becomes empty.
+//!
+//! # How to use?
+//!
+//! ```ignore
+//! let peripherals = Peripherals::take().unwrap();
+//! peripherals.uart0.configure(&peripherals.slcr).unwrap();
+//! let mut uart = BufferedUart::new(peripherals.uart0);
+//!
+//! // Route UART interrupt to processor core.
+//! let gic = peripherals.gic;
+//! gic.toggle_interrupt(Irq::Spi(SpiIrq::Uart0), true);
+//! gic.set_shared_peripheral_interrupt_targets(SpiIrq::Uart0, InterruptTargets::Cpu0);
+//! gic.toggle(true);
+//!
+//! uart.write(b"Hello, World!\r\n");
+//! let mut buffer = [0; 16];
+//! let count = uart.read(&mut buffer);
+//! ```
+
+use super::DeviceIndex;
+use super::Error;
+use super::Interrupt;
+use super::InterruptCauses;
+use super::ReceiverInterrupt;
+use super::TransmitterInterrupt;
+use super::Uart;
+use crate::common::bus::Bus;
+use crate::common::bus::Mmio;
+use crate::interrupt::cpu;
+use crate::interrupt::handler::irq::IRQ_HANDLER;
+use core::cell::UnsafeCell;
+use core::ops::Not;
+use core::sync::atomic::AtomicBool;
+use core::sync::atomic::AtomicU32;
+use core::sync::atomic::AtomicUsize;
+use core::sync::atomic::Ordering;
+
+/// How many bytes each ring buffer holds.
+pub const BUFFER_SIZE: usize = 256;
+
+/// Receiver FIFO level which triggers moving bytes to receive buffer.
+const RECEIVER_TRIGGER: u32 = 32;
+
+/// Default receiver timeout in bit periods.
+///
+/// Bytes which do not reach trigger level are moved after the line has been idle this long.
+const RECEIVER_TIMEOUT: u32 = 40;
+
+/// Ring buffer with one producer and one consumer.
+///
+/// Interrupt handler is the producer of receive buffer and the consumer of transmit buffer.
+/// Program side is [`BufferedUart::read`] and [`BufferedUart::write`],
+/// which take `&mut self`, so only one context at a time is the other end.
+struct Queue {
+    /// Stored bytes.
+    bytes: UnsafeCell<[u8; BUFFER_SIZE]>,
+
+    /// Index where next byte is stored, only written by producer.
+    head: AtomicUsize,
+
+    /// Index of oldest stored byte, only written by consumer.
+    tail: AtomicUsize,
+}
+
+// Safety:
+// Producer only writes slots which consumer has released and vice versa.
+unsafe impl Sync for Queue {}
+
+impl Queue {
+    /// Create empty queue.
+    const fn new() -> Self {
+        Self {
+            bytes: UnsafeCell::new([0; BUFFER_SIZE]),
+            head: AtomicUsize::new(0),
+            tail: AtomicUsize::new(0),
+        }
+    }
+
+    /// Store byte.
+    ///
+    /// Returns false if queue is full.
+    fn push(&self, byte: u8) -> bool {
+        let head = self.head.load(Ordering::Relaxed);
+        let next = (head + 1) % BUFFER_SIZE;
+        if next == self.tail.load(Ordering::Acquire) {
+            return false;
+        }
+        // Safety:
+        // Slot at head is not visible to consumer until head is advanced.
+        unsafe { (*self.bytes.get())[head] = byte };
+        self.head.store(next, Ordering::Release);
+        true
+    }
+
+    /// Take oldest byte.
+    fn pop(&self) -> Option<u8> {
+        let tail = self.tail.load(Ordering::Relaxed);
+        if tail == self.head.load(Ordering::Acquire) {
+            return None;
+        }
+        // Safety:
+        // Slot at tail is not reused by producer until tail is advanced.
+        let byte = unsafe { (*self.bytes.get())[tail] };
+        self.tail.store((tail + 1) % BUFFER_SIZE, Ordering::Release);
+        Some(byte)
+    }
+
+    /// True if queue holds no bytes.
+    fn is_empty(&self) -> bool {
+        self.head.load(Ordering::Acquire) == self.tail.load(Ordering::Acquire)
+    }
+
+    /// True if queue can not hold more bytes.
+    fn is_full(&self) -> bool {
+        let head = self.head.load(Ordering::Acquire);
+        (head + 1) % BUFFER_SIZE == self.tail.load(Ordering::Acquire)
+    }
+
+    /// Discard stored bytes.
+    ///
+    /// Must not be called while producer or consumer is running,
+    /// so interrupt handler must be masked.
+    fn clear(&self) {
+        self.head.store(0, Ordering::Relaxed);
+        self.tail.store(0, Ordering::Relaxed);
+    }
+}
+
+/// Buffers and statistics of one UART.
+struct State {
+    /// Received bytes.
+    receiver: Queue,
+
+    /// Bytes waiting to be transmitted.
+    transmitter: Queue,
+
+    /// How many received bytes were discarded because receive buffer was full.
+    buffer_overruns: AtomicU32,
+
+    /// How many times receiver FIFO overflowed before interrupt handler emptied it.
+    fifo_overflows: AtomicU32,
+
+    /// How many received bytes had framing error.
+    framing_errors: AtomicU32,
+
+    /// How many received bytes had parity error.
+    parity_errors: AtomicU32,
+
+    /// Receiver has timed out since end of frame was last taken.
+    end_of_frame: AtomicBool,
+}
+
+impl State {
+    /// Create empty state.
+    const fn new() -> Self {
+        Self {
+            receiver: Queue::new(),
+            transmitter: Queue::new(),
+            buffer_overruns: AtomicU32::new(0),
+            fifo_overflows: AtomicU32::new(0),
+            framing_errors: AtomicU32::new(0),
+            parity_errors: AtomicU32::new(0),
+            end_of_frame: AtomicBool::new(false),
+        }
+    }
+
+    /// Discard buffered bytes and reset statistics.
+    fn clear(&self) {
+        self.receiver.clear();
+        self.transmitter.clear();
+        self.buffer_overruns.store(0, Ordering::Relaxed);
+        self.fifo_overflows.store(0, Ordering::Relaxed);
+        self.framing_errors.store(0, Ordering::Relaxed);
+        self.parity_errors.store(0, Ordering::Relaxed);
+        self.end_of_frame.store(false, Ordering::Relaxed);
+    }
+}
+
+/// UART 0 state.
+static UART0: State = State::new();
+
+/// UART 1 state.
+static UART1: State = State::new();
+
+/// Get state of given UART.
+fn state(index: DeviceIndex) -> &'static State {
+    match index {
+        DeviceIndex::Uart0 => &UART0,
+        DeviceIndex::Uart1 => &UART1,
+    }
+}
+
+/// Lost and corrupted data counters.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Statistics {
+    /// How many received bytes were discarded because receive buffer was full.
+    pub buffer_overruns: u32,
+
+    /// How many times receiver FIFO overflowed before interrupt handler emptied it.
+    pub fifo_overflows: u32,
+
+    /// How many received bytes had framing error.
+    pub framing_errors: u32,
+
+    /// How many received bytes had parity error.
+    pub parity_errors: u32,
+}
+
+/// UART which transmits and receives in its interrupt handler.
+pub struct BufferedUart<B = Mmio> {
+    /// Underlying UART.
+    uart: Uart<B>,
+
+    /// Buffers and statistics.
+    state: &'static State,
+
+    /// Interrupt handler replaced by [`BufferedUart::new`], put back by [`release`](Self::release).
+    previous_handler: Option<fn()>,
+}
+
+/// Install UART interrupt handler.
+///
+/// Returns previously installed handler.
+fn install_handler(index: DeviceIndex, handler: fn()) -> fn() {
+    // Safety:
+    // Writing a function pointer is a single store,
+    // so interrupt handler never sees a partial write.
+    unsafe {
+        let irq_handler = core::ptr::addr_of_mut!(IRQ_HANDLER);
+        match index {
+            DeviceIndex::Uart0 => core::mem::replace(&mut (*irq_handler).handle_uart0, handler),
+            DeviceIndex::Uart1 => core::mem::replace(&mut (*irq_handler).handle_uart1, handler),
+        }
+    }
+}
+
+impl BufferedUart {
+    /// Create buffered UART and install its interrupt handler.
+    ///
+    /// UART must be configured.
+    /// UART interrupt must be enabled and routed in `GIC` to the processor core using the UART.
+    #[must_use]
+    pub fn new(uart: Uart) -> Self {
+        let handler = match uart.index() {
+            DeviceIndex::Uart0 => handle_uart0,
+            DeviceIndex::Uart1 => handle_uart1,
+        };
+        let previous_handler = install_handler(uart.index(), handler);
+        Self {
+            previous_handler: Some(previous_handler),
+            ..Self::with_uart(uart)
+        }
+    }
+}
+
+impl<B: Bus> BufferedUart<B> {
+    /// Create buffered UART without installing interrupt handler.
+    ///
+    /// Call [`handle_interrupt`](Self::handle_interrupt) when UART interrupt is raised.
+    ///
+    /// UART must be configured.
+    #[must_use]
+    pub fn with_uart(uart: Uart<B>) -> Self {
+        let state = state(uart.index());
+
+        // Interrupt handler may still be pending, so it must not run while buffers are cleared.
+        cpu::free(|_| {
+            uart.toggle(false);
+            uart.registers().interrupt_disable().write_bits(0xFFFF_FFFF);
+            uart.clear_all_interrupts();
+            uart.reset_receiver();
+            uart.reset_transmitter();
+            state.clear();
+        });
+
+        // Values are in range.
+        let _ = uart.set_receiver_fifo_trigger_value(RECEIVER_TRIGGER);
+        let _ = uart.set_receiver_timeout(RECEIVER_TIMEOUT);
+        uart.toggle_interrupt(Interrupt::Receiver(ReceiverInterrupt::FifoTrigger), true);
+        uart.toggle_interrupt(Interrupt::Receiver(ReceiverInterrupt::FifoFull), true);
+        uart.toggle_interrupt(Interrupt::Receiver(ReceiverInterrupt::FifoOverflow), true);
+        uart.toggle_interrupt(Interrupt::Receiver(ReceiverInterrupt::FramingError), true);
+        uart.toggle_interrupt(Interrupt::Receiver(ReceiverInterrupt::ParityError), true);
+        uart.toggle_interrupt(Interrupt::Receiver(ReceiverInterrupt::Timeout), true);
+        uart.toggle(true);
+
+        Self {
+            uart,
+            state,
+            previous_handler: None,
+        }
+    }
+
+    /// Stop buffering and give back the UART.
+    ///
+    /// Bytes not yet transmitted are discarded.
+    /// Interrupt handler replaced by [`BufferedUart::new`] is put back.
+    #[must_use]
+    pub fn release(self) -> Uart<B> {
+        self.uart
+            .registers()
+            .interrupt_disable()
+            .write_bits(0xFFFF_FFFF);
+        self.uart.clear_all_interrupts();
+        if let Some(handler) = self.previous_handler {
+            install_handler(self.uart.index(), handler);
+        }
+        self.uart
+    }
+
+    /// Underlying UART.
+    #[inline]
+    #[must_use]
+    pub fn uart(&self) -> &Uart<B> {
+        &self.uart
+    }
+
+    /// Move received bytes to given buffer.
+    ///
+    /// Returns how many bytes were moved.
+    /// This function does not block.
+    pub fn read(&mut self, buffer: &mut [u8]) -> usize {
+        let mut count = 0;
+        for byte in buffer.iter_mut() {
+            match self.state.receiver.pop() {
+                Some(value) => *byte = value,
+                None => break,
+            }
+            count += 1;
+        }
+        count
+    }
+
+    /// Move given bytes to transmit buffer.
+    ///
+    /// Returns how many bytes were moved, less than given if transmit buffer became full.
+    /// This function does not block.
+    pub fn write(&mut self, bytes: &[u8]) -> usize {
+        let mut count = 0;
+        for byte in bytes {
+            if self.state.transmitter.push(*byte).not() {
+                break;
+            }
+            count += 1;
+        }
+        self.start_transmitting();
+        count
+    }
+
+    /// True if received bytes are buffered.
+    #[inline]
+    #[must_use]
+    pub fn is_read_ready(&self) -> bool {
+        self.state.receiver.is_empty().not()
+    }
+
+    /// True if transmit buffer has space.
+    #[inline]
+    #[must_use]
+    pub fn is_write_ready(&self) -> bool {
+        self.state.transmitter.is_full().not()
+    }
+
+    /// True if all written bytes have been transmitted.
+    #[must_use]
+    pub fn is_transmit_complete(&self) -> bool {
+        self.state.transmitter.is_empty()
+            && self.uart.is_transmitter_fifo_empty()
+            && self.uart.is_transmitter_active().not()
+    }
+
+    /// True if the line has been idle for receiver timeout since last call.
+    ///
+    /// Use this as end of frame signal in idle-line framed protocols.
+    /// Timeout is configured with [`Uart::set_receiver_timeout`].
+    #[inline]
+    #[must_use]
+    pub fn take_end_of_frame(&self) -> bool {
+        self.state.end_of_frame.swap(false, Ordering::AcqRel)
+    }
+
+    /// Get lost and corrupted data counters.
+    #[must_use]
+    pub fn statistics(&self) -> Statistics {
+        Statistics {
+            buffer_overruns: self.state.buffer_overruns.load(Ordering::Relaxed),
+            fifo_overflows: self.state.fifo_overflows.load(Ordering::Relaxed),
+            framing_errors: self.state.framing_errors.load(Ordering::Relaxed),
+            parity_errors: self.state.parity_errors.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Reset lost and corrupted data counters.
+    pub fn clear_statistics(&self) {
+        self.state.buffer_overruns.store(0, Ordering::Relaxed);
+        self.state.fifo_overflows.store(0, Ordering::Relaxed);
+        self.state.framing_errors.store(0, Ordering::Relaxed);
+        self.state.parity_errors.store(0, Ordering::Relaxed);
+    }
+
+    /// Move bytes between FIFOs and buffers.
+    ///
+    /// Called from UART interrupt handler.
+    pub fn handle_interrupt(&self) {
+        let status = self
+            .uart
+            .registers()
+            .channel_interrupt_status()
+            .read()
+            .bits();
+        let mask = self.uart.registers().interrupt_mask().read().bits();
+        let causes = InterruptCauses::new(status & mask);
+        // Clear handled causes before handling, so new events are not lost.
+        self.uart
+            .registers()
+            .channel_interrupt_status()
+            .write_bits(status & mask);
+
+        if causes.receiver_overflow {
+            self.state.fifo_overflows.fetch_add(1, Ordering::Relaxed);
+        }
+        if causes.receiver_framing {
+            self.state.framing_errors.fetch_add(1, Ordering::Relaxed);
+        }
+        if causes.receiver_parity {
+            self.state.parity_errors.fetch_add(1, Ordering::Relaxed);
+        }
+        if causes.receiver_fifo_trigger || causes.receiver_fifo_full || causes.receiver_timeout {
+            while let Some(byte) = self.uart.try_receive_byte() {
+                if self.state.receiver.push(byte).not() {
+                    self.state.buffer_overruns.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+        }
+        if causes.receiver_timeout {
+            self.state.end_of_frame.store(true, Ordering::Release);
+        }
+        if causes.transmitter_fifo_empty {
+            self.fill_transmitter_fifo();
+        }
+    }
+
+    /// Move bytes from transmit buffer to transmitter FIFO.
+    ///
+    /// Transmitter FIFO empty interrupt is enabled while transmit buffer holds bytes.
+    fn fill_transmitter_fifo(&self) {
+        while self.uart.is_transmitter_fifo_full().not() {
+            match self.state.transmitter.pop() {
+                Some(byte) => self.uart.transmit_byte(byte),
+                None => break,
+            }
+        }
+        let pending = self.state.transmitter.is_empty().not();
+        self.uart.toggle_interrupt(
+            Interrupt::Transmitter(TransmitterInterrupt::FifoEmpty),
+            pending,
+        );
+    }
+
+    /// Start transmitting buffered bytes.
+    fn start_transmitting(&self) {
+        // Interrupt handler also consumes transmit buffer and toggles transmitter interrupt,
+        // so it must not run while transmitter FIFO is filled here.
+        cpu::free(|_| self.fill_transmitter_fifo());
+    }
+}
+
+impl<B> embedded_io::ErrorType for BufferedUart<B> {
+    type Error = Error;
+}
+
+impl<B: Bus> embedded_io::Read for BufferedUart<B> {
+    /// Move received bytes to given buffer.
+    ///
+    /// Blocks until at least one byte is received.
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        loop {
+            let count = BufferedUart::read(self, buf);
+            if count != 0 {
+                return Ok(count);
+            }
+        }
+    }
+}
+
+impl<B: Bus> embedded_io::ReadReady for BufferedUart<B> {
+    fn read_ready(&mut self) -> Result<bool, Self::Error> {
+        Ok(self.is_read_ready())
+    }
+}
+
+impl<B: Bus> embedded_io::Write for BufferedUart<B> {
+    /// Move given bytes to transmit buffer.
+    ///
+    /// Blocks until at least one byte fits to transmit buffer.
+    fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        loop {
+            let count = BufferedUart::write(self, buf);
+            if count != 0 {
+                return Ok(count);
+            }
+        }
+    }
+
+    /// Wait until all bytes have been transmitted.
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        while self.is_transmit_complete().not() {}
+        Ok(())
+    }
+}
+
+impl<B: Bus> embedded_io::WriteReady for BufferedUart<B> {
+    fn write_ready(&mut self) -> Result<bool, Self::Error> {
+        Ok(self.is_write_ready())
+    }
+}
+
+/// UART 0 interrupt handler installed by [`BufferedUart::new`].
+fn handle_uart0() {
+    // Interrupt handler runs outside of the program's ownership of peripherals.
+    // Buffered UART owns UART 0 and relies on this handler.
+    let uart = Uart::with_bus(DeviceIndex::Uart0, unsafe { Mmio::steal() });
+    let uart = BufferedUart {
+        uart,
+        state: &UART0,
+        previous_handler: None,
+    };
+    uart.handle_interrupt();
+}
+
+/// UART 1 interrupt handler installed by [`BufferedUart::new`].
+fn handle_uart1() {
+    // Interrupt handler runs outside of the program's ownership of peripherals.
+    // Buffered UART owns UART 1 and relies on this handler.
+    let uart = Uart::with_bus(DeviceIndex::Uart1, unsafe { Mmio::steal() });
+    let uart = BufferedUart {
+        uart,
+        state: &UART1,
+        previous_handler: None,
+    };
+    uart.handle_interrupt();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn queue_wraps_around() {
+        let queue = Queue::new();
+        // Move indices close to the end of the buffer.
+        for _ in 0..BUFFER_SIZE - 2 {
+            assert!(queue.push(0));
+            assert_eq!(queue.pop(), Some(0));
+        }
+        for byte in 0..4 {
+            assert!(queue.push(byte));
+        }
+        assert_eq!(queue.head.load(Ordering::Relaxed), 2);
+        for byte in 0..4 {
+            assert_eq!(queue.pop(), Some(byte));
+        }
+        assert!(queue.is_empty());
+        assert_eq!(queue.pop(), None);
+    }
+
+    #[test]
+    fn installing_handler_returns_previous_handler() {
+        fn handler() {}
+        let previous = install_handler(DeviceIndex::Uart1, handler);
+        let installed = install_handler(DeviceIndex::Uart1, previous);
+        assert!(core::ptr::fn_addr_eq(installed, handler as fn()));
+    }
+
+    #[test]
+    fn queue_holds_one_less_than_buffer_size() {
+        let queue = Queue::new();
+        for byte in 0..BUFFER_SIZE - 1 {
+            assert!(queue.push(byte as u8));
+        }
+        assert!(queue.is_full());
+        assert!(queue.push(0).not());
+        assert_eq!(queue.pop(), Some(0));
+        assert!(queue.push(0));
+        assert!(queue.is_full());
+        queue.clear();
+        assert!(queue.is_empty());
+    }
+}
@@ -0,0 +1,166 @@
+//! UART registers.
+
+crate::register_block! {
+    /// UART registers.
+    pub UartRegisters {
+        /// UART control register.
+        0x00 => control: ReadWrite, reset = 0x0000_0128 {
+            /// Software reset for receiver data path, cleared automatically.
+            RXRST: 0, 1;
+            /// Software reset for transmitter data path, cleared automatically.
+            TXRST: 1, 1;
+            /// Enable receiver.
+            RXEN: 2, 1;
+            /// Disable receiver.
+            RXDIS: 3, 1;
+            /// Enable transmitter.
+            TXEN: 4, 1;
+            /// Disable transmitter.
+            TXDIS: 5, 1;
+            /// Restart receiver timeout counter.
+            RSTTO: 6, 1;
+            /// Start transmitter break.
+            STTBRK: 7, 1;
+            /// Stop transmitter break.
+            STPBRK: 8, 1;
+        }
+
+        /// UART mode register.
+        0x04 => mode: ReadWrite, reset = 0x0000_0000 {
+            /// Clock source select.
+            CLKS: 0, 1;
+            /// Character length select.
+            CHRL: 1, 2;
+            /// Parity type select.
+            PAR: 3, 3;
+            /// Number of stop bits.
+            NBSTOP: 6, 2;
+            /// Channel mode.
+            CHMODE: 8, 2;
+        }
+
+        /// Interrupt enable register.
+        0x08 => interrupt_enable: WriteOnly, reset = 0x0000_0000 {
+            /// Interrupts, see [`Interrupt`](super::Interrupt) for indices.
+            interrupt[13]: 0, 1;
+        }
+
+        /// Interrupt disable register.
+        0x0C => interrupt_disable: WriteOnly, reset = 0x0000_0000 {
+            /// Interrupts, see [`Interrupt`](super::Interrupt) for indices.
+            interrupt[13]: 0, 1;
+        }
+
+        /// Interrupt mask register.
+        0x10 => interrupt_mask: ReadOnly, reset = 0x0000_0000 {
+            /// Interrupts, see [`Interrupt`](super::Interrupt) for indices.
+            interrupt[13]: 0, 1;
+        }
+
+        /// Channel interrupt status register.
+        ///
+        /// Write high bit to clear interrupt.
+        0x14 => channel_interrupt_status: ReadWrite, reset = 0x0000_0000 {
+            /// Interrupts, see [`Interrupt`](super::Interrupt) for indices.
+            interrupt[13]: 0, 1;
+        }
+
+        /// Baud rate generator register.
+        0x18 => baud_rate_generator: ReadWrite, reset = 0x0000_028B {
+            /// Baud rate clock divisor.
+            CD: 0, 16;
+        }
+
+        /// Receiver timeout register.
+        0x1C => receiver_timeout: ReadWrite, reset = 0x0000_0000 {
+            /// Receiver timeout value.
+            RTO: 0, 8;
+        }
+
+        /// Receiver FIFO trigger level register.
+        0x20 => receiver_fifo_trigger_level: ReadWrite, reset = 0x0000_0020 {
+            /// Receiver FIFO trigger level.
+            RTRIG: 0, 6;
+        }
+
+        /// Modem control register.
+        0x24 => modem_control: ReadWrite, reset = 0x0000_0000 {
+            /// Data terminal ready.
+            DTR: 0, 1;
+            /// Request to send.
+            RTS: 1, 1;
+            /// Automatic flow control mode.
+            FCM: 5, 1;
+        }
+
+        /// Modem status register.
+        0x28 => modem_status: ReadWrite, reset = 0x0000_0000 {
+            /// Clear to send changed.
+            DCTS: 0, 1;
+            /// Data set ready changed.
+            DDSR: 1, 1;
+            /// Ring indicator trailing edge.
+            TERI: 2, 1;
+            /// Data carrier detect changed.
+            DDCD: 3, 1;
+            /// Clear to send.
+            CTS: 4, 1;
+            /// Data set ready.
+            DSR: 5, 1;
+            /// Ring indicator.
+            RI: 6, 1;
+            /// Data carrier detect.
+            DCD: 7, 1;
+            /// Flow control mode.
+            FCMS: 8, 1;
+        }
+
+        /// Channel status register.
+        0x2C => channel_status: ReadOnly, reset = 0x0000_0000 {
+            /// Receiver FIFO has reached trigger level.
+            RTRIG: 0, 1;
+            /// Receiver FIFO is empty.
+            REMPTY: 1, 1;
+            /// Receiver FIFO is full.
+            RFUL: 2, 1;
+            /// Transmitter FIFO is empty.
+            TEMPTY: 3, 1;
+            /// Transmitter FIFO is full.
+            TFUL: 4, 1;
+            /// Receiver is active.
+            RACTIVE: 10, 1;
+            /// Transmitter is active.
+            TACTIVE: 11, 1;
+            /// Receiver flow delay trigger level has been reached.
+            FDELT: 12, 1;
+            /// Transmitter FIFO has reached trigger level.
+            TTRIG: 13, 1;
+            /// Transmitter FIFO is nearly full.
+            TNFUL: 14, 1;
+        }
+
+        /// Transmit and receive FIFO.
+        0x30 => fifo: ReadWrite, reset = 0x0000_0000 {
+            /// Transmitted or received byte.
+            DATA: 0, 8;
+        }
+
+        /// Baud rate divider register.
+        0x34 => baud_rate_divider: ReadWrite, reset = 0x0000_000F {
+            /// Baud rate divider.
+            BDIV: 0, 8;
+        }
+
+        /// Flow control delay register.
+        0x38 => flow_control_delay: ReadWrite, reset = 0x0000_0000 {
+            /// Receiver FIFO level which deasserts request to send.
+            FDEL: 0, 6;
+        }
+
+        /// Transmitter FIFO trigger level register.
+        0x44 => transmitter_fifo_trigger_level: ReadWrite, reset = 0x0000_0020 {
+            /// Transmitter FIFO trigger level.
+            TTRIG: 0, 6;
+        }
+    }
+}
@@ -0,0 +1,139 @@
+//! UART self-test.
+//!
+//! Self-test switches UART to local loopback,
+//! so transmitted bytes are received without touching the pins.
+//! Test pattern is pushed through the FIFOs with each [configuration](CONFIGURATIONS)
+//! and prior configuration is restored afterwards.
+//!
+//! # How to use?
+//!
+//! ```ignore
+//! let uart = peripherals.uart1;
+//! uart.configure(&peripherals.slcr)?;
+//! match uart.self_test(&peripherals.slcr) {
+//!     Ok(()) => sprintln!("UART 1 passed"),
+//!     Err(error) => sprintln!("UART 1 failed: {error}"),
+//! }
+//! ```
+
+use super::ChannelMode;
+use super::CharacterLength;
+use super::Error;
+use super::ParityType;
+use super::StopBits;
+use super::Uart;
+use crate::common::bus::Bus;
+use crate::peripheral::slcr::Slcr;
+
+/// Line configuration used during self-test.
+#[derive(Clone, Copy)]
+pub struct Configuration {
+    /// Baud rate.
+    pub baud: u32,
+
+    /// Parity bits.
+    pub parity: ParityType,
+
+    /// Stop bits.
+    pub stop_bits: StopBits,
+}
+
+/// Line configurations tested in order.
+pub const CONFIGURATIONS: [Configuration; 4] = [
+    Configuration {
+        baud: 9_600,
+        parity: ParityType::Disabled,
+        stop_bits: StopBits::One,
+    },
+    Configuration {
+        baud: 115_200,
+        parity: ParityType::Even,
+        stop_bits: StopBits::One,
+    },
+    Configuration {
+        baud: 115_200,
+        parity: ParityType::Odd,
+        stop_bits: StopBits::Two,
+    },
+    Configuration {
+        baud: 921_600,
+        parity: ParityType::Disabled,
+        stop_bits: StopBits::Two,
+    },
+];
+
+/// Bytes pushed through the FIFOs.
+///
+/// Pattern toggles every bit and contains both all-low and all-high bytes.
+pub const PATTERN: [u8; 8] = [0x00, 0xFF, 0x55, 0xAA, 0x0F, 0xF0, 0x5A, 0xA5];
+
+/// How many times receiver FIFO is polled before a byte is considered lost.
+///
+/// Polling takes longer than one frame at the slowest tested baud rate.
+const RECEIVE_POLLS: u32 = 1_000_000;
+
+impl<B: Bus> Uart<B> {
+    /// Test UART using local loopback.
+    ///
+    /// UART interrupts are disabled during the test.
+    /// Pending receiver and transmitter data is discarded.
+    ///
+    /// # Errors
+    ///
+    /// - Baud rate can not be generated from UART reference clock.
+    /// - Received byte differs from transmitted byte or no byte was received.
+    /// - Received byte has framing or parity error, or receiver FIFO overflowed.
+    pub fn self_test<S: Bus>(&self, slcr: &Slcr<S>) -> Result<(), Error> {
+        let mode = self.registers.mode().read().bits();
+        let cd = self.registers.baud_rate_generator().read().bits();
+        let bdiv = self.registers.baud_rate_divider().read().bits();
+        let interrupts = self.registers.interrupt_mask().read().bits();
+        let receiving = self.is_receiving();
+        let transmitting = self.is_transmitting();
+
+        self.registers.interrupt_disable().write_bits(0xFFFF_FFFF);
+        let result = CONFIGURATIONS
+            .iter()
+            .try_for_each(|configuration| self.loopback(slcr, *configuration));
+
+        self.toggle(false);
+        self.registers.mode().write_bits(mode);
+        self.registers.baud_rate_generator().write_bits(cd);
+        self.registers.baud_rate_divider().write_bits(bdiv);
+        self.reset_receiver();
+        self.reset_transmitter();
+        self.clear_all_interrupts();
+        self.registers.interrupt_enable().write_bits(interrupts);
+        self.toggle_receiving(receiving);
+        self.toggle_transmitting(transmitting);
+        result
+    }
+
+    /// Push test pattern through FIFOs with given configuration.
+    fn loopback<S: Bus>(&self, slcr: &Slcr<S>, configuration: Configuration) -> Result<(), Error> {
+        self.toggle(false);
+        self.set_channel_mode(ChannelMode::LocalLoopback);
+        self.set_character_length(CharacterLength::Eight);
+        self.set_parity(configuration.parity);
+        self.set_stop_bits(configuration.stop_bits);
+        // Receiver and transmitter are reset.
+        self.set_baud_rate(slcr, configuration.baud)?;
+        self.clear_all_interrupts();
+        self.toggle(true);
+
+        for sent in PATTERN {
+            self.transmit_byte(sent);
+            let received = (0..RECEIVE_POLLS)
+                .find_map(|_| self.try_receive_checked())
+                .transpose()?;
+            if received != Some(sent) {
+                return Err(Error::LoopbackMismatch {
+                    baud: configuration.baud,
+                    sent,
+                    received,
+                });
+            }
+        }
+        Ok(())
+    }
+}
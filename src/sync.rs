@@ -0,0 +1,26 @@
+//! Synchronization primitives.
+//!
+//! Masking interrupts protects shared state from interrupt handlers of the same core only.
+//! [`Spinlock`](spinlock::Spinlock) protects shared state from the other core,
+//! and [`Mutex`](mutex::Mutex) combines both.
+//!
+//! With `critical-section-impl` feature, the crate implements
+//! [`critical-section`](https://docs.rs/critical-section) with the same combination,
+//! so that crates depending on it can be used on the board.
+//!
+//! # How to use?
+//!
+//! ```ignore
+//! static COUNTER: Mutex<u32> = Mutex::new(0);
+//!
+//! fn handle_ttc0_0() {
+//!     COUNTER.lock(|counter| *counter += 1);
+//! }
+//!
+//! let count = COUNTER.lock(|counter| *counter);
+//! ```
+
+#[cfg(feature = "critical-section-impl")]
+mod critical_section;
+pub mod mutex;
+pub mod spinlock;
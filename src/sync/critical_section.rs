@@ -0,0 +1,53 @@
+//! Implementation of [`critical-section`](https://docs.rs/critical-section).
+//!
+//! Critical section masks IRQ and FIQ and takes a global spinlock.
+//! Critical sections may be nested, so the core holding the lock does not take it again.
+
+use super::spinlock::Spinlock;
+use crate::interrupt::cpu::mask_interrupts;
+use crate::interrupt::cpu::restore_interrupts;
+use crate::multicore::cpu_id;
+use core::sync::atomic::AtomicU32;
+use core::sync::atomic::Ordering;
+
+/// Restore state's bits holding the control field of program status register.
+const CONTROL_MASK: u32 = 0xFF;
+
+/// Restore state's bit which is set when critical section was already held by this core.
+const NESTED: u32 = 1 << 8;
+
+/// Value of [`OWNER`] when no core holds the lock.
+const NO_OWNER: u32 = u32::MAX;
+
+/// Held while any core is in a critical section.
+static LOCK: Spinlock = Spinlock::new();
+
+/// Identifier of the core holding [`LOCK`].
+static OWNER: AtomicU32 = AtomicU32::new(NO_OWNER);
+
+/// Critical section of the board.
+struct CriticalSection;
+
+critical_section::set_impl!(CriticalSection);
+
+unsafe impl critical_section::Impl for CriticalSection {
+    unsafe fn acquire() -> critical_section::RawRestoreState {
+        let cpsr = mask_interrupts() & CONTROL_MASK;
+        let cpu = cpu_id();
+        // Only this core can have stored its own identifier, and interrupts are masked.
+        if OWNER.load(Ordering::Relaxed) == cpu {
+            return cpsr | NESTED;
+        }
+        LOCK.lock();
+        OWNER.store(cpu, Ordering::Relaxed);
+        cpsr
+    }
+
+    unsafe fn release(state: critical_section::RawRestoreState) {
+        if state & NESTED == 0 {
+            OWNER.store(NO_OWNER, Ordering::Relaxed);
+            LOCK.unlock();
+        }
+        restore_interrupts(state & CONTROL_MASK);
+    }
+}
@@ -0,0 +1,84 @@
+//! Mutual exclusion between interrupt handlers and cores.
+
+use super::spinlock::Spinlock;
+use crate::interrupt::cpu::mask_interrupts;
+use crate::interrupt::cpu::restore_interrupts;
+use core::cell::UnsafeCell;
+
+/// Protects value from concurrent access.
+///
+/// Value is accessed with IRQ and FIQ masked and spinlock taken,
+/// so neither interrupt handlers of the same core nor the other core can access it meanwhile.
+///
+/// Locking the same mutex again inside the closure deadlocks.
+pub struct Mutex<T> {
+    /// Taken while value is accessed.
+    lock: Spinlock,
+
+    /// Protected value.
+    value: UnsafeCell<T>,
+}
+
+// Safety:
+// Value is only accessed by one context at a time.
+unsafe impl<T: Send> Sync for Mutex<T> {}
+
+impl<T> Mutex<T> {
+    /// Create unlocked mutex.
+    #[must_use]
+    pub const fn new(value: T) -> Self {
+        Self {
+            lock: Spinlock::new(),
+            value: UnsafeCell::new(value),
+        }
+    }
+
+    /// Run closure with exclusive access to value.
+    ///
+    /// Waits until the other core has released the value.
+    #[inline]
+    pub fn lock<R>(&self, f: impl FnOnce(&mut T) -> R) -> R {
+        let cpsr = mask_interrupts();
+        self.lock.lock();
+        // Safety:
+        // Interrupts are masked and spinlock is held, so no other reference to the value exists.
+        let result = f(unsafe { &mut *self.value.get() });
+        self.lock.unlock();
+        restore_interrupts(cpsr);
+        result
+    }
+
+    /// Run closure with exclusive access to value if the value is not locked.
+    ///
+    /// Returns `None` if the value is locked.
+    #[inline]
+    pub fn try_lock<R>(&self, f: impl FnOnce(&mut T) -> R) -> Option<R> {
+        let cpsr = mask_interrupts();
+        if !self.lock.try_lock() {
+            restore_interrupts(cpsr);
+            return None;
+        }
+        // Safety:
+        // Interrupts are masked and spinlock is held, so no other reference to the value exists.
+        let result = f(unsafe { &mut *self.value.get() });
+        self.lock.unlock();
+        restore_interrupts(cpsr);
+        Some(result)
+    }
+
+    /// Get value without locking.
+    ///
+    /// Exclusive reference guarantees that nothing else accesses the value.
+    #[inline]
+    #[must_use]
+    pub fn get_mut(&mut self) -> &mut T {
+        self.value.get_mut()
+    }
+
+    /// Take value out of mutex.
+    #[inline]
+    #[must_use]
+    pub fn into_inner(self) -> T {
+        self.value.into_inner()
+    }
+}
@@ -0,0 +1,149 @@
+//! Spinlock.
+//!
+//! Lock is taken with exclusive load and store instructions,
+//! which work between cores only if the lock resides in normal, shareable memory.
+//! Startup code maps memory so, and enables caches and coherency between cores, see `runtime.S`.
+//! Waiting core sleeps until an event is sent by unlocking core.
+
+use core::sync::atomic::AtomicU32;
+#[cfg(not(target_arch = "arm"))]
+use core::sync::atomic::Ordering;
+
+/// Lock value when lock is free.
+const UNLOCKED: u32 = 0;
+
+/// Lock value when lock is taken.
+const LOCKED: u32 = 1;
+
+/// Spinlock.
+///
+/// Spinlock does not mask interrupts,
+/// so an interrupt handler taking a lock held by the interrupted code deadlocks.
+/// Use [`Mutex`](super::mutex::Mutex) to share state with interrupt handlers.
+pub struct Spinlock {
+    /// Lock value.
+    state: AtomicU32,
+}
+
+impl Spinlock {
+    /// Create unlocked spinlock.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            state: AtomicU32::new(UNLOCKED),
+        }
+    }
+
+    /// Take lock, wait until it is free.
+    #[inline]
+    pub fn lock(&self) {
+        #[cfg(target_arch = "arm")]
+        // Safety:
+        // Only lock value is accessed.
+        unsafe {
+            core::arch::asm!(
+                "1:",
+                "ldrex {value}, [{state}]",
+                // Sleep until lock is released.
+                "cmp {value}, #{unlocked}",
+                "wfene",
+                "bne 1b",
+                "strex {value}, {locked}, [{state}]",
+                // Retry if another core took the lock in between.
+                "cmp {value}, #0",
+                "bne 1b",
+                // Accesses to protected state happen after taking the lock.
+                "dmb",
+                state = in(reg) self.state.as_ptr(),
+                value = out(reg) _,
+                unlocked = const UNLOCKED,
+                locked = in(reg) LOCKED,
+                options(nostack),
+            );
+        }
+        #[cfg(not(target_arch = "arm"))]
+        while !self.try_lock() {
+            core::hint::spin_loop();
+        }
+    }
+
+    /// Take lock if it is free.
+    ///
+    /// Returns true if lock was taken.
+    #[inline]
+    #[must_use]
+    pub fn try_lock(&self) -> bool {
+        #[cfg(target_arch = "arm")]
+        {
+            let value: u32;
+            // Safety:
+            // Only lock value is accessed.
+            unsafe {
+                core::arch::asm!(
+                    "1:",
+                    "ldrex {value}, [{state}]",
+                    "cmp {value}, #{unlocked}",
+                    "bne 2f",
+                    "strex {value}, {locked}, [{state}]",
+                    // Retry if another core accessed the lock in between.
+                    "cmp {value}, #0",
+                    "bne 1b",
+                    "dmb",
+                    "b 3f",
+                    // Lock is taken, clear exclusive access.
+                    "2:",
+                    "clrex",
+                    "3:",
+                    state = in(reg) self.state.as_ptr(),
+                    value = out(reg) value,
+                    unlocked = const UNLOCKED,
+                    locked = in(reg) LOCKED,
+                    options(nostack),
+                );
+            }
+            value == UNLOCKED
+        }
+        #[cfg(not(target_arch = "arm"))]
+        self.state
+            .compare_exchange(UNLOCKED, LOCKED, Ordering::Acquire, Ordering::Relaxed)
+            .is_ok()
+    }
+
+    /// Release lock.
+    ///
+    /// Lock must be held by the caller.
+    #[inline]
+    pub fn unlock(&self) {
+        #[cfg(target_arch = "arm")]
+        // Safety:
+        // Only lock value is accessed.
+        unsafe {
+            core::arch::asm!(
+                // Accesses to protected state happen before releasing the lock.
+                "dmb",
+                "str {unlocked}, [{state}]",
+                // Wake up waiting core after lock value is visible.
+                "dsb",
+                "sev",
+                state = in(reg) self.state.as_ptr(),
+                unlocked = in(reg) UNLOCKED,
+                options(nostack),
+            );
+        }
+        #[cfg(not(target_arch = "arm"))]
+        self.state.store(UNLOCKED, Ordering::Release);
+    }
+
+    /// True if lock is taken.
+    #[inline]
+    #[must_use]
+    pub fn is_locked(&self) -> bool {
+        self.state.load(core::sync::atomic::Ordering::Relaxed) != UNLOCKED
+    }
+}
+
+impl Default for Spinlock {
+    fn default() -> Self {
+        Self::new()
+    }
+}